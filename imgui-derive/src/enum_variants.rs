@@ -0,0 +1,91 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, Result, Variant};
+
+/// The options of a variant, from its `#[imgui(...)]` attributes
+#[derive(Default)]
+struct VariantOptions {
+    label: Option<LitStr>,
+    description: Option<LitStr>,
+    skip: bool,
+}
+
+impl VariantOptions {
+    fn parse(variant: &Variant) -> Result<Self> {
+        let mut options = VariantOptions::default();
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("imgui"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    options.label = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("description") {
+                    options.description = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("expected `label`, `description` or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "EnumVariants can only be derived for enums",
+        ));
+    };
+
+    let mut listed = Vec::new();
+    let mut labels = Vec::new();
+    let mut descriptions = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "EnumVariants can only be derived for variants without fields",
+            ));
+        }
+        let options = VariantOptions::parse(variant)?;
+        let ident = &variant.ident;
+        let label = options
+            .label
+            .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+        labels.push(quote!(Self::#ident => #label));
+        let description = match options.description {
+            Some(description) => quote!(::core::option::Option::Some(#description)),
+            None => quote!(::core::option::Option::None),
+        };
+        descriptions.push(quote!(Self::#ident => #description));
+        if !options.skip {
+            listed.push(quote!(Self::#ident));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::imgui::EnumVariants for #name #ty_generics #where_clause {
+            const VARIANTS: &'static [Self] = &[#(#listed),*];
+
+            fn label(&self) -> &'static str {
+                match *self {
+                    #(#labels,)*
+                }
+            }
+
+            fn description(&self) -> ::core::option::Option<&'static str> {
+                match *self {
+                    #(#descriptions,)*
+                }
+            }
+        }
+    })
+}
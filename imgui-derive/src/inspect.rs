@@ -0,0 +1,154 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::ParseStream;
+use syn::{parse_quote, Data, DeriveInput, Field, Index, Lit, LitStr, Path, Result, Token};
+
+/// The options of a field, from its `#[inspect(...)]` attributes
+#[derive(Default)]
+struct FieldOptions {
+    label: Option<LitStr>,
+    range: Option<(TokenStream, TokenStream)>,
+    speed: Option<TokenStream>,
+    read_only: bool,
+    tooltip: Option<LitStr>,
+    color: bool,
+    open: bool,
+    with: Option<Path>,
+    skip: bool,
+}
+
+impl FieldOptions {
+    fn parse(field: &Field) -> Result<Self> {
+        let mut options = FieldOptions::default();
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("inspect"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    options.label = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("range") {
+                    let input = meta.value()?;
+                    let min = parse_number(input)?;
+                    input.parse::<Token![..=]>()?;
+                    options.range = Some((min, parse_number(input)?));
+                } else if meta.path.is_ident("speed") {
+                    options.speed = Some(parse_number(meta.value()?)?);
+                } else if meta.path.is_ident("read_only") {
+                    options.read_only = true;
+                } else if meta.path.is_ident("tooltip") {
+                    options.tooltip = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("color") {
+                    options.color = true;
+                } else if meta.path.is_ident("open") {
+                    options.open = true;
+                } else if meta.path.is_ident("with") {
+                    let path: LitStr = meta.value()?.parse()?;
+                    options.with = Some(path.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error(
+                        "expected `label`, `range`, `speed`, `read_only`, `tooltip`, `color`, \
+                         `open`, `with` or `skip`",
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// Parses a number literal, possibly negative, or the path of a constant
+fn parse_number(input: ParseStream) -> Result<TokenStream> {
+    if input.peek(Token![-]) || input.peek(Lit) {
+        let minus: Option<Token![-]> = input.parse()?;
+        let lit: Lit = input.parse()?;
+        Ok(quote!(#minus #lit))
+    } else {
+        Ok(input.parse::<Path>()?.into_token_stream())
+    }
+}
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Inspect can only be derived for structs",
+        ));
+    };
+
+    let mut rows = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let options = FieldOptions::parse(field)?;
+        if options.skip {
+            continue;
+        }
+        let (member, name) = match &field.ident {
+            Some(ident) => (ident.to_token_stream(), ident.to_string()),
+            None => (Index::from(index).to_token_stream(), index.to_string()),
+        };
+        let label = options
+            .label
+            .unwrap_or_else(|| LitStr::new(&name, proc_macro2::Span::call_site()));
+        let range = match options.range {
+            Some((min, max)) => {
+                quote!(::core::option::Option::Some([(#min) as f64, (#max) as f64]))
+            }
+            None => quote!(::core::option::Option::None),
+        };
+        let speed = match options.speed {
+            Some(speed) => quote!(::core::option::Option::Some((#speed) as f32)),
+            None => quote!(::core::option::Option::None),
+        };
+        let tooltip = match options.tooltip {
+            Some(tooltip) => quote!(::core::option::Option::Some(#tooltip)),
+            None => quote!(::core::option::Option::None),
+        };
+        let read_only = options.read_only;
+        let color = options.color;
+        let open = options.open;
+        let field_options = quote! {
+            ::imgui::InspectOptions {
+                range: #range,
+                speed: #speed,
+                read_only: #read_only || options.read_only,
+                tooltip: #tooltip,
+                color: #color,
+                default_open: #open,
+            }
+        };
+        rows.push(match options.with {
+            Some(with) => quote! {
+                ui.inspect_row(#label, &#field_options, |id| {
+                    #with(ui, id, &mut self.#member)
+                })
+            },
+            None => quote! {
+                ::imgui::Inspect::inspect(&mut self.#member, ui, #label, &#field_options)
+            },
+        });
+    }
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::imgui::Inspect));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::imgui::Inspect for #name #ty_generics #where_clause {
+            fn inspect(
+                &mut self,
+                ui: &::imgui::Ui,
+                label: &str,
+                options: &::imgui::InspectOptions,
+            ) -> bool {
+                // Every row is shown, even after an edited one
+                ui.inspect_node(label, options, || false #(| #rows)*)
+            }
+        }
+    })
+}
@@ -0,0 +1,107 @@
+//! Derive macros for [imgui-rs](https://docs.rs/imgui), enabled with its `derive` feature.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod enum_variants;
+mod inspect;
+
+/// Derives `imgui::EnumVariants` for an enum whose variants have no fields.
+///
+/// Each variant is labeled with its name unless a label is given. The `#[imgui(...)]` attribute
+/// of a variant accepts:
+///
+/// - `label = "..."`: the text shown for the variant
+/// - `description = "..."`: the tooltip of the variant
+/// - `skip`: leaves the variant out of the choices
+///
+/// ```
+/// use imgui::EnumVariants;
+///
+/// #[derive(Copy, Clone, PartialEq, EnumVariants)]
+/// enum Filter {
+///     Nearest,
+///     #[imgui(label = "Bilinear", description = "Blends the four nearest texels")]
+///     Linear,
+///     #[imgui(skip)]
+///     Unsupported,
+/// }
+///
+/// assert_eq!(Filter::VARIANTS.len(), 2);
+/// assert_eq!(Filter::Nearest.label(), "Nearest");
+/// assert_eq!(Filter::Linear.label(), "Bilinear");
+/// assert_eq!(Filter::Nearest.description(), None);
+/// ```
+#[proc_macro_derive(EnumVariants, attributes(imgui))]
+pub fn derive_enum_variants(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    enum_variants::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `imgui::Inspect` for a struct whose fields implement it, showing each field in a row
+/// of the inspector under a collapsible tree node.
+///
+/// Each field is labeled with its name unless a label is given. The `#[inspect(...)]` attribute
+/// of a field accepts:
+///
+/// - `label = "..."`: the text shown for the field
+/// - `range = min..=max`: the range of a number, edited with a slider unless a speed is set
+/// - `speed = x`: the change of a dragged number per pixel
+/// - `read_only`: shows the field without letting it be edited
+/// - `tooltip = "..."`: the tooltip of the label
+/// - `color`: edits arrays and vectors of 3 or 4 floats as colors
+/// - `open`: opens the tree node of a nested value the first time it's shown
+/// - `with = "path"`: edits the field with a function `fn(&Ui, &str, &mut T) -> bool`, passed
+///   the hidden label of the widget
+/// - `skip`: leaves the field out of the inspector
+///
+/// Bounds and speeds are number literals or paths of constants. Fields of read-only structs are
+/// read-only too.
+///
+/// ```
+/// use imgui::{AngleSlider, Inspect, Ui};
+///
+/// const MAX_INTENSITY: f32 = 10.0;
+///
+/// #[derive(Inspect)]
+/// struct Light {
+///     #[inspect(tooltip = "Shown in the scene tree")]
+///     name: String,
+///     #[inspect(color)]
+///     color: [f32; 3],
+///     #[inspect(range = 0.0..=MAX_INTENSITY)]
+///     intensity: f32,
+///     #[inspect(speed = 0.1, range = -100..=100)]
+///     position: mint::Vector3<f32>,
+///     #[inspect(label = "ID", read_only)]
+///     id: u64,
+///     #[inspect(with = "angle_slider")]
+///     spot_angle: f32,
+///     #[inspect(open)]
+///     shadow: Shadow,
+///     #[inspect(skip)]
+///     dirty: bool,
+/// }
+///
+/// #[derive(Default, Inspect)]
+/// struct Shadow(bool, Option<u32>);
+///
+/// fn angle_slider(ui: &Ui, id: &str, value: &mut f32) -> bool {
+///     AngleSlider::new(id).build(ui, value)
+/// }
+///
+/// fn light_panel(ui: &Ui, light: &mut Light) {
+///     if ui.inspect("Light", light) {
+///         light.dirty = true;
+///     }
+/// }
+/// ```
+#[proc_macro_derive(Inspect, attributes(inspect))]
+pub fn derive_inspect(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    inspect::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
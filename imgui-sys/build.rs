@@ -3,6 +3,10 @@
 const DEFINES: &[(&str, Option<&str>)] = &[
     // Rust `char` is a unicode scalar value, e.g. 32 bits.
     ("IMGUI_USE_WCHAR32", None),
+    // cimgui's generated structs (and so our bindings) assume the obsolete API is compiled
+    // out. Without this, `ImGuiIO` has extra legacy key arrays and the fields after
+    // `KeysData` end up at different offsets in C++ and Rust.
+    ("IMGUI_DISABLE_OBSOLETE_FUNCTIONS", None),
     // Disabled due to linking issues
     ("CIMGUI_NO_EXPORT", None),
     ("IMGUI_DISABLE_WIN32_FUNCTIONS", None),
@@ -0,0 +1,202 @@
+// cimgui only generates wrappers for Dear ImGui's internal API (imgui_internal.h) in the docking
+// branch. This provides the subset imgui-rs uses for the master branch, with the same names and
+// signatures as the generated docking wrappers, so the Rust side can call them the same way with
+// or without the docking feature.
+//
+// Included at the end of the include_imgui_master*.cpp files, after cimgui.cpp, which already
+// includes imgui_internal.h and defines CIMGUI_API.
+
+// Inputs: key ownership and shortcut routing
+
+CIMGUI_API ImGuiID igGetKeyOwner(ImGuiKey key)
+{
+    return ImGui::GetKeyOwner(key);
+}
+CIMGUI_API void igSetKeyOwner(ImGuiKey key,ImGuiID owner_id,ImGuiInputFlags flags)
+{
+    return ImGui::SetKeyOwner(key,owner_id,flags);
+}
+CIMGUI_API void igSetKeyOwnersForKeyChord(ImGuiKeyChord key,ImGuiID owner_id,ImGuiInputFlags flags)
+{
+    return ImGui::SetKeyOwnersForKeyChord(key,owner_id,flags);
+}
+CIMGUI_API void igSetItemKeyOwner_InputFlags(ImGuiKey key,ImGuiInputFlags flags)
+{
+    return ImGui::SetItemKeyOwner(key,flags);
+}
+CIMGUI_API bool igTestKeyOwner(ImGuiKey key,ImGuiID owner_id)
+{
+    return ImGui::TestKeyOwner(key,owner_id);
+}
+CIMGUI_API bool igIsKeyDown_ID(ImGuiKey key,ImGuiID owner_id)
+{
+    return ImGui::IsKeyDown(key,owner_id);
+}
+CIMGUI_API bool igIsKeyPressed_InputFlags(ImGuiKey key,ImGuiInputFlags flags,ImGuiID owner_id)
+{
+    return ImGui::IsKeyPressed(key,flags,owner_id);
+}
+CIMGUI_API bool igIsKeyReleased_ID(ImGuiKey key,ImGuiID owner_id)
+{
+    return ImGui::IsKeyReleased(key,owner_id);
+}
+CIMGUI_API bool igIsKeyChordPressed_InputFlags(ImGuiKeyChord key_chord,ImGuiInputFlags flags,ImGuiID owner_id)
+{
+    return ImGui::IsKeyChordPressed(key_chord,flags,owner_id);
+}
+CIMGUI_API bool igShortcut_ID(ImGuiKeyChord key_chord,ImGuiInputFlags flags,ImGuiID owner_id)
+{
+    return ImGui::Shortcut(key_chord,flags,owner_id);
+}
+CIMGUI_API bool igSetShortcutRouting(ImGuiKeyChord key_chord,ImGuiInputFlags flags,ImGuiID owner_id)
+{
+    return ImGui::SetShortcutRouting(key_chord,flags,owner_id);
+}
+CIMGUI_API bool igTestShortcutRouting(ImGuiKeyChord key_chord,ImGuiID owner_id)
+{
+    return ImGui::TestShortcutRouting(key_chord,owner_id);
+}
+
+// Typing-select
+
+CIMGUI_API ImGuiTypingSelectRequest* igGetTypingSelectRequest(ImGuiTypingSelectFlags flags)
+{
+    return ImGui::GetTypingSelectRequest(flags);
+}
+CIMGUI_API int igTypingSelectFindMatch(ImGuiTypingSelectRequest* req,int items_count,const char*(*get_item_name_func)(void*,int),void* user_data,int nav_item_idx)
+{
+    return ImGui::TypingSelectFindMatch(req,items_count,get_item_name_func,user_data,nav_item_idx);
+}
+CIMGUI_API int igTypingSelectFindNextSingleCharMatch(ImGuiTypingSelectRequest* req,int items_count,const char*(*get_item_name_func)(void*,int),void* user_data,int nav_item_idx)
+{
+    return ImGui::TypingSelectFindNextSingleCharMatch(req,items_count,get_item_name_func,user_data,nav_item_idx);
+}
+CIMGUI_API int igTypingSelectFindBestLeadingMatch(ImGuiTypingSelectRequest* req,int items_count,const char*(*get_item_name_func)(void*,int),void* user_data)
+{
+    return ImGui::TypingSelectFindBestLeadingMatch(req,items_count,get_item_name_func,user_data);
+}
+
+// Widgets: items, data types and text input
+
+CIMGUI_API void igItemSize_Rect(const ImRect bb,float text_baseline_y)
+{
+    return ImGui::ItemSize(bb,text_baseline_y);
+}
+CIMGUI_API bool igItemAdd(const ImRect bb,ImGuiID id,const ImRect* nav_bb,ImGuiItemFlags extra_flags)
+{
+    return ImGui::ItemAdd(bb,id,nav_bb,extra_flags);
+}
+CIMGUI_API bool igItemHoverable(const ImRect bb,ImGuiID id,ImGuiItemFlags item_flags)
+{
+    return ImGui::ItemHoverable(bb,id,item_flags);
+}
+CIMGUI_API void igKeepAliveID(ImGuiID id)
+{
+    return ImGui::KeepAliveID(id);
+}
+CIMGUI_API void igMarkItemEdited(ImGuiID id)
+{
+    return ImGui::MarkItemEdited(id);
+}
+CIMGUI_API int igDataTypeFormatString(char* buf,int buf_size,ImGuiDataType data_type,const void* p_data,const char* format)
+{
+    return ImGui::DataTypeFormatString(buf,buf_size,data_type,p_data,format);
+}
+CIMGUI_API void igDataTypeApplyOp(ImGuiDataType data_type,int op,void* output,const void* arg_1,const void* arg_2)
+{
+    return ImGui::DataTypeApplyOp(data_type,op,output,arg_1,arg_2);
+}
+CIMGUI_API bool igDataTypeApplyFromText(const char* buf,ImGuiDataType data_type,void* p_data,const char* format,void* p_data_when_empty)
+{
+    return ImGui::DataTypeApplyFromText(buf,data_type,p_data,format,p_data_when_empty);
+}
+CIMGUI_API int igDataTypeCompare(ImGuiDataType data_type,const void* arg_1,const void* arg_2)
+{
+    return ImGui::DataTypeCompare(data_type,arg_1,arg_2);
+}
+CIMGUI_API bool igDataTypeClamp(ImGuiDataType data_type,void* p_data,const void* p_min,const void* p_max)
+{
+    return ImGui::DataTypeClamp(data_type,p_data,p_min,p_max);
+}
+CIMGUI_API bool igTempInputText(const ImRect bb,ImGuiID id,const char* label,char* buf,int buf_size,ImGuiInputTextFlags flags)
+{
+    return ImGui::TempInputText(bb,id,label,buf,buf_size,flags);
+}
+CIMGUI_API bool igTempInputIsActive(ImGuiID id)
+{
+    return ImGui::TempInputIsActive(id);
+}
+CIMGUI_API ImGuiInputTextState* igGetInputTextState(ImGuiID id)
+{
+    return ImGui::GetInputTextState(id);
+}
+CIMGUI_API void ImGuiInputTextState_ReloadUserBufAndSelectAll(ImGuiInputTextState* self)
+{
+    return self->ReloadUserBufAndSelectAll();
+}
+
+// Widgets: behaviors, for custom widgets
+
+CIMGUI_API bool igButtonBehavior(const ImRect bb,ImGuiID id,bool* out_hovered,bool* out_held,ImGuiButtonFlags flags)
+{
+    return ImGui::ButtonBehavior(bb,id,out_hovered,out_held,flags);
+}
+CIMGUI_API bool igDragBehavior(ImGuiID id,ImGuiDataType data_type,void* p_v,float v_speed,const void* p_min,const void* p_max,const char* format,ImGuiSliderFlags flags)
+{
+    return ImGui::DragBehavior(id,data_type,p_v,v_speed,p_min,p_max,format,flags);
+}
+CIMGUI_API bool igSliderBehavior(const ImRect bb,ImGuiID id,ImGuiDataType data_type,void* p_v,const void* p_min,const void* p_max,const char* format,ImGuiSliderFlags flags,ImRect* out_grab_bb)
+{
+    return ImGui::SliderBehavior(bb,id,data_type,p_v,p_min,p_max,format,flags,out_grab_bb);
+}
+CIMGUI_API void igRenderNavHighlight(const ImRect bb,ImGuiID id,ImGuiNavHighlightFlags flags)
+{
+    return ImGui::RenderNavHighlight(bb,id,flags);
+}
+
+// Widgets: combo box preview
+
+CIMGUI_API bool igBeginComboPreview()
+{
+    return ImGui::BeginComboPreview();
+}
+CIMGUI_API void igEndComboPreview()
+{
+    return ImGui::EndComboPreview();
+}
+
+// Widgets: plots, returning the hovered index
+
+CIMGUI_API int igPlotEx(ImGuiPlotType plot_type,const char* label,float(*values_getter)(void* data,int idx),void* data,int values_count,int values_offset,const char* overlay_text,float scale_min,float scale_max,const ImVec2 size_arg)
+{
+    return ImGui::PlotEx(plot_type,label,values_getter,data,values_count,values_offset,overlay_text,scale_min,scale_max,size_arg);
+}
+
+// Widgets: splitter
+
+CIMGUI_API bool igSplitterBehavior(const ImRect bb,ImGuiID id,ImGuiAxis axis,float* size1,float* size2,float min_size1,float min_size2,float hover_extend,float hover_visibility_delay,ImU32 bg_col)
+{
+    return ImGui::SplitterBehavior(bb,id,axis,size1,size2,min_size1,min_size2,hover_extend,hover_visibility_delay,bg_col);
+}
+
+// Settings: .ini handlers
+
+CIMGUI_API ImGuiID igImHashStr(const char* data,size_t data_size,ImGuiID seed)
+{
+    return ImHashStr(data,data_size,seed);
+}
+CIMGUI_API void igAddSettingsHandler(const ImGuiSettingsHandler* handler)
+{
+    return ImGui::AddSettingsHandler(handler);
+}
+CIMGUI_API void igMarkIniSettingsDirty_Nil()
+{
+    return ImGui::MarkIniSettingsDirty();
+}
+
+// Popups: IsPopupOpen() is overloaded in the docking branch, so its public wrapper gets a suffix
+
+CIMGUI_API bool igIsPopupOpen_Str(const char* str_id,ImGuiPopupFlags flags)
+{
+    return ImGui::IsPopupOpen(str_id,flags);
+}
@@ -8,3 +8,4 @@
 #include "./third-party/imgui-master/imgui/imgui_widgets.cpp"
 #include "./third-party/imgui-master/imgui/imgui_tables.cpp"
 #include "./third-party/imgui-master/cimgui.cpp"
+#include "./cimgui_internal.cpp"
@@ -8,5 +8,6 @@
 #include "./third-party/imgui-master-freetype/imgui/imgui_widgets.cpp"
 #include "./third-party/imgui-master-freetype/imgui/imgui_tables.cpp"
 #include "./third-party/imgui-master-freetype/cimgui.cpp"
+#include "./cimgui_internal.cpp"
 
 #include "./third-party/imgui-master-freetype/imgui/misc/freetype/imgui_freetype.cpp"
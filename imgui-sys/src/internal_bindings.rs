@@ -0,0 +1,336 @@
+//! Declarations for `cimgui_internal.cpp`, the parts of Dear ImGui's internal API that cimgui only
+//! generates for the docking branch. Names, signatures and values match the generated docking
+//! bindings.
+
+#![allow(nonstandard_style, clippy::all)]
+
+use crate::{
+    ImGuiButtonFlags, ImGuiContext, ImGuiDataType, ImGuiID, ImGuiInputFlags, ImGuiInputTextFlags,
+    ImGuiItemFlags, ImGuiKey, ImGuiKeyChord, ImGuiPopupFlags, ImGuiSliderFlags, ImGuiTextBuffer,
+    ImS8, ImU32, ImVec2,
+};
+
+pub const ImGuiInputFlags_RepeatRateDefault: ImGuiInputFlagsPrivate_ = 2;
+pub const ImGuiInputFlags_RepeatRateNavMove: ImGuiInputFlagsPrivate_ = 4;
+pub const ImGuiInputFlags_RepeatRateNavTweak: ImGuiInputFlagsPrivate_ = 8;
+pub const ImGuiInputFlags_RepeatUntilRelease: ImGuiInputFlagsPrivate_ = 16;
+pub const ImGuiInputFlags_RepeatUntilKeyModsChange: ImGuiInputFlagsPrivate_ = 32;
+pub const ImGuiInputFlags_RepeatUntilKeyModsChangeFromNone: ImGuiInputFlagsPrivate_ = 64;
+pub const ImGuiInputFlags_RepeatUntilOtherKeyPress: ImGuiInputFlagsPrivate_ = 128;
+pub const ImGuiInputFlags_LockThisFrame: ImGuiInputFlagsPrivate_ = 1048576;
+pub const ImGuiInputFlags_LockUntilRelease: ImGuiInputFlagsPrivate_ = 2097152;
+pub const ImGuiInputFlags_CondHovered: ImGuiInputFlagsPrivate_ = 4194304;
+pub const ImGuiInputFlags_CondActive: ImGuiInputFlagsPrivate_ = 8388608;
+pub type ImGuiInputFlagsPrivate_ = core::ffi::c_uint;
+
+pub const ImGuiItemFlags_Disabled: ImGuiItemFlagsPrivate_ = 1024;
+pub const ImGuiItemFlags_ReadOnly: ImGuiItemFlagsPrivate_ = 2048;
+pub const ImGuiItemFlags_MixedValue: ImGuiItemFlagsPrivate_ = 4096;
+pub const ImGuiItemFlags_NoWindowHoverableCheck: ImGuiItemFlagsPrivate_ = 8192;
+pub const ImGuiItemFlags_AllowOverlap: ImGuiItemFlagsPrivate_ = 16384;
+pub const ImGuiItemFlags_Inputable: ImGuiItemFlagsPrivate_ = 1048576;
+pub const ImGuiItemFlags_HasSelectionUserData: ImGuiItemFlagsPrivate_ = 2097152;
+pub const ImGuiItemFlags_IsMultiSelect: ImGuiItemFlagsPrivate_ = 4194304;
+pub const ImGuiItemFlags_Default_: ImGuiItemFlagsPrivate_ = 16;
+pub type ImGuiItemFlagsPrivate_ = core::ffi::c_uint;
+pub const ImGuiInputTextFlags_Multiline: ImGuiInputTextFlagsPrivate_ = 67108864;
+pub const ImGuiInputTextFlags_NoMarkEdited: ImGuiInputTextFlagsPrivate_ = 134217728;
+pub const ImGuiInputTextFlags_MergedItem: ImGuiInputTextFlagsPrivate_ = 268435456;
+pub const ImGuiInputTextFlags_LocalizeDecimalPoint: ImGuiInputTextFlagsPrivate_ = 536870912;
+pub type ImGuiInputTextFlagsPrivate_ = core::ffi::c_uint;
+pub const ImGuiButtonFlags_PressedOnClick: ImGuiButtonFlagsPrivate_ = 16;
+pub const ImGuiButtonFlags_PressedOnClickRelease: ImGuiButtonFlagsPrivate_ = 32;
+pub const ImGuiButtonFlags_PressedOnClickReleaseAnywhere: ImGuiButtonFlagsPrivate_ = 64;
+pub const ImGuiButtonFlags_PressedOnRelease: ImGuiButtonFlagsPrivate_ = 128;
+pub const ImGuiButtonFlags_PressedOnDoubleClick: ImGuiButtonFlagsPrivate_ = 256;
+pub const ImGuiButtonFlags_PressedOnDragDropHold: ImGuiButtonFlagsPrivate_ = 512;
+pub const ImGuiButtonFlags_Repeat: ImGuiButtonFlagsPrivate_ = 1024;
+pub const ImGuiButtonFlags_FlattenChildren: ImGuiButtonFlagsPrivate_ = 2048;
+pub const ImGuiButtonFlags_AllowOverlap: ImGuiButtonFlagsPrivate_ = 4096;
+pub const ImGuiButtonFlags_DontClosePopups: ImGuiButtonFlagsPrivate_ = 8192;
+pub const ImGuiButtonFlags_AlignTextBaseLine: ImGuiButtonFlagsPrivate_ = 32768;
+pub const ImGuiButtonFlags_NoKeyModifiers: ImGuiButtonFlagsPrivate_ = 65536;
+pub const ImGuiButtonFlags_NoHoldingActiveId: ImGuiButtonFlagsPrivate_ = 131072;
+pub const ImGuiButtonFlags_NoNavFocus: ImGuiButtonFlagsPrivate_ = 262144;
+pub const ImGuiButtonFlags_NoHoveredOnFocus: ImGuiButtonFlagsPrivate_ = 524288;
+pub const ImGuiButtonFlags_NoSetKeyOwner: ImGuiButtonFlagsPrivate_ = 1048576;
+pub const ImGuiButtonFlags_NoTestKeyOwner: ImGuiButtonFlagsPrivate_ = 2097152;
+pub const ImGuiButtonFlags_PressedOnMask_: ImGuiButtonFlagsPrivate_ = 1008;
+pub const ImGuiButtonFlags_PressedOnDefault_: ImGuiButtonFlagsPrivate_ = 32;
+pub type ImGuiButtonFlagsPrivate_ = core::ffi::c_uint;
+pub const ImGuiSliderFlags_Vertical: ImGuiSliderFlagsPrivate_ = 1048576;
+pub const ImGuiSliderFlags_ReadOnly: ImGuiSliderFlagsPrivate_ = 2097152;
+pub type ImGuiSliderFlagsPrivate_ = core::ffi::c_uint;
+pub const ImGuiNavHighlightFlags_None: ImGuiNavHighlightFlags_ = 0;
+pub const ImGuiNavHighlightFlags_Compact: ImGuiNavHighlightFlags_ = 2;
+pub const ImGuiNavHighlightFlags_AlwaysDraw: ImGuiNavHighlightFlags_ = 4;
+pub const ImGuiNavHighlightFlags_NoRounding: ImGuiNavHighlightFlags_ = 8;
+pub type ImGuiNavHighlightFlags_ = core::ffi::c_uint;
+pub type ImGuiNavHighlightFlags = core::ffi::c_int;
+pub const ImGuiComboFlags_CustomPreview: ImGuiComboFlagsPrivate_ = 1048576;
+pub type ImGuiComboFlagsPrivate_ = core::ffi::c_uint;
+pub const ImGuiPlotType_Lines: ImGuiPlotType = 0;
+pub const ImGuiPlotType_Histogram: ImGuiPlotType = 1;
+pub type ImGuiPlotType = core::ffi::c_uint;
+pub const ImGuiAxis_None: ImGuiAxis = -1;
+pub const ImGuiAxis_X: ImGuiAxis = 0;
+pub const ImGuiAxis_Y: ImGuiAxis = 1;
+pub type ImGuiAxis = core::ffi::c_int;
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ImRect {
+    pub Min: ImVec2,
+    pub Max: ImVec2,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ImGuiSettingsHandler {
+    pub TypeName: *const core::ffi::c_char,
+    pub TypeHash: ImGuiID,
+    pub ClearAllFn: ::core::option::Option<
+        unsafe extern "C" fn(ctx: *mut ImGuiContext, handler: *mut ImGuiSettingsHandler),
+    >,
+    pub ReadInitFn: ::core::option::Option<
+        unsafe extern "C" fn(ctx: *mut ImGuiContext, handler: *mut ImGuiSettingsHandler),
+    >,
+    pub ReadOpenFn: ::core::option::Option<
+        unsafe extern "C" fn(
+            ctx: *mut ImGuiContext,
+            handler: *mut ImGuiSettingsHandler,
+            name: *const core::ffi::c_char,
+        ) -> *mut core::ffi::c_void,
+    >,
+    pub ReadLineFn: ::core::option::Option<
+        unsafe extern "C" fn(
+            ctx: *mut ImGuiContext,
+            handler: *mut ImGuiSettingsHandler,
+            entry: *mut core::ffi::c_void,
+            line: *const core::ffi::c_char,
+        ),
+    >,
+    pub ApplyAllFn: ::core::option::Option<
+        unsafe extern "C" fn(ctx: *mut ImGuiContext, handler: *mut ImGuiSettingsHandler),
+    >,
+    pub WriteAllFn: ::core::option::Option<
+        unsafe extern "C" fn(
+            ctx: *mut ImGuiContext,
+            handler: *mut ImGuiSettingsHandler,
+            out_buf: *mut ImGuiTextBuffer,
+        ),
+    >,
+    pub UserData: *mut core::ffi::c_void,
+}
+impl Default for ImGuiSettingsHandler {
+    fn default() -> Self {
+        let mut s = ::core::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::core::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ImGuiInputTextState {
+    _unused: [u8; 0],
+}
+
+pub type ImGuiTypingSelectFlags = core::ffi::c_int;
+pub const ImGuiTypingSelectFlags_None: ImGuiTypingSelectFlags_ = 0;
+pub const ImGuiTypingSelectFlags_AllowBackspace: ImGuiTypingSelectFlags_ = 1;
+pub const ImGuiTypingSelectFlags_AllowSingleCharMode: ImGuiTypingSelectFlags_ = 2;
+pub type ImGuiTypingSelectFlags_ = core::ffi::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct ImGuiTypingSelectRequest {
+    pub Flags: ImGuiTypingSelectFlags,
+    pub SearchBufferLen: core::ffi::c_int,
+    pub SearchBuffer: *const core::ffi::c_char,
+    pub SelectRequest: bool,
+    pub SingleCharMode: bool,
+    pub SingleCharSize: ImS8,
+}
+
+#[cfg_attr(feature = "wasm", link(wasm_import_module = "imgui-sys-v0"))]
+extern "C" {
+    pub fn igGetKeyOwner(key: ImGuiKey) -> ImGuiID;
+    pub fn igSetKeyOwner(key: ImGuiKey, owner_id: ImGuiID, flags: ImGuiInputFlags);
+    pub fn igSetKeyOwnersForKeyChord(key: ImGuiKeyChord, owner_id: ImGuiID, flags: ImGuiInputFlags);
+    pub fn igSetItemKeyOwner_InputFlags(key: ImGuiKey, flags: ImGuiInputFlags);
+    pub fn igTestKeyOwner(key: ImGuiKey, owner_id: ImGuiID) -> bool;
+    pub fn igIsKeyDown_ID(key: ImGuiKey, owner_id: ImGuiID) -> bool;
+    pub fn igIsKeyPressed_InputFlags(
+        key: ImGuiKey,
+        flags: ImGuiInputFlags,
+        owner_id: ImGuiID,
+    ) -> bool;
+    pub fn igIsKeyReleased_ID(key: ImGuiKey, owner_id: ImGuiID) -> bool;
+    pub fn igIsKeyChordPressed_InputFlags(
+        key_chord: ImGuiKeyChord,
+        flags: ImGuiInputFlags,
+        owner_id: ImGuiID,
+    ) -> bool;
+    pub fn igShortcut_ID(
+        key_chord: ImGuiKeyChord,
+        flags: ImGuiInputFlags,
+        owner_id: ImGuiID,
+    ) -> bool;
+    pub fn igSetShortcutRouting(
+        key_chord: ImGuiKeyChord,
+        flags: ImGuiInputFlags,
+        owner_id: ImGuiID,
+    ) -> bool;
+    pub fn igTestShortcutRouting(key_chord: ImGuiKeyChord, owner_id: ImGuiID) -> bool;
+    pub fn igGetTypingSelectRequest(flags: ImGuiTypingSelectFlags)
+        -> *mut ImGuiTypingSelectRequest;
+    pub fn igTypingSelectFindMatch(
+        req: *mut ImGuiTypingSelectRequest,
+        items_count: core::ffi::c_int,
+        get_item_name_func: ::core::option::Option<
+            unsafe extern "C" fn(
+                arg1: *mut core::ffi::c_void,
+                arg2: core::ffi::c_int,
+            ) -> *const core::ffi::c_char,
+        >,
+        user_data: *mut core::ffi::c_void,
+        nav_item_idx: core::ffi::c_int,
+    ) -> core::ffi::c_int;
+    pub fn igTypingSelectFindNextSingleCharMatch(
+        req: *mut ImGuiTypingSelectRequest,
+        items_count: core::ffi::c_int,
+        get_item_name_func: ::core::option::Option<
+            unsafe extern "C" fn(
+                arg1: *mut core::ffi::c_void,
+                arg2: core::ffi::c_int,
+            ) -> *const core::ffi::c_char,
+        >,
+        user_data: *mut core::ffi::c_void,
+        nav_item_idx: core::ffi::c_int,
+    ) -> core::ffi::c_int;
+    pub fn igTypingSelectFindBestLeadingMatch(
+        req: *mut ImGuiTypingSelectRequest,
+        items_count: core::ffi::c_int,
+        get_item_name_func: ::core::option::Option<
+            unsafe extern "C" fn(
+                arg1: *mut core::ffi::c_void,
+                arg2: core::ffi::c_int,
+            ) -> *const core::ffi::c_char,
+        >,
+        user_data: *mut core::ffi::c_void,
+    ) -> core::ffi::c_int;
+    pub fn igItemSize_Rect(bb: ImRect, text_baseline_y: f32);
+    pub fn igItemAdd(
+        bb: ImRect,
+        id: ImGuiID,
+        nav_bb: *const ImRect,
+        extra_flags: ImGuiItemFlags,
+    ) -> bool;
+    pub fn igItemHoverable(bb: ImRect, id: ImGuiID, item_flags: ImGuiItemFlags) -> bool;
+    pub fn igKeepAliveID(id: ImGuiID);
+    pub fn igMarkItemEdited(id: ImGuiID);
+    pub fn igDataTypeFormatString(
+        buf: *mut core::ffi::c_char,
+        buf_size: core::ffi::c_int,
+        data_type: ImGuiDataType,
+        p_data: *const core::ffi::c_void,
+        format: *const core::ffi::c_char,
+    ) -> core::ffi::c_int;
+    pub fn igDataTypeApplyOp(
+        data_type: ImGuiDataType,
+        op: core::ffi::c_int,
+        output: *mut core::ffi::c_void,
+        arg_1: *const core::ffi::c_void,
+        arg_2: *const core::ffi::c_void,
+    );
+    pub fn igDataTypeApplyFromText(
+        buf: *const core::ffi::c_char,
+        data_type: ImGuiDataType,
+        p_data: *mut core::ffi::c_void,
+        format: *const core::ffi::c_char,
+        p_data_when_empty: *mut core::ffi::c_void,
+    ) -> bool;
+    pub fn igDataTypeCompare(
+        data_type: ImGuiDataType,
+        arg_1: *const core::ffi::c_void,
+        arg_2: *const core::ffi::c_void,
+    ) -> core::ffi::c_int;
+    pub fn igDataTypeClamp(
+        data_type: ImGuiDataType,
+        p_data: *mut core::ffi::c_void,
+        p_min: *const core::ffi::c_void,
+        p_max: *const core::ffi::c_void,
+    ) -> bool;
+    pub fn igTempInputText(
+        bb: ImRect,
+        id: ImGuiID,
+        label: *const core::ffi::c_char,
+        buf: *mut core::ffi::c_char,
+        buf_size: core::ffi::c_int,
+        flags: ImGuiInputTextFlags,
+    ) -> bool;
+    pub fn igTempInputIsActive(id: ImGuiID) -> bool;
+    pub fn igGetInputTextState(id: ImGuiID) -> *mut ImGuiInputTextState;
+    pub fn ImGuiInputTextState_ReloadUserBufAndSelectAll(self_: *mut ImGuiInputTextState);
+    pub fn igIsPopupOpen_Str(str_id: *const core::ffi::c_char, flags: ImGuiPopupFlags) -> bool;
+    pub fn igButtonBehavior(
+        bb: ImRect,
+        id: ImGuiID,
+        out_hovered: *mut bool,
+        out_held: *mut bool,
+        flags: ImGuiButtonFlags,
+    ) -> bool;
+    pub fn igDragBehavior(
+        id: ImGuiID,
+        data_type: ImGuiDataType,
+        p_v: *mut core::ffi::c_void,
+        v_speed: f32,
+        p_min: *const core::ffi::c_void,
+        p_max: *const core::ffi::c_void,
+        format: *const core::ffi::c_char,
+        flags: ImGuiSliderFlags,
+    ) -> bool;
+    pub fn igSliderBehavior(
+        bb: ImRect,
+        id: ImGuiID,
+        data_type: ImGuiDataType,
+        p_v: *mut core::ffi::c_void,
+        p_min: *const core::ffi::c_void,
+        p_max: *const core::ffi::c_void,
+        format: *const core::ffi::c_char,
+        flags: ImGuiSliderFlags,
+        out_grab_bb: *mut ImRect,
+    ) -> bool;
+    pub fn igRenderNavHighlight(bb: ImRect, id: ImGuiID, flags: ImGuiNavHighlightFlags);
+    pub fn igBeginComboPreview() -> bool;
+    pub fn igEndComboPreview();
+    pub fn igPlotEx(
+        plot_type: ImGuiPlotType,
+        label: *const core::ffi::c_char,
+        values_getter: ::core::option::Option<
+            unsafe extern "C" fn(data: *mut core::ffi::c_void, idx: core::ffi::c_int) -> f32,
+        >,
+        data: *mut core::ffi::c_void,
+        values_count: core::ffi::c_int,
+        values_offset: core::ffi::c_int,
+        overlay_text: *const core::ffi::c_char,
+        scale_min: f32,
+        scale_max: f32,
+        size_arg: ImVec2,
+    ) -> core::ffi::c_int;
+    pub fn igSplitterBehavior(
+        bb: ImRect,
+        id: ImGuiID,
+        axis: ImGuiAxis,
+        size1: *mut f32,
+        size2: *mut f32,
+        min_size1: f32,
+        min_size2: f32,
+        hover_extend: f32,
+        hover_visibility_delay: f32,
+        bg_col: ImU32,
+    ) -> bool;
+    pub fn igImHashStr(data: *const core::ffi::c_char, data_size: usize, seed: ImGuiID) -> ImGuiID;
+    pub fn igAddSettingsHandler(handler: *const ImGuiSettingsHandler);
+    pub fn igMarkIniSettingsDirty_Nil();
+}
@@ -52,6 +52,12 @@ cfg_if::cfg_if! {
     }
 }
 
+// The docking bindings already include Dear ImGui's internal API
+#[cfg(not(feature = "docking"))]
+mod internal_bindings;
+#[cfg(not(feature = "docking"))]
+pub use crate::internal_bindings::*;
+
 impl ImVec2 {
     #[inline]
     pub const fn new(x: f32, y: f32) -> ImVec2 {
@@ -0,0 +1,113 @@
+//! Little-endian reading and writing of the binary formats saved by imgui-rs, such as font atlas
+//! build caches and input recordings
+
+/// Writes values in little-endian order
+pub(crate) struct BinaryWriter(pub(crate) Vec<u8>);
+
+impl BinaryWriter {
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+    pub(crate) fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+    pub(crate) fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+    pub(crate) fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+    pub(crate) fn f32(&mut self, value: f32) {
+        self.u32(value.to_bits());
+    }
+    pub(crate) fn f32_2(&mut self, value: [f32; 2]) {
+        self.f32(value[0]);
+        self.f32(value[1]);
+    }
+}
+
+/// Reads values in little-endian order, returning `error` for truncated or malformed data
+pub(crate) struct BinaryReader<'a, E> {
+    data: &'a [u8],
+    error: E,
+}
+
+impl<'a, E: Copy> BinaryReader<'a, E> {
+    pub(crate) fn new(data: &'a [u8], error: E) -> Self {
+        BinaryReader { data, error }
+    }
+    /// Returns true if all the data was read
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub(crate) fn bytes(&mut self, len: usize) -> Result<&'a [u8], E> {
+        if self.data.len() < len {
+            return Err(self.error);
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], E> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+    pub(crate) fn u8(&mut self) -> Result<u8, E> {
+        Ok(self.bytes(1)?[0])
+    }
+    /// Reads a bool written as a 0 or 1 byte
+    pub(crate) fn bool(&mut self) -> Result<bool, E> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(self.error),
+        }
+    }
+    pub(crate) fn u16(&mut self) -> Result<u16, E> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+    pub(crate) fn u32(&mut self) -> Result<u32, E> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+    pub(crate) fn u64(&mut self) -> Result<u64, E> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+    pub(crate) fn f32(&mut self) -> Result<f32, E> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+    pub(crate) fn f32_2(&mut self) -> Result<[f32; 2], E> {
+        Ok([self.f32()?, self.f32()?])
+    }
+    /// Reads an element count, rejecting counts that can't possibly fit in the remaining data
+    pub(crate) fn count(&mut self) -> Result<usize, E> {
+        let count = self.u32()? as usize;
+        if count > self.data.len() {
+            return Err(self.error);
+        }
+        Ok(count)
+    }
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let mut w = BinaryWriter(Vec::new());
+    w.bytes(b"ab");
+    w.u8(1);
+    w.u16(0x0203);
+    w.u32(0x0405_0607);
+    w.u64(u64::MAX - 1);
+    w.f32_2([1.5, -2.0]);
+    assert_eq!(&w.0[..7], &[b'a', b'b', 1, 3, 2, 7, 6]);
+
+    let mut r = BinaryReader::new(&w.0, "invalid");
+    assert_eq!(r.bytes(2), Ok(&b"ab"[..]));
+    assert_eq!(r.bool(), Ok(true));
+    assert_eq!(r.u16(), Ok(0x0203));
+    assert_eq!(r.u32(), Ok(0x0405_0607));
+    assert_eq!(r.u64(), Ok(u64::MAX - 1));
+    assert_eq!(r.f32_2(), Ok([1.5, -2.0]));
+    assert!(r.is_empty());
+    assert_eq!(r.u8(), Err("invalid"));
+}
@@ -1,13 +1,19 @@
 use parking_lot::ReentrantMutex;
-use std::cell::UnsafeCell;
+use std::cell::{RefCell, UnsafeCell};
 use std::ffi::{CStr, CString};
 use std::ops::Drop;
 use std::path::PathBuf;
 use std::ptr;
+use std::rc::Rc;
 
 use crate::clipboard::{ClipboardBackend, ClipboardContext};
-use crate::fonts::atlas::{FontAtlas, FontId, SharedFontAtlas};
+use crate::fonts::atlas::{
+    FontAtlas, FontAtlasRefMut, FontId, SharedFontAtlas, SyncSharedFontAtlas,
+};
 use crate::io::Io;
+use crate::platform_services::{
+    PlatformServices, PlatformServicesClipboard, PlatformServicesContext, SharedPlatformServices,
+};
 use crate::style::Style;
 use crate::{sys, DrawData};
 use crate::{MouseCursor, Ui};
@@ -53,6 +59,8 @@ use crate::docking_utils;
 pub struct Context {
     raw: *mut sys::ImGuiContext,
     shared_font_atlas: Option<SharedFontAtlas>,
+    // boxed to keep the size of `Context` down
+    sync_font_atlas: Option<Box<SyncFontAtlasAttachment>>,
     ini_filename: Option<CString>,
     log_filename: Option<CString>,
     platform_name: Option<CString>,
@@ -62,10 +70,42 @@ pub struct Context {
     // we also put it in an unsafecell since we're going to give
     // imgui a mutable pointer to it.
     clipboard_ctx: Box<UnsafeCell<ClipboardContext>>,
+    // boxed for the same reason
+    platform_services: Option<Box<PlatformServicesContext>>,
 
     ui: Ui,
 }
 
+/// A [`SyncSharedFontAtlas`] used by a context, and whether the context holds a shared lock on it
+/// for a frame in progress
+#[derive(Debug)]
+struct SyncFontAtlasAttachment {
+    atlas: SyncSharedFontAtlas,
+    /// Held from `new_frame` until `render`
+    frame_lock: bool,
+}
+
+impl SyncFontAtlasAttachment {
+    fn new(atlas: SyncSharedFontAtlas) -> Box<SyncFontAtlasAttachment> {
+        Box::new(SyncFontAtlasAttachment {
+            atlas,
+            frame_lock: false,
+        })
+    }
+    fn lock_frame(&mut self) {
+        if !self.frame_lock {
+            self.atlas.lock_read();
+            self.frame_lock = true;
+        }
+    }
+    fn release(&mut self) {
+        if self.frame_lock {
+            self.atlas.unlock_read();
+            self.frame_lock = false;
+        }
+    }
+}
+
 // This mutex needs to be used to guard all public functions that can affect the underlying
 // Dear ImGui active context
 static CTX_MUTEX: ReentrantMutex<()> = parking_lot::const_reentrant_mutex(());
@@ -88,7 +128,7 @@ impl Context {
     /// Panics if an active context already exists
     #[doc(alias = "CreateContext")]
     pub fn create() -> Self {
-        Self::create_internal(None)
+        Self::create_internal(None, None)
     }
     /// Creates a new active imgui-rs context with a shared font atlas.
     ///
@@ -97,7 +137,19 @@ impl Context {
     /// Panics if an active context already exists
     #[doc(alias = "CreateContext")]
     pub fn create_with_shared_font_atlas(shared_font_atlas: SharedFontAtlas) -> Self {
-        Self::create_internal(Some(shared_font_atlas))
+        Self::create_internal(Some(shared_font_atlas), None)
+    }
+    /// Creates a new active imgui-rs context with a font atlas that can be shared with contexts
+    /// on other threads.
+    ///
+    /// See [`SyncSharedFontAtlas`] for how the atlas is locked during frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an active context already exists
+    #[doc(alias = "CreateContext")]
+    pub fn create_with_sync_shared_font_atlas(sync_font_atlas: SyncSharedFontAtlas) -> Self {
+        Self::create_internal(None, Some(sync_font_atlas))
     }
     /// Suspends this context so another context can be the active context.
     #[doc(alias = "CreateContext")]
@@ -223,33 +275,67 @@ impl Context {
         platform_io.clipboard_user_data = clipboard_ctx.get() as *mut _;
         self.clipboard_ctx = clipboard_ctx;
     }
-    fn create_internal(mut shared_font_atlas: Option<SharedFontAtlas>) -> Self {
+    /// Sets the platform services used for text input (IME) positioning, opening URLs and the
+    /// clipboard.
+    ///
+    /// This replaces any clipboard backend set with [`set_clipboard_backend`], as well as Dear
+    /// ImGui's default URL opener.
+    ///
+    /// [`set_clipboard_backend`]: Self::set_clipboard_backend
+    pub fn set_platform_services<T: PlatformServices>(&mut self, services: T) {
+        let services: SharedPlatformServices = Rc::new(RefCell::new(services));
+        self.set_clipboard_backend(PlatformServicesClipboard(services.clone()));
+        let services_ctx = Box::new(PlatformServicesContext { services });
+        let platform_io = unsafe {
+            // safe because PlatformIo is a transparent wrapper around sys::ImGuiPlatformIO
+            // and &mut self ensures exclusive ownership of PlatformIo.
+            &mut *(sys::igGetPlatformIO() as *mut crate::PlatformIo)
+        };
+        platform_io.open_in_shell_fn = Some(crate::platform_services::open_in_shell);
+        platform_io.set_ime_data_fn = Some(crate::platform_services::set_ime_data);
+
+        let user_data = &*services_ctx as *const PlatformServicesContext as *mut _;
+        platform_io.open_in_shell_user_data = user_data;
+        platform_io.ime_user_data = user_data;
+        self.platform_services = Some(services_ctx);
+    }
+    fn create_internal(
+        mut shared_font_atlas: Option<SharedFontAtlas>,
+        sync_font_atlas: Option<SyncSharedFontAtlas>,
+    ) -> Self {
         let _guard = CTX_MUTEX.lock();
         assert!(
             no_current_context(),
             "A new active context cannot be created, because another one already exists"
         );
 
-        let shared_font_atlas_ptr = match &mut shared_font_atlas {
-            Some(shared_font_atlas) => shared_font_atlas.as_ptr_mut(),
-            None => ptr::null_mut(),
-        };
+        let shared_font_atlas_ptr = font_atlas_ptr(&mut shared_font_atlas, &sync_font_atlas);
         // Dear ImGui implicitly sets the current context during igCreateContext if the current
         // context doesn't exist
         let raw = unsafe { sys::igCreateContext(shared_font_atlas_ptr) };
 
-        Context {
+        let ctx = Context {
             raw,
             shared_font_atlas,
+            sync_font_atlas: sync_font_atlas.map(SyncFontAtlasAttachment::new),
             ini_filename: None,
             log_filename: None,
             platform_name: None,
             renderer_name: None,
             clipboard_ctx: Box::new(ClipboardContext::dummy().into()),
+            platform_services: None,
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
+                gestures: Default::default(),
+                text_input_initial: Default::default(),
+                text_input_error: Default::default(),
+                widget_states: Default::default(),
             },
-        }
+        };
+        // The settings handlers use the current context, and must be added before settings are
+        // loaded
+        unsafe { crate::widget::splitter::add_settings_handler(&ctx.ui.widget_states) };
+        ctx
     }
     fn is_current_context(&self) -> bool {
         let ctx = unsafe { sys::igGetCurrentContext() };
@@ -257,6 +343,18 @@ impl Context {
     }
 }
 
+/// Returns the font atlas to pass to `igCreateContext`, or null to let Dear ImGui create one
+fn font_atlas_ptr(
+    shared_font_atlas: &mut Option<SharedFontAtlas>,
+    sync_font_atlas: &Option<SyncSharedFontAtlas>,
+) -> *mut sys::ImFontAtlas {
+    match (shared_font_atlas, sync_font_atlas) {
+        (Some(shared_font_atlas), _) => shared_font_atlas.as_ptr_mut(),
+        (None, Some(sync_font_atlas)) => sync_font_atlas.raw_mut(),
+        (None, None) => ptr::null_mut(),
+    }
+}
+
 impl Drop for Context {
     #[doc(alias = "DestroyContext")]
     fn drop(&mut self) {
@@ -270,6 +368,9 @@ impl Drop for Context {
             }
             sys::igDestroyContext(self.raw);
         }
+        if let Some(sync_font_atlas) = &mut self.sync_font_atlas {
+            sync_font_atlas.release();
+        }
     }
 }
 
@@ -299,12 +400,18 @@ impl SuspendedContext {
     /// Creates a new suspended imgui-rs context.
     #[doc(alias = "CreateContext")]
     pub fn create() -> Self {
-        Self::create_internal(None)
+        Self::create_internal(None, None)
     }
 
     /// Creates a new suspended imgui-rs context with a shared font atlas.
     pub fn create_with_shared_font_atlas(shared_font_atlas: SharedFontAtlas) -> Self {
-        Self::create_internal(Some(shared_font_atlas))
+        Self::create_internal(Some(shared_font_atlas), None)
+    }
+
+    /// Creates a new suspended imgui-rs context with a font atlas that can be shared with
+    /// contexts on other threads.
+    pub fn create_with_sync_shared_font_atlas(sync_font_atlas: SyncSharedFontAtlas) -> Self {
+        Self::create_internal(None, Some(sync_font_atlas))
     }
     /// Attempts to activate this suspended context.
     ///
@@ -313,6 +420,7 @@ impl SuspendedContext {
     /// If there is already an active context, nothing happens and `Err` is returned, containing
     /// the original suspended context.
     #[doc(alias = "SetCurrentContext")]
+    #[allow(clippy::result_large_err)] // boxing the error would change the public API for no real gain
     pub fn activate(self) -> Result<Context, SuspendedContext> {
         let _guard = CTX_MUTEX.lock();
         if no_current_context() {
@@ -324,21 +432,37 @@ impl SuspendedContext {
             Err(self)
         }
     }
-    fn create_internal(shared_font_atlas: Option<SharedFontAtlas>) -> Self {
+    fn create_internal(
+        mut shared_font_atlas: Option<SharedFontAtlas>,
+        sync_font_atlas: Option<SyncSharedFontAtlas>,
+    ) -> Self {
         let _guard = CTX_MUTEX.lock();
-        let raw = unsafe { sys::igCreateContext(ptr::null_mut()) };
+        let shared_font_atlas_ptr = font_atlas_ptr(&mut shared_font_atlas, &sync_font_atlas);
+        let raw = unsafe { sys::igCreateContext(shared_font_atlas_ptr) };
         let ctx = Context {
             raw,
             shared_font_atlas,
+            sync_font_atlas: sync_font_atlas.map(SyncFontAtlasAttachment::new),
             ini_filename: None,
             log_filename: None,
             platform_name: None,
             renderer_name: None,
             clipboard_ctx: Box::new(ClipboardContext::dummy().into()),
+            platform_services: None,
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
+                gestures: Default::default(),
+                text_input_initial: Default::default(),
+                text_input_error: Default::default(),
+                widget_states: Default::default(),
             },
         };
+        unsafe {
+            let previous = sys::igGetCurrentContext();
+            sys::igSetCurrentContext(raw);
+            crate::widget::splitter::add_settings_handler(&ctx.ui.widget_states);
+            sys::igSetCurrentContext(previous);
+        }
         if ctx.is_current_context() {
             // Oops, the context was activated -> deactivate
             clear_current_context();
@@ -430,6 +554,55 @@ fn test_shared_font_atlas() {
     let _borrow = ctx.fonts();
 }
 
+#[test]
+fn test_sync_shared_font_atlas() {
+    let _guard = crate::test::TEST_MUTEX.lock();
+    let atlas = SyncSharedFontAtlas::create();
+    let suspended = SuspendedContext::create_with_sync_shared_font_atlas(atlas.clone());
+    let mut ctx = Context::create_with_sync_shared_font_atlas(atlas.clone());
+    ctx.io_mut().ini_filename = ptr::null();
+    ctx.io_mut().display_size = [1024.0, 768.0];
+    ctx.io_mut().delta_time = 1.0 / 60.0;
+    {
+        let mut fonts = ctx.fonts();
+        fonts.build_rgba32_texture();
+        // the atlas stays locked for writing while it's borrowed
+        assert!(atlas.try_write().is_none());
+    }
+    assert!(atlas.try_write().is_some());
+    ctx.new_frame();
+    let other_thread = {
+        let atlas = atlas.clone();
+        std::thread::spawn(move || atlas.try_write().is_some())
+    };
+    assert!(!other_thread.join().unwrap());
+    ctx.render();
+    assert!(atlas.try_write().is_some());
+
+    let _suspended = ctx.suspend();
+    let mut ctx = suspended.activate().unwrap();
+    assert!(ctx.fonts().is_built());
+}
+
+#[test]
+fn test_sync_shared_font_atlas_fonts_then_other_frame() {
+    let _guard = crate::test::TEST_MUTEX.lock();
+    let atlas = SyncSharedFontAtlas::create();
+    let suspended = SuspendedContext::create_with_sync_shared_font_atlas(atlas.clone());
+    let mut ctx = Context::create_with_sync_shared_font_atlas(atlas.clone());
+    ctx.fonts().build_rgba32_texture();
+    let _suspended = ctx.suspend();
+
+    // the other context doesn't wait for a lock left behind by `fonts`
+    let mut ctx = suspended.activate().unwrap();
+    ctx.io_mut().ini_filename = ptr::null();
+    ctx.io_mut().display_size = [1024.0, 768.0];
+    ctx.io_mut().delta_time = 1.0 / 60.0;
+    assert!(atlas.try_write().is_some());
+    ctx.new_frame();
+    ctx.render();
+}
+
 #[test]
 fn test_ini_load_save() {
     let (_guard, mut ctx) = crate::test::test_ctx();
@@ -512,11 +685,34 @@ impl Context {
         }
     }
     /// Returns a mutable reference to the font atlas.
-    pub fn fonts(&mut self) -> &mut FontAtlas {
-        // we take this with an `&mut Self` here, which means
-        // that we can't get the sharedfontatlas through safe code
-        // otherwise
-        unsafe { &mut *self.io_mut().fonts }
+    ///
+    /// If this context uses a [`SyncSharedFontAtlas`], this waits until no other context is in
+    /// the middle of a frame, and the atlas stays locked for writing until the returned guard is
+    /// dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this context uses a [`SyncSharedFontAtlas`] and is in the middle of a frame.
+    pub fn fonts(&mut self) -> FontAtlasRefMut<'_> {
+        let fonts = self.io_mut().fonts;
+        match &mut self.sync_font_atlas {
+            Some(sync_font_atlas) => {
+                if sync_font_atlas.frame_lock {
+                    // the frame may have been ended early without calling `render`
+                    let atlas = unsafe { &*fonts };
+                    assert!(
+                        !atlas.is_locked(),
+                        "The font atlas cannot be modified while a frame is in progress"
+                    );
+                    sync_font_atlas.release();
+                }
+                FontAtlasRefMut::sync(sync_font_atlas.atlas.write())
+            }
+            // we take this with an `&mut Self` here, which means
+            // that we can't get the sharedfontatlas through safe code
+            // otherwise
+            None => FontAtlasRefMut::owned(unsafe { &mut *fonts }),
+        }
     }
 
     /// Attempts to clone the interior shared font atlas **if it exists**.
@@ -524,6 +720,19 @@ impl Context {
         self.shared_font_atlas.clone()
     }
 
+    /// Attempts to clone the interior sync shared font atlas **if it exists**.
+    pub fn clone_sync_shared_font_atlas(&self) -> Option<SyncSharedFontAtlas> {
+        self.sync_font_atlas
+            .as_ref()
+            .map(|sync_font_atlas| sync_font_atlas.atlas.clone())
+    }
+
+    /// Returns the `Ui` without starting a frame, for filling in per-frame state ahead of
+    /// [`new_frame`](Self::new_frame)
+    pub(crate) fn ui_mut(&mut self) -> &mut Ui {
+        &mut self.ui
+    }
+
     /// Starts a new frame. Use [`new_frame`] instead.
     ///
     /// [`new_frame`]: Self::new_frame
@@ -534,9 +743,13 @@ impl Context {
     /// Starts a new frame and returns an `Ui` instance for constructing a user interface.
     #[doc(alias = "NewFrame")]
     pub fn new_frame(&mut self) -> &mut Ui {
+        if let Some(sync_font_atlas) = &mut self.sync_font_atlas {
+            sync_font_atlas.lock_frame();
+        }
         // Clear default font if it no longer exists. This could be an error in the future
         let default_font = self.io().font_default;
-        if !default_font.is_null() && self.fonts().get_font(FontId(default_font)).is_none() {
+        let fonts = unsafe { &*(self.io().fonts as *const FontAtlas) };
+        if !default_font.is_null() && fonts.get_font(FontId(default_font)).is_none() {
             self.io_mut().font_default = ptr::null_mut();
         }
         // TODO: precondition checks
@@ -554,10 +767,11 @@ impl Context {
     /// [`new_frame`]: Self::new_frame
     #[doc(alias = "Render", alias = "GetDrawData")]
     pub fn render(&mut self) -> &DrawData {
-        unsafe {
-            sys::igRender();
-            &*(sys::igGetDrawData() as *mut DrawData)
+        unsafe { sys::igRender() };
+        if let Some(sync_font_atlas) = &mut self.sync_font_atlas {
+            sync_font_atlas.release();
         }
+        unsafe { &*(sys::igGetDrawData() as *mut DrawData) }
     }
 
     /// Returns the currently desired mouse cursor type.
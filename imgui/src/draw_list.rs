@@ -15,7 +15,7 @@
 
 use bitflags::bitflags;
 
-use crate::{math::MintVec2, ImColor32};
+use crate::{math::MintVec2, FontAtlas, ImColor32, MouseCursor};
 use sys::{ImDrawCmd, ImDrawList};
 
 use super::Ui;
@@ -467,6 +467,34 @@ impl<'ui> DrawListMut<'ui> {
         ImageRounded::new(self, texture_id, p_min, p_max, rounding)
     }
 
+    /// Draw one of the software mouse cursor shapes stored in the font atlas, with its hot spot
+    /// at `pos`.
+    ///
+    /// This is what Dear ImGui draws when [`Io::mouse_draw_cursor`](crate::Io::mouse_draw_cursor)
+    /// is enabled, and is useful when the cursor has to be drawn by hand, e.g. when the frame is
+    /// displayed remotely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use imgui::*;
+    /// fn draw_cursor(ui: &Ui) {
+    ///     if let Some(cursor) = ui.mouse_cursor() {
+    ///         ui.get_foreground_draw_list()
+    ///             .add_mouse_cursor(cursor, ui.io().mouse_pos)
+    ///             .build();
+    ///     }
+    /// }
+    /// ```
+    #[doc(alias = "RenderMouseCursor")]
+    pub fn add_mouse_cursor(
+        &'ui self,
+        cursor: MouseCursor,
+        pos: impl Into<MintVec2>,
+    ) -> MouseCursorImage<'ui> {
+        MouseCursorImage::new(self, cursor, pos)
+    }
+
     /// Draw the specified callback.
     ///
     /// Note: if this DrawList is never rendered the callback will leak because DearImGui
@@ -988,6 +1016,108 @@ impl<'ui> Image<'ui> {
     }
 }
 
+/// Represents a software mouse cursor about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct MouseCursorImage<'ui> {
+    cursor: MouseCursor,
+    pos: [f32; 2],
+    scale: f32,
+    fill_color: ImColor32,
+    border_color: ImColor32,
+    shadow_color: ImColor32,
+    draw_list: &'ui DrawListMut<'ui>,
+}
+
+impl<'ui> MouseCursorImage<'ui> {
+    /// Typically constructed by [`DrawListMut::add_mouse_cursor`]
+    pub fn new(
+        draw_list: &'ui DrawListMut<'_>,
+        cursor: MouseCursor,
+        pos: impl Into<MintVec2>,
+    ) -> Self {
+        Self {
+            cursor,
+            pos: pos.into().into(),
+            scale: 1.0,
+            fill_color: ImColor32::WHITE,
+            border_color: ImColor32::BLACK,
+            shadow_color: ImColor32::from_rgba(0, 0, 0, 48),
+            draw_list,
+        }
+    }
+
+    /// Set the scale of the cursor (default `1.0`).
+    ///
+    /// Dear ImGui uses [`Style::mouse_cursor_scale`](crate::Style::mouse_cursor_scale).
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the color of the cursor's interior (default: white)
+    pub fn fill_color(mut self, fill_color: impl Into<ImColor32>) -> Self {
+        self.fill_color = fill_color.into();
+        self
+    }
+
+    /// Set the color of the cursor's outline (default: black)
+    pub fn border_color(mut self, border_color: impl Into<ImColor32>) -> Self {
+        self.border_color = border_color.into();
+        self
+    }
+
+    /// Set the color of the cursor's shadow (default: translucent black)
+    pub fn shadow_color(mut self, shadow_color: impl Into<ImColor32>) -> Self {
+        self.shadow_color = shadow_color.into();
+        self
+    }
+
+    /// Draw the cursor.
+    ///
+    /// Returns false if nothing was drawn, because the font atlas doesn't contain mouse cursors.
+    pub fn build(self) -> bool {
+        use std::os::raw::c_void;
+
+        let atlas = unsafe { &*((*sys::igGetIO()).Fonts as *const FontAtlas) };
+        let data = match atlas.mouse_cursor_tex_data(self.cursor) {
+            Some(data) => data,
+            None => return false,
+        };
+        let texture_id = atlas.tex_id.id() as *mut c_void;
+        let scale = self.scale;
+        let pos = [self.pos[0] - data.offset[0], self.pos[1] - data.offset[1]];
+        let rect = |dx: f32| {
+            let min = [pos[0] + dx * scale, pos[1]];
+            let max = [min[0] + data.size[0] * scale, pos[1] + data.size[1] * scale];
+            (sys::ImVec2::from(min), sys::ImVec2::from(max))
+        };
+        // Same layers as Dear ImGui's own software cursor: two shadows, the outline, the interior
+        let layers = [
+            (1.0, data.uv_border, self.shadow_color),
+            (2.0, data.uv_border, self.shadow_color),
+            (0.0, data.uv_border, self.border_color),
+            (0.0, data.uv_fill, self.fill_color),
+        ];
+        unsafe {
+            sys::ImDrawList_PushTextureID(self.draw_list.draw_list, texture_id);
+            for (dx, uv, color) in layers {
+                let (min, max) = rect(dx);
+                sys::ImDrawList_AddImage(
+                    self.draw_list.draw_list,
+                    texture_id,
+                    min,
+                    max,
+                    uv[0].into(),
+                    uv[1].into(),
+                    color.into(),
+                );
+            }
+            sys::ImDrawList_PopTextureID(self.draw_list.draw_list);
+        }
+        true
+    }
+}
+
 /// Represents a image about to be drawn
 #[must_use = "should call .build() to draw the object"]
 pub struct ImageQuad<'ui> {
@@ -1,15 +1,17 @@
 use bitflags::bitflags;
+use parking_lot::{Condvar, Mutex};
 use std::f32;
 use std::os::raw::{c_int, c_uchar, c_void};
 use std::ptr;
 use std::rc::Rc;
 use std::slice;
+use std::sync::Arc;
 
 use crate::fonts::font::Font;
 use crate::fonts::glyph_ranges::FontGlyphRanges;
 use crate::internal::{ImVector, RawCast};
 use crate::sys;
-use crate::TextureId;
+use crate::{MouseCursor, TextureId};
 
 bitflags! {
     /// Font atlas configuration flags
@@ -134,11 +136,58 @@ impl FontAtlas {
         }
         None
     }
+    /// Returns true if the font atlas is locked because a frame is in progress.
+    ///
+    /// A locked atlas must not be modified.
+    pub(crate) fn is_locked(&self) -> bool {
+        self.locked
+    }
+    /// Builds the glyph data and texture of the atlas without converting its pixels
+    #[doc(alias = "Build")]
+    pub(crate) fn build(&mut self) -> bool {
+        unsafe { sys::ImFontAtlas_Build(self.raw_mut()) }
+    }
     /// Returns true if the font atlas has been built
     #[doc(alias = "IsBuilt")]
     pub fn is_built(&self) -> bool {
         unsafe { sys::ImFontAtlas_IsBuilt(self.raw() as *const sys::ImFontAtlas as *mut _) }
     }
+    /// Returns where the software mouse cursor shapes for `cursor` are stored in the atlas texture.
+    ///
+    /// These shapes are used when [`Io::mouse_draw_cursor`](crate::Io::mouse_draw_cursor) is
+    /// enabled, and can be drawn manually with
+    /// [`DrawListMut::add_mouse_cursor`](crate::DrawListMut::add_mouse_cursor).
+    ///
+    /// Returns `None` if the atlas hasn't been built yet, or was built with
+    /// [`FontAtlasFlags::NO_MOUSE_CURSORS`].
+    #[doc(alias = "GetMouseCursorTexData")]
+    pub fn mouse_cursor_tex_data(&self, cursor: MouseCursor) -> Option<MouseCursorTexData> {
+        if !self.is_built() || self.pack_id_mouse_cursors < 0 {
+            return None;
+        }
+        let mut offset = sys::ImVec2::zero();
+        let mut size = sys::ImVec2::zero();
+        let mut uv_first = [sys::ImVec2::zero(); 2];
+        let mut uv_second = [sys::ImVec2::zero(); 2];
+        let found = unsafe {
+            sys::ImFontAtlas_GetMouseCursorTexData(
+                self.raw() as *const sys::ImFontAtlas as *mut _,
+                cursor as sys::ImGuiMouseCursor,
+                &mut offset,
+                &mut size,
+                uv_first.as_mut_ptr(),
+                uv_second.as_mut_ptr(),
+            )
+        };
+        // Dear ImGui calls the first rect the "border" and the second the "fill", but the first
+        // one holds the cursor's interior and the second its outline (see `RenderMouseCursor`)
+        found.then(|| MouseCursorTexData {
+            offset: offset.into(),
+            size: size.into(),
+            uv_fill: [uv_first[0].into(), uv_first[1].into()],
+            uv_border: [uv_second[0].into(), uv_second[1].into()],
+        })
+    }
     /// Builds a 1 byte per-pixel font atlas texture
     #[doc(alias = "GetTextDataAsAlpha8")]
     pub fn build_alpha8_texture(&mut self) -> FontAtlasTexture<'_> {
@@ -280,6 +329,21 @@ fn test_font_atlas_memory_layout() {
     assert_field_offset!(pack_id_lines, PackIdLines);
 }
 
+/// Location of a software mouse cursor shape in the font atlas texture
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MouseCursorTexData {
+    /// Position of the cursor's hot spot within the shape, in pixels
+    pub offset: [f32; 2],
+    /// Size of the shape in pixels
+    pub size: [f32; 2],
+    /// Texture coordinates (`[min, max]`) of the cursor's interior
+    pub uv_fill: [[f32; 2]; 2],
+    /// Texture coordinates (`[min, max]`) of the cursor's outline.
+    ///
+    /// Dear ImGui also draws this shape with a small offset to give the cursor a shadow.
+    pub uv_border: [[f32; 2]; 2],
+}
+
 /// A source for binary font data
 #[derive(Clone, Debug)]
 pub enum FontSource<'a> {
@@ -364,7 +428,7 @@ impl FontConfig {
         raw.GlyphMaxAdvanceX = self.glyph_max_advance_x;
         raw.FontBuilderFlags = self.font_builder_flags;
         raw.RasterizerMultiply = self.rasterizer_multiply;
-        raw.RasterizerMultiply = self.rasterizer_density;
+        raw.RasterizerDensity = self.rasterizer_density;
         // char is used as "unset" for EllipsisChar
         raw.EllipsisChar = self.ellipsis_char.map(|c| c as u32).unwrap_or(!0);
         if let Some(name) = self.name.as_ref() {
@@ -484,51 +548,269 @@ impl Drop for SharedFontAtlas {
     }
 }
 
-// /// An immutably borrowed reference to a (possibly shared) font atlas
-// pub enum FontAtlasRef<'a> {
-//     Owned(&'a FontAtlas),
-//     Shared(&'a cell::RefMut<'a, SharedFontAtlas>),
-// }
-
-// impl<'a> Deref for FontAtlasRef<'a> {
-//     type Target = FontAtlas;
-//     fn deref(&self) -> &FontAtlas {
-//         use self::FontAtlasRef::*;
-//         match self {
-//             Owned(atlas) => atlas,
-//             Shared(cell) => {
-//                 let font_atlas: &SharedFontAtlas = &cell;
-//                 let font_atlas: &FontAtlas = &font_atlas;
-
-//                 todo!()
-//             }
-//         }
-//     }
-// }
-
-// /// A mutably borrowed reference to a (possibly shared) font atlas
-// pub enum FontAtlasRefMut<'a> {
-//     Owned(&'a mut FontAtlas),
-//     Shared(cell::RefMut<'a, SharedFontAtlas>),
-// }
-
-// impl<'a> Deref for FontAtlasRefMut<'a> {
-//     type Target = FontAtlas;
-//     fn deref(&self) -> &FontAtlas {
-//         use self::FontAtlasRefMut::*;
-//         match self {
-//             Owned(atlas) => atlas,
-//             Shared(cell) => cell,
-//         }
-//     }
-// }
-
-// impl<'a> DerefMut for FontAtlasRefMut<'a> {
-//     fn deref_mut(&mut self) -> &mut FontAtlas {
-//         use self::FontAtlasRefMut::*;
-//         match self {
-//             Owned(atlas) => atlas,
-//             Shared(cell) => cell,
-//         }
-//     }
-// }
+/// A font atlas that can be shared between contexts on different threads.
+///
+/// Unlike [`SharedFontAtlas`], this is reference counted with an `Arc` and guarded by a lock.
+/// Every context created with it holds a shared lock on the atlas from
+/// [`Context::new_frame`](crate::Context::new_frame) until
+/// [`Context::render`](crate::Context::render), so the atlas can't be modified while any of those
+/// contexts is in the middle of a frame. Modifying the atlas through [`write`](Self::write) waits
+/// for all frames in progress to end, and new frames wait until the write guard is dropped.
+///
+/// Note that Dear ImGui keeps a single, process-wide current context, so contexts on different
+/// threads still have to take turns being the active context.
+#[derive(Debug, Clone)]
+pub struct SyncSharedFontAtlas(Arc<SyncFontAtlasInner>);
+
+#[derive(Debug)]
+struct SyncFontAtlasInner {
+    raw: SyncFontAtlasPtr,
+    state: Mutex<SyncFontAtlasState>,
+    changed: Condvar,
+}
+
+/// Raw atlas pointer that may be moved between threads
+#[derive(Debug)]
+struct SyncFontAtlasPtr(*mut sys::ImFontAtlas);
+
+// Safe because every access to the atlas goes through the lock in `SyncFontAtlasInner`
+unsafe impl Send for SyncFontAtlasPtr {}
+unsafe impl Sync for SyncFontAtlasPtr {}
+
+#[derive(Debug, Default)]
+struct SyncFontAtlasState {
+    /// Number of frames in progress and read guards alive
+    readers: usize,
+    /// Whether a write guard is alive
+    writer: bool,
+}
+
+impl SyncSharedFontAtlas {
+    #[doc(alias = "ImFontAtlas", alias = "ImFontAtlas::ImFontAtlas")]
+    pub fn create() -> SyncSharedFontAtlas {
+        SyncSharedFontAtlas(Arc::new(SyncFontAtlasInner {
+            raw: SyncFontAtlasPtr(unsafe { sys::ImFontAtlas_ImFontAtlas() }),
+            state: Mutex::new(SyncFontAtlasState::default()),
+            changed: Condvar::new(),
+        }))
+    }
+
+    /// Gets a raw pointer to the underlying `ImFontAtlas`.
+    pub fn as_ptr(&self) -> *const sys::ImFontAtlas {
+        self.0.raw.0 as *const _
+    }
+
+    /// Locks the atlas for reading, waiting until no write guard is alive.
+    pub fn read(&self) -> SyncFontAtlasReadGuard<'_> {
+        self.lock_read();
+        SyncFontAtlasReadGuard { atlas: self }
+    }
+
+    /// Locks the atlas for writing, waiting until no context is in the middle of a frame and no
+    /// other guard is alive.
+    ///
+    /// Never call this on a thread that has a frame in progress on a context attached to this
+    /// atlas, as it would wait forever.
+    pub fn write(&self) -> SyncFontAtlasWriteGuard<'_> {
+        self.lock_write();
+        SyncFontAtlasWriteGuard { atlas: self }
+    }
+
+    /// Attempts to lock the atlas for writing without waiting.
+    ///
+    /// Returns `None` if a context is in the middle of a frame or another guard is alive.
+    pub fn try_write(&self) -> Option<SyncFontAtlasWriteGuard<'_>> {
+        let mut state = self.0.state.lock();
+        if state.writer || state.readers > 0 {
+            None
+        } else {
+            state.writer = true;
+            Some(SyncFontAtlasWriteGuard { atlas: self })
+        }
+    }
+
+    pub(crate) fn lock_read(&self) {
+        let mut state = self.0.state.lock();
+        while state.writer {
+            self.0.changed.wait(&mut state);
+        }
+        state.readers += 1;
+    }
+
+    pub(crate) fn unlock_read(&self) {
+        let mut state = self.0.state.lock();
+        state.readers -= 1;
+        self.0.changed.notify_all();
+    }
+
+    pub(crate) fn lock_write(&self) {
+        let mut state = self.0.state.lock();
+        while state.writer || state.readers > 0 {
+            self.0.changed.wait(&mut state);
+        }
+        state.writer = true;
+    }
+
+    pub(crate) fn unlock_write(&self) {
+        let mut state = self.0.state.lock();
+        state.writer = false;
+        self.0.changed.notify_all();
+    }
+
+    pub(crate) fn raw_mut(&self) -> *mut sys::ImFontAtlas {
+        self.0.raw.0
+    }
+}
+
+impl Drop for SyncFontAtlasInner {
+    #[doc(alias = "ImFontAtlas::Destory")]
+    fn drop(&mut self) {
+        unsafe { sys::ImFontAtlas_destroy(self.raw.0) };
+    }
+}
+
+/// Shared access to a [`SyncSharedFontAtlas`]. The atlas can't be modified while it is alive.
+#[derive(Debug)]
+pub struct SyncFontAtlasReadGuard<'a> {
+    atlas: &'a SyncSharedFontAtlas,
+}
+
+impl std::ops::Deref for SyncFontAtlasReadGuard<'_> {
+    type Target = FontAtlas;
+
+    fn deref(&self) -> &FontAtlas {
+        unsafe { FontAtlas::from_raw(&*self.atlas.raw_mut()) }
+    }
+}
+
+impl Drop for SyncFontAtlasReadGuard<'_> {
+    fn drop(&mut self) {
+        self.atlas.unlock_read();
+    }
+}
+
+/// Exclusive access to a [`SyncSharedFontAtlas`]. No attached context can start a frame while
+/// it is alive.
+#[derive(Debug)]
+pub struct SyncFontAtlasWriteGuard<'a> {
+    atlas: &'a SyncSharedFontAtlas,
+}
+
+impl std::ops::Deref for SyncFontAtlasWriteGuard<'_> {
+    type Target = FontAtlas;
+
+    fn deref(&self) -> &FontAtlas {
+        unsafe { FontAtlas::from_raw(&*self.atlas.raw_mut()) }
+    }
+}
+
+impl std::ops::DerefMut for SyncFontAtlasWriteGuard<'_> {
+    fn deref_mut(&mut self) -> &mut FontAtlas {
+        unsafe { FontAtlas::from_raw_mut(&mut *self.atlas.raw_mut()) }
+    }
+}
+
+impl Drop for SyncFontAtlasWriteGuard<'_> {
+    fn drop(&mut self) {
+        self.atlas.unlock_write();
+    }
+}
+
+/// A mutably borrowed font atlas, returned by [`Context::fonts`](crate::Context::fonts).
+///
+/// If the context uses a [`SyncSharedFontAtlas`], the atlas is locked for writing until this is
+/// dropped.
+pub struct FontAtlasRefMut<'a>(FontAtlasRefMutInner<'a>);
+
+enum FontAtlasRefMutInner<'a> {
+    Owned(&'a mut FontAtlas),
+    Sync(SyncFontAtlasWriteGuard<'a>),
+}
+
+impl<'a> FontAtlasRefMut<'a> {
+    pub(crate) fn owned(atlas: &'a mut FontAtlas) -> Self {
+        FontAtlasRefMut(FontAtlasRefMutInner::Owned(atlas))
+    }
+    pub(crate) fn sync(guard: SyncFontAtlasWriteGuard<'a>) -> Self {
+        FontAtlasRefMut(FontAtlasRefMutInner::Sync(guard))
+    }
+}
+
+impl std::fmt::Debug for FontAtlasRefMut<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let synced = matches!(self.0, FontAtlasRefMutInner::Sync(_));
+        f.debug_struct("FontAtlasRefMut")
+            .field("synced", &synced)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::ops::Deref for FontAtlasRefMut<'_> {
+    type Target = FontAtlas;
+
+    fn deref(&self) -> &FontAtlas {
+        match &self.0 {
+            FontAtlasRefMutInner::Owned(atlas) => atlas,
+            FontAtlasRefMutInner::Sync(guard) => guard,
+        }
+    }
+}
+
+impl std::ops::DerefMut for FontAtlasRefMut<'_> {
+    fn deref_mut(&mut self) -> &mut FontAtlas {
+        match &mut self.0 {
+            FontAtlasRefMutInner::Owned(atlas) => atlas,
+            FontAtlasRefMutInner::Sync(guard) => guard,
+        }
+    }
+}
+
+#[test]
+fn test_sync_shared_font_atlas_is_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SyncSharedFontAtlas>();
+}
+
+#[test]
+fn test_sync_shared_font_atlas_locking() {
+    let _guard = crate::test::TEST_MUTEX.lock();
+    let atlas = SyncSharedFontAtlas::create();
+    {
+        let read = atlas.read();
+        assert!(!read.is_built());
+        assert!(atlas.try_write().is_none());
+    }
+    let mut write = atlas.try_write().expect("no readers are alive");
+    write.add_font(&[FontSource::DefaultFontData { config: None }]);
+    assert!(atlas.try_write().is_none());
+    drop(write);
+
+    let reader = {
+        let atlas = atlas.clone();
+        std::thread::spawn(move || atlas.read().fonts().len())
+    };
+    assert_eq!(reader.join().unwrap(), 1);
+}
+
+#[test]
+fn test_mouse_cursor_tex_data() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let mut fonts = ctx.fonts();
+    assert!(fonts.mouse_cursor_tex_data(MouseCursor::Arrow).is_none());
+    fonts.build_rgba32_texture();
+    for &cursor in MouseCursor::VARIANTS.iter() {
+        let data = fonts
+            .mouse_cursor_tex_data(cursor)
+            .expect("cursors are built by default");
+        assert!(data.size[0] > 0.0 && data.size[1] > 0.0);
+        for uv in data.uv_fill.iter().chain(data.uv_border.iter()) {
+            assert!((0.0..=1.0).contains(&uv[0]) && (0.0..=1.0).contains(&uv[1]));
+        }
+        assert!(data.uv_border[0][0] > data.uv_fill[0][0]);
+    }
+
+    fonts.clear_fonts();
+    fonts.flags |= FontAtlasFlags::NO_MOUSE_CURSORS;
+    fonts.build_rgba32_texture();
+    assert!(fonts.mouse_cursor_tex_data(MouseCursor::Arrow).is_none());
+}
@@ -0,0 +1,539 @@
+use std::fmt;
+use std::os::raw::c_int;
+use std::slice;
+
+use crate::binary::{BinaryReader, BinaryWriter};
+use crate::fonts::atlas::FontAtlas;
+use crate::fonts::glyph::FontGlyph;
+use crate::internal::{ImVector, RawCast};
+use crate::sys;
+
+const CACHE_MAGIC: &[u8; 8] = b"IMGUIFAC";
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// An error returned when loading a font atlas build cache
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FontAtlasCacheError {
+    /// The data is not a font atlas cache, or it is truncated or corrupted
+    InvalidData,
+    /// The cache was written in a format this version of imgui-rs can't read
+    UnsupportedVersion,
+    /// The cache was built from different font sources, font configs or atlas settings
+    Stale,
+    /// The font atlas is locked because a frame is in progress
+    Locked,
+}
+
+impl fmt::Display for FontAtlasCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            FontAtlasCacheError::InvalidData => "Invalid font atlas cache data",
+            FontAtlasCacheError::UnsupportedVersion => "Unsupported font atlas cache version",
+            FontAtlasCacheError::Stale => "Font atlas cache doesn't match the font atlas inputs",
+            FontAtlasCacheError::Locked => "Font atlas is locked while a frame is in progress",
+        })
+    }
+}
+
+impl std::error::Error for FontAtlasCacheError {}
+
+/// # Build cache
+impl FontAtlas {
+    /// Returns a hash of every input that affects building this font atlas.
+    ///
+    /// This covers the data and configuration of every added font source, the atlas flags and
+    /// texture settings, and the Dear ImGui version. It is stored in the build cache to detect
+    /// stale caches.
+    ///
+    /// The key is stable between runs of the program, but changes if [`clear_input_data`] is
+    /// called.
+    ///
+    /// [`clear_input_data`]: Self::clear_input_data
+    pub fn build_cache_key(&self) -> u64 {
+        let raw = unsafe { self.raw() };
+        let mut hasher = Fnv64::new();
+        hasher.write(crate::dear_imgui_version().as_bytes());
+        hasher.write_u8(cfg!(feature = "freetype") as u8);
+        hasher.write_u32(raw.Flags as u32);
+        hasher.write_u32(raw.TexDesiredWidth as u32);
+        hasher.write_u32(raw.TexGlyphPadding as u32);
+        hasher.write_u32(raw.FontBuilderFlags);
+        hasher.write_u32(raw.Fonts.Size as u32);
+        for config in unsafe { raw_slice(raw.ConfigData.Data, raw.ConfigData.Size) } {
+            let font_data = unsafe {
+                raw_slice(
+                    config.FontData as *const u8,
+                    if config.FontData.is_null() {
+                        0
+                    } else {
+                        config.FontDataSize
+                    },
+                )
+            };
+            hasher.write_u32(font_data.len() as u32);
+            hasher.write(font_data);
+            hasher.write_u32(config.FontNo as u32);
+            hasher.write_f32(config.SizePixels);
+            hasher.write_u32(config.OversampleH as u32);
+            hasher.write_u32(config.OversampleV as u32);
+            hasher.write_u8(config.PixelSnapH as u8);
+            hasher.write_f32(config.GlyphExtraSpacing.x);
+            hasher.write_f32(config.GlyphExtraSpacing.y);
+            hasher.write_f32(config.GlyphOffset.x);
+            hasher.write_f32(config.GlyphOffset.y);
+            if !config.GlyphRanges.is_null() {
+                let mut range = config.GlyphRanges;
+                unsafe {
+                    while *range != 0 {
+                        hasher.write_u32(*range);
+                        range = range.add(1);
+                    }
+                }
+            }
+            hasher.write_u32(0);
+            hasher.write_f32(config.GlyphMinAdvanceX);
+            hasher.write_f32(config.GlyphMaxAdvanceX);
+            hasher.write_u8(config.MergeMode as u8);
+            hasher.write_u32(config.FontBuilderFlags);
+            hasher.write_f32(config.RasterizerMultiply);
+            hasher.write_f32(config.RasterizerDensity);
+            hasher.write_u32(config.EllipsisChar);
+            hasher.write(unsafe { slice::from_raw_parts(config.Name.as_ptr() as *const u8, 40) });
+        }
+        hasher.finish()
+    }
+
+    /// Serializes the built font atlas into a byte blob that can be loaded with
+    /// [`load_build_cache`](Self::load_build_cache) instead of rasterizing the fonts again.
+    ///
+    /// The blob holds the texture pixels, the glyph tables and metrics of every font and the
+    /// custom rectangles, keyed by [`build_cache_key`](Self::build_cache_key). It is meant to be
+    /// stored on the machine that created it.
+    ///
+    /// Returns `None` if the atlas hasn't been built, or if its texture data has already been
+    /// cleared.
+    pub fn save_build_cache(&self) -> Option<Vec<u8>> {
+        if !self.is_built() {
+            return None;
+        }
+        let raw = unsafe { self.raw() };
+        let pixel_count = (raw.TexWidth as usize).checked_mul(raw.TexHeight as usize)?;
+        let alpha8 = unsafe { raw_slice(raw.TexPixelsAlpha8, pixel_count as c_int) };
+        let rgba32 = unsafe { raw_slice(raw.TexPixelsRGBA32, pixel_count as c_int) };
+        if alpha8.is_empty() && rgba32.is_empty() {
+            return None;
+        }
+        let fonts = unsafe { raw_slice(raw.Fonts.Data, raw.Fonts.Size) };
+
+        let mut out = BinaryWriter(Vec::with_capacity(alpha8.len() + rgba32.len() * 4 + 4096));
+        out.bytes(CACHE_MAGIC);
+        out.u32(CACHE_FORMAT_VERSION);
+        out.u64(self.build_cache_key());
+
+        out.u32(raw.TexWidth as u32);
+        out.u32(raw.TexHeight as u32);
+        out.u8(raw.TexPixelsUseColors as u8);
+        out.f32(raw.TexUvScale.x);
+        out.f32(raw.TexUvScale.y);
+        out.f32(raw.TexUvWhitePixel.x);
+        out.f32(raw.TexUvWhitePixel.y);
+        for uv in &raw.TexUvLines {
+            out.f32(uv.x);
+            out.f32(uv.y);
+            out.f32(uv.z);
+            out.f32(uv.w);
+        }
+        out.u32(raw.PackIdMouseCursors as u32);
+        out.u32(raw.PackIdLines as u32);
+
+        out.u8(!alpha8.is_empty() as u8);
+        out.bytes(alpha8);
+        out.u8(!rgba32.is_empty() as u8);
+        for &pixel in rgba32 {
+            out.u32(pixel);
+        }
+
+        let custom_rects = unsafe { raw_slice(raw.CustomRects.Data, raw.CustomRects.Size) };
+        out.u32(custom_rects.len() as u32);
+        for rect in custom_rects {
+            out.u16(rect.Width);
+            out.u16(rect.Height);
+            out.u16(rect.X);
+            out.u16(rect.Y);
+            out.u32(rect.GlyphID);
+            out.f32(rect.GlyphAdvanceX);
+            out.f32(rect.GlyphOffset.x);
+            out.f32(rect.GlyphOffset.y);
+            let font_index = fonts.iter().position(|&font| font == rect.Font);
+            out.u32(font_index.map_or(u32::MAX, |index| index as u32));
+        }
+
+        out.u32(fonts.len() as u32);
+        for &font in fonts {
+            let font = unsafe { &*font };
+            out.f32(font.FontSize);
+            out.f32(font.Ascent);
+            out.f32(font.Descent);
+            out.u32(font.MetricsTotalSurface as u32);
+            let glyphs =
+                unsafe { raw_slice(font.Glyphs.Data as *const FontGlyph, font.Glyphs.Size) };
+            out.u32(glyphs.len() as u32);
+            for glyph in glyphs {
+                out.u32(glyph.bitfields());
+                out.f32(glyph.advance_x);
+                out.f32(glyph.x0);
+                out.f32(glyph.y0);
+                out.f32(glyph.x1);
+                out.f32(glyph.y1);
+                out.f32(glyph.u0);
+                out.f32(glyph.v0);
+                out.f32(glyph.u1);
+                out.f32(glyph.v1);
+            }
+        }
+        Some(out.0)
+    }
+
+    /// Restores a font atlas from a blob created by [`save_build_cache`], without rasterizing
+    /// the fonts.
+    ///
+    /// The same font sources must have been added to this atlas, in the same order, as when the
+    /// cache was saved. After a successful load the atlas is built, and the texture can be
+    /// fetched with [`build_alpha8_texture`] or [`build_rgba32_texture`] as usual. On error the
+    /// atlas is left untouched and can be built normally.
+    ///
+    /// [`save_build_cache`]: Self::save_build_cache
+    /// [`build_alpha8_texture`]: Self::build_alpha8_texture
+    /// [`build_rgba32_texture`]: Self::build_rgba32_texture
+    pub fn load_build_cache(&mut self, data: &[u8]) -> Result<(), FontAtlasCacheError> {
+        if self.is_locked() {
+            return Err(FontAtlasCacheError::Locked);
+        }
+        let cache = ParsedCache::parse(data)?;
+        let font_count = unsafe { self.raw().Fonts.Size } as usize;
+        if cache.key != self.build_cache_key() || cache.fonts.len() != font_count {
+            return Err(FontAtlasCacheError::Stale);
+        }
+
+        unsafe {
+            let atlas: *mut sys::ImFontAtlas = self.raw_mut();
+            sys::ImFontAtlas_ClearTexData(atlas);
+            let raw = &mut *atlas;
+            raw.TexWidth = cache.tex_width;
+            raw.TexHeight = cache.tex_height;
+            raw.TexUvScale = cache.tex_uv_scale.into();
+            raw.TexUvWhitePixel = cache.tex_uv_white_pixel.into();
+            for (dst, src) in raw.TexUvLines.iter_mut().zip(&cache.tex_uv_lines) {
+                *dst = (*src).into();
+            }
+            raw.PackIdMouseCursors = cache.pack_id_mouse_cursors;
+            raw.PackIdLines = cache.pack_id_lines;
+            if let Some(alpha8) = &cache.alpha8 {
+                raw.TexPixelsAlpha8 = copy_to_imgui_heap(alpha8);
+            }
+            if let Some(rgba32) = &cache.rgba32 {
+                raw.TexPixelsRGBA32 = copy_to_imgui_heap(rgba32);
+            }
+            raw.TexPixelsUseColors = cache.tex_pixels_use_colors;
+
+            let fonts = raw_slice(raw.Fonts.Data, raw.Fonts.Size).to_vec();
+            let custom_rects: Vec<sys::ImFontAtlasCustomRect> = cache
+                .custom_rects
+                .iter()
+                .map(|(rect, font_index)| sys::ImFontAtlasCustomRect {
+                    Font: font_index.map_or(std::ptr::null_mut(), |index| fonts[index]),
+                    ..*rect
+                })
+                .collect();
+            (*(&mut raw.CustomRects as *mut _ as *mut ImVector<sys::ImFontAtlasCustomRect>))
+                .replace_from_slice(&custom_rects);
+
+            for (&font, cached) in fonts.iter().zip(&cache.fonts) {
+                let font = &mut *font;
+                font.FontSize = cached.font_size;
+                font.Ascent = cached.ascent;
+                font.Descent = cached.descent;
+                font.MetricsTotalSurface = cached.metrics_total_surface;
+                font.ContainerAtlas = atlas;
+                (*(&mut font.Glyphs as *mut _ as *mut ImVector<FontGlyph>))
+                    .replace_from_slice(&cached.glyphs);
+                sys::ImFont_BuildLookupTable(font);
+            }
+            raw.TexReady = true;
+        }
+        Ok(())
+    }
+}
+
+/// Font output data read from a build cache
+struct CachedFont {
+    font_size: f32,
+    ascent: f32,
+    descent: f32,
+    metrics_total_surface: c_int,
+    glyphs: Vec<FontGlyph>,
+}
+
+/// A fully validated build cache, read before anything in the atlas is modified
+struct ParsedCache {
+    key: u64,
+    tex_width: c_int,
+    tex_height: c_int,
+    tex_pixels_use_colors: bool,
+    tex_uv_scale: [f32; 2],
+    tex_uv_white_pixel: [f32; 2],
+    tex_uv_lines: Vec<[f32; 4]>,
+    pack_id_mouse_cursors: c_int,
+    pack_id_lines: c_int,
+    alpha8: Option<Vec<u8>>,
+    rgba32: Option<Vec<u32>>,
+    custom_rects: Vec<(sys::ImFontAtlasCustomRect, Option<usize>)>,
+    fonts: Vec<CachedFont>,
+}
+
+impl ParsedCache {
+    fn parse(data: &[u8]) -> Result<ParsedCache, FontAtlasCacheError> {
+        let mut input = BinaryReader::new(data, FontAtlasCacheError::InvalidData);
+        if input.bytes(CACHE_MAGIC.len())? != CACHE_MAGIC {
+            return Err(FontAtlasCacheError::InvalidData);
+        }
+        if input.u32()? != CACHE_FORMAT_VERSION {
+            return Err(FontAtlasCacheError::UnsupportedVersion);
+        }
+        let key = input.u64()?;
+
+        let tex_width = input.u32()?;
+        let tex_height = input.u32()?;
+        if tex_width == 0 || tex_height == 0 || tex_width > i32::MAX as u32 {
+            return Err(FontAtlasCacheError::InvalidData);
+        }
+        let pixel_count = (tex_width as usize)
+            .checked_mul(tex_height as usize)
+            .filter(|&count| count <= i32::MAX as usize)
+            .ok_or(FontAtlasCacheError::InvalidData)?;
+        let tex_pixels_use_colors = input.u8()? != 0;
+        let tex_uv_scale = [input.f32()?, input.f32()?];
+        let tex_uv_white_pixel = [input.f32()?, input.f32()?];
+        let mut tex_uv_lines = Vec::with_capacity(64);
+        for _ in 0..64 {
+            tex_uv_lines.push([input.f32()?, input.f32()?, input.f32()?, input.f32()?]);
+        }
+        let pack_id_mouse_cursors = input.u32()? as c_int;
+        let pack_id_lines = input.u32()? as c_int;
+
+        let alpha8 = match input.u8()? {
+            0 => None,
+            _ => Some(input.bytes(pixel_count)?.to_vec()),
+        };
+        let rgba32 = match input.u8()? {
+            0 => None,
+            _ => {
+                let len = pixel_count
+                    .checked_mul(4)
+                    .ok_or(FontAtlasCacheError::InvalidData)?;
+                let bytes = input.bytes(len)?;
+                Some(
+                    bytes
+                        .chunks_exact(4)
+                        .map(|pixel| u32::from_le_bytes(pixel.try_into().unwrap()))
+                        .collect(),
+                )
+            }
+        };
+        if alpha8.is_none() && rgba32.is_none() {
+            return Err(FontAtlasCacheError::InvalidData);
+        }
+
+        let custom_rect_count = input.count()?;
+        let mut custom_rects = Vec::with_capacity(custom_rect_count);
+        for _ in 0..custom_rect_count {
+            let rect = sys::ImFontAtlasCustomRect {
+                Width: input.u16()?,
+                Height: input.u16()?,
+                X: input.u16()?,
+                Y: input.u16()?,
+                GlyphID: input.u32()?,
+                GlyphAdvanceX: input.f32()?,
+                GlyphOffset: [input.f32()?, input.f32()?].into(),
+                Font: std::ptr::null_mut(),
+            };
+            let font_index = match input.u32()? {
+                u32::MAX => None,
+                index => Some(index as usize),
+            };
+            custom_rects.push((rect, font_index));
+        }
+
+        let font_count = input.count()?;
+        let mut fonts = Vec::with_capacity(font_count);
+        for _ in 0..font_count {
+            let font_size = input.f32()?;
+            let ascent = input.f32()?;
+            let descent = input.f32()?;
+            let metrics_total_surface = input.u32()? as c_int;
+            let glyph_count = input.count()?;
+            // Dear ImGui can't build a lookup table for a font without glyphs
+            if glyph_count == 0 || glyph_count >= 0xFFFF {
+                return Err(FontAtlasCacheError::InvalidData);
+            }
+            let mut glyphs = Vec::with_capacity(glyph_count);
+            for _ in 0..glyph_count {
+                let mut glyph = unsafe { *FontGlyph::from_raw(&sys::ImFontGlyph::default()) };
+                glyph.set_bitfields(input.u32()?);
+                glyph.advance_x = input.f32()?;
+                glyph.x0 = input.f32()?;
+                glyph.y0 = input.f32()?;
+                glyph.x1 = input.f32()?;
+                glyph.y1 = input.f32()?;
+                glyph.u0 = input.f32()?;
+                glyph.v0 = input.f32()?;
+                glyph.u1 = input.f32()?;
+                glyph.v1 = input.f32()?;
+                glyphs.push(glyph);
+            }
+            fonts.push(CachedFont {
+                font_size,
+                ascent,
+                descent,
+                metrics_total_surface,
+                glyphs,
+            });
+        }
+        if !input.is_empty()
+            || custom_rects
+                .iter()
+                .any(|(_, font_index)| font_index.is_some_and(|index| index >= fonts.len()))
+        {
+            return Err(FontAtlasCacheError::InvalidData);
+        }
+
+        Ok(ParsedCache {
+            key,
+            tex_width: tex_width as c_int,
+            tex_height: tex_height as c_int,
+            tex_pixels_use_colors,
+            tex_uv_scale,
+            tex_uv_white_pixel,
+            tex_uv_lines,
+            pack_id_mouse_cursors,
+            pack_id_lines,
+            alpha8,
+            rgba32,
+            custom_rects,
+            fonts,
+        })
+    }
+}
+
+/// 64-bit FNV-1a hasher.
+///
+/// `DefaultHasher` isn't used because its output may change between Rust releases, which would
+/// invalidate every stored cache.
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Fnv64 {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+    fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Builds a slice from a raw Dear ImGui pointer and length, which may be null when empty
+unsafe fn raw_slice<'a, T>(data: *const T, len: c_int) -> &'a [T] {
+    if data.is_null() || len <= 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len as usize)
+    }
+}
+
+/// Copies data into a buffer allocated with `igMemAlloc`, so the atlas can free it
+unsafe fn copy_to_imgui_heap<T: Copy>(data: &[T]) -> *mut T {
+    let ptr = sys::igMemAlloc(std::mem::size_of_val(data)) as *mut T;
+    assert!(!ptr.is_null());
+    ptr.copy_from_nonoverlapping(data.as_ptr(), data.len());
+    ptr
+}
+
+#[test]
+fn test_font_atlas_build_cache_round_trip() {
+    use crate::fonts::atlas::FontSource;
+
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    ctx.fonts()
+        .add_font(&[FontSource::DefaultFontData { config: None }]);
+    assert!(ctx.fonts().save_build_cache().is_none());
+    let (width, height, pixels) = {
+        let mut fonts = ctx.fonts();
+        let texture = fonts.build_alpha8_texture();
+        (texture.width, texture.height, texture.data.to_vec())
+    };
+    let cache = ctx.fonts().save_build_cache().unwrap();
+    let font_count = ctx.fonts().fonts().len();
+    drop(ctx);
+
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let font = ctx
+        .fonts()
+        .add_font(&[FontSource::DefaultFontData { config: None }]);
+    assert!(!ctx.fonts().is_built());
+    assert_eq!(ctx.fonts().load_build_cache(&cache), Ok(()));
+    assert!(ctx.fonts().is_built());
+    assert_eq!(ctx.fonts().fonts().len(), font_count);
+    assert!(ctx.fonts().get_font(font).unwrap().font_size > 0.0);
+    let mut fonts = ctx.fonts();
+    let texture = fonts.build_alpha8_texture();
+    assert_eq!((texture.width, texture.height), (width, height));
+    assert_eq!(texture.data, &pixels[..]);
+    assert_eq!(fonts.save_build_cache().unwrap(), cache);
+}
+
+#[test]
+fn test_font_atlas_build_cache_errors() {
+    use crate::fonts::atlas::{FontConfig, FontSource};
+
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    ctx.fonts()
+        .add_font(&[FontSource::DefaultFontData { config: None }]);
+    ctx.fonts().build_rgba32_texture();
+    let cache = ctx.fonts().save_build_cache().unwrap();
+    drop(ctx);
+
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    ctx.fonts().add_font(&[FontSource::DefaultFontData {
+        config: Some(FontConfig {
+            size_pixels: 20.0,
+            ..FontConfig::default()
+        }),
+    }]);
+    assert_eq!(
+        ctx.fonts().load_build_cache(&cache),
+        Err(FontAtlasCacheError::Stale)
+    );
+    assert_eq!(
+        ctx.fonts().load_build_cache(&cache[..cache.len() - 1]),
+        Err(FontAtlasCacheError::InvalidData)
+    );
+    assert_eq!(
+        ctx.fonts().load_build_cache(b"not a cache"),
+        Err(FontAtlasCacheError::InvalidData)
+    );
+    assert!(!ctx.fonts().is_built());
+}
@@ -38,6 +38,15 @@ impl Font {
     pub fn id(&self) -> FontId {
         FontId(self as *const _)
     }
+    /// Returns the horizontal advance of a character, in pixels at the size of the font
+    #[doc(alias = "GetCharAdvance")]
+    pub fn char_advance(&self, c: char) -> f32 {
+        self.index_advance_x
+            .as_slice()
+            .get(c as usize)
+            .copied()
+            .unwrap_or(self.fallback_advance_x)
+    }
 }
 
 #[test]
@@ -30,6 +30,14 @@ impl FontGlyph {
     pub fn set_visible(&mut self, visible: bool) {
         unsafe { self.raw_mut().set_Visible(visible as u32) }
     }
+    /// Returns the packed codepoint/visible/colored bits
+    pub(crate) fn bitfields(&self) -> u32 {
+        self.bitfields
+    }
+    /// Replaces the packed codepoint/visible/colored bits
+    pub(crate) fn set_bitfields(&mut self, bitfields: u32) {
+        self.bitfields = bitfields;
+    }
 }
 
 unsafe impl RawCast<sys::ImFontGlyph> for FontGlyph {}
@@ -3,9 +3,11 @@ use crate::internal::RawCast;
 use crate::Ui;
 
 pub mod atlas;
+pub mod cache;
 pub mod font;
 pub mod glyph;
 pub mod glyph_ranges;
+pub mod scaled;
 
 /// # Fonts
 impl Ui {
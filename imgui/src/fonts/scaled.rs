@@ -0,0 +1,450 @@
+use std::ptr;
+
+use crate::context::Context;
+use crate::fonts::atlas::{FontConfig, FontId, FontSource};
+use crate::fonts::font::Font;
+use crate::style::Style;
+
+/// Pixel size used by Dear ImGui for the default font when none is given
+const DEFAULT_FONT_SIZE_PIXELS: f32 = 13.0;
+
+/// How [`ScaledFonts`] applies a scale factor to the fonts and the style
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FontScaleMode {
+    /// Fonts are rasterized at `size_pixels * scale_factor` and all style sizes are scaled with
+    /// [`Style::scale_all_sizes`].
+    ///
+    /// Use this when the user interface is laid out in physical pixels.
+    SizePixels,
+    /// Fonts keep their size but are rasterized with `rasterizer_density * scale_factor`. The
+    /// style is left untouched.
+    ///
+    /// Use this when the user interface is laid out in logical pixels and the renderer applies
+    /// [`Io::display_framebuffer_scale`](crate::Io::display_framebuffer_scale).
+    RasterizerDensity,
+}
+
+/// Owned copy of a [`FontSource`], so it can be added to an atlas again later
+#[derive(Clone, Debug)]
+enum OwnedFontSource {
+    DefaultFontData {
+        config: Option<FontConfig>,
+    },
+    TtfData {
+        data: Vec<u8>,
+        size_pixels: f32,
+        config: Option<FontConfig>,
+    },
+}
+
+impl OwnedFontSource {
+    fn new(source: &FontSource<'_>) -> OwnedFontSource {
+        match source {
+            FontSource::DefaultFontData { config } => OwnedFontSource::DefaultFontData {
+                config: config.clone(),
+            },
+            FontSource::TtfData {
+                data,
+                size_pixels,
+                config,
+            } => OwnedFontSource::TtfData {
+                data: data.to_vec(),
+                size_pixels: *size_pixels,
+                config: config.clone(),
+            },
+        }
+    }
+
+    fn scaled(&self, scale_factor: f32, mode: FontScaleMode) -> FontSource<'_> {
+        match self {
+            OwnedFontSource::DefaultFontData { config } => {
+                let mut config = config.clone().unwrap_or_default();
+                if config.size_pixels <= 0.0 {
+                    config.size_pixels = DEFAULT_FONT_SIZE_PIXELS;
+                }
+                scale_font_config(&mut config, scale_factor, mode);
+                FontSource::DefaultFontData {
+                    config: Some(config),
+                }
+            }
+            OwnedFontSource::TtfData {
+                data,
+                size_pixels,
+                config,
+            } => {
+                let mut config = config.clone().unwrap_or_default();
+                scale_font_config(&mut config, scale_factor, mode);
+                let size_pixels = match mode {
+                    FontScaleMode::SizePixels => size_pixels * scale_factor,
+                    FontScaleMode::RasterizerDensity => *size_pixels,
+                };
+                FontSource::TtfData {
+                    data,
+                    size_pixels,
+                    config: Some(config),
+                }
+            }
+        }
+    }
+}
+
+fn scale_font_config(config: &mut FontConfig, scale_factor: f32, mode: FontScaleMode) {
+    match mode {
+        FontScaleMode::SizePixels => {
+            config.size_pixels *= scale_factor;
+            config.glyph_extra_spacing = config.glyph_extra_spacing.map(|v| v * scale_factor);
+            config.glyph_offset = config.glyph_offset.map(|v| v * scale_factor);
+            config.glyph_min_advance_x *= scale_factor;
+            // the default maximum is f32::MAX, which must not overflow into infinity
+            config.glyph_max_advance_x = (config.glyph_max_advance_x * scale_factor).min(f32::MAX);
+        }
+        FontScaleMode::RasterizerDensity => {
+            config.rasterizer_density *= scale_factor;
+        }
+    }
+}
+
+/// A font added through [`ScaledFonts::add_font`]
+#[derive(Clone, Debug)]
+struct ScaledFont {
+    sources: Vec<OwnedFontSource>,
+    id: FontId,
+}
+
+/// Mapping from the [`FontId`]s that existed before a font atlas rebuild to the new ones.
+///
+/// Returned by [`ScaledFonts::rebuild`]. Any `FontId` stored by the application must be updated
+/// with it, because the old ids no longer refer to a font.
+#[derive(Clone, Debug, Default)]
+pub struct FontIdRemap {
+    pairs: Vec<(FontId, FontId)>,
+}
+
+impl FontIdRemap {
+    /// Returns the new id of the font that was previously identified by `old`.
+    ///
+    /// Returns `None` if `old` was not a font managed by [`ScaledFonts`].
+    pub fn get(&self, old: FontId) -> Option<FontId> {
+        self.pairs
+            .iter()
+            .find(|(from, _)| *from == old)
+            .map(|&(_, to)| to)
+    }
+
+    /// Replaces `id` with its new value.
+    ///
+    /// Returns false (and leaves `id` untouched) if `id` is not part of this mapping.
+    pub fn update(&self, id: &mut FontId) -> bool {
+        match self.get(*id) {
+            Some(new_id) => {
+                *id = new_id;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over `(old, new)` font id pairs
+    pub fn iter(&self) -> impl Iterator<Item = (FontId, FontId)> + '_ {
+        self.pairs.iter().copied()
+    }
+}
+
+/// Keeps the descriptors of every font in the context's font atlas, so the atlas can be rebuilt
+/// for a new display scale factor (for example when a window moves to a monitor with a different
+/// DPI).
+///
+/// All fonts must be added through [`ScaledFonts::add_font`]: a rebuild clears the atlas
+/// completely, so fonts that were added to the [`FontAtlas`](crate::FontAtlas) directly are lost.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let new_scale_factor = 2.0;
+/// let mut fonts = ScaledFonts::new(&mut ctx, 1.0, FontScaleMode::SizePixels);
+/// let mut font = fonts.add_font(
+///     &mut ctx,
+///     &[FontSource::DefaultFontData { config: None }],
+/// );
+///
+/// // The window moved to another monitor
+/// fonts.set_scale_factor(new_scale_factor);
+///
+/// // Between frames
+/// if let Some(remap) = fonts.update(&mut ctx) {
+///     remap.update(&mut font);
+/// }
+/// if fonts.is_texture_dirty() {
+///     // Re-upload `ctx.fonts().build_rgba32_texture()` to the GPU
+///     fonts.mark_texture_uploaded();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScaledFonts {
+    fonts: Vec<ScaledFont>,
+    mode: FontScaleMode,
+    scale_factor: f32,
+    applied_scale_factor: f32,
+    base_style: Style,
+    texture_dirty: bool,
+}
+
+impl ScaledFonts {
+    /// Creates a new font manager for the given context.
+    ///
+    /// The current style of the context is taken as the unscaled base style, and is immediately
+    /// scaled by `scale_factor` if `mode` is [`FontScaleMode::SizePixels`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` is not a positive finite number.
+    pub fn new(ctx: &mut Context, scale_factor: f32, mode: FontScaleMode) -> ScaledFonts {
+        assert_valid_scale_factor(scale_factor);
+        let fonts = ScaledFonts {
+            fonts: Vec::new(),
+            mode,
+            scale_factor,
+            applied_scale_factor: scale_factor,
+            base_style: *ctx.style(),
+            texture_dirty: true,
+        };
+        fonts.apply_style(ctx);
+        fonts
+    }
+
+    /// Adds a font to the context's font atlas at the current scale factor, and remembers its
+    /// sources so it can be added again on the next rebuild.
+    ///
+    /// The font data is copied, so `font_sources` doesn't need to outlive this call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `font_sources` is empty or if a frame is in progress.
+    #[doc(alias = "AddFont")]
+    pub fn add_font(&mut self, ctx: &mut Context, font_sources: &[FontSource<'_>]) -> FontId {
+        assert!(
+            !ctx.fonts().is_locked(),
+            "Fonts cannot be added while a frame is in progress"
+        );
+        let sources: Vec<OwnedFontSource> = font_sources.iter().map(OwnedFontSource::new).collect();
+        let scaled: Vec<FontSource<'_>> = sources
+            .iter()
+            .map(|source| source.scaled(self.applied_scale_factor, self.mode))
+            .collect();
+        let id = ctx.fonts().add_font(&scaled);
+        self.fonts.push(ScaledFont { sources, id });
+        self.texture_dirty = true;
+        id
+    }
+
+    /// Returns the ids of all managed fonts, in the order they were added
+    pub fn fonts(&self) -> Vec<FontId> {
+        self.fonts.iter().map(|font| font.id).collect()
+    }
+
+    /// Returns the scale factor mode
+    pub fn mode(&self) -> FontScaleMode {
+        self.mode
+    }
+
+    /// Returns the requested scale factor.
+    ///
+    /// This may differ from the scale factor of the atlas until the next [`update`].
+    ///
+    /// [`update`]: Self::update
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Requests a new scale factor.
+    ///
+    /// Nothing is rebuilt until [`update`] is called between frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` is not a positive finite number.
+    ///
+    /// [`update`]: Self::update
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        assert_valid_scale_factor(scale_factor);
+        self.scale_factor = scale_factor;
+    }
+
+    /// Returns true if the requested scale factor differs from the one the atlas was built with
+    pub fn needs_rebuild(&self) -> bool {
+        self.scale_factor != self.applied_scale_factor
+    }
+
+    /// Returns the unscaled style that is scaled on every rebuild
+    pub fn base_style(&self) -> &Style {
+        &self.base_style
+    }
+
+    /// Replaces the unscaled base style, and applies it to the context at the current scale
+    /// factor.
+    pub fn set_base_style(&mut self, ctx: &mut Context, style: Style) {
+        self.base_style = style;
+        self.apply_style(ctx);
+    }
+
+    /// Rebuilds the atlas if the scale factor changed since the last rebuild.
+    ///
+    /// Must be called between frames, i.e. before [`Context::new_frame`] or after
+    /// [`Context::render`]. Returns `None` if nothing had to be rebuilt.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a frame is in progress.
+    pub fn update(&mut self, ctx: &mut Context) -> Option<FontIdRemap> {
+        if self.needs_rebuild() {
+            Some(self.rebuild(ctx))
+        } else {
+            None
+        }
+    }
+
+    /// Clears the context's font atlas and adds every managed font again at the requested scale
+    /// factor, then rescales the style.
+    ///
+    /// The default font of the context is carried over to its new id. The atlas texture is
+    /// marked dirty and must be re-uploaded by the renderer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a frame is in progress.
+    pub fn rebuild(&mut self, ctx: &mut Context) -> FontIdRemap {
+        assert!(
+            !ctx.fonts().is_locked(),
+            "The font atlas cannot be rebuilt while a frame is in progress"
+        );
+        let old_default_font = FontId(ctx.io().font_default);
+
+        let mut atlas = ctx.fonts();
+        atlas.clear();
+        let mut remap = FontIdRemap {
+            pairs: Vec::with_capacity(self.fonts.len()),
+        };
+        for font in &mut self.fonts {
+            let sources: Vec<FontSource<'_>> = font
+                .sources
+                .iter()
+                .map(|source| source.scaled(self.scale_factor, self.mode))
+                .collect();
+            let new_id = atlas.add_font(&sources);
+            remap.pairs.push((font.id, new_id));
+            font.id = new_id;
+        }
+        if !self.fonts.is_empty() {
+            atlas.build();
+        }
+        drop(atlas);
+
+        ctx.io_mut().font_default = remap
+            .get(old_default_font)
+            .map_or(ptr::null_mut(), |id| id.0 as *mut Font);
+        self.applied_scale_factor = self.scale_factor;
+        self.apply_style(ctx);
+        self.texture_dirty = true;
+        remap
+    }
+
+    /// Returns true if the atlas changed since the last call to [`mark_texture_uploaded`].
+    ///
+    /// [`mark_texture_uploaded`]: Self::mark_texture_uploaded
+    pub fn is_texture_dirty(&self) -> bool {
+        self.texture_dirty
+    }
+
+    /// Tells the font manager that the renderer uploaded the current atlas texture
+    pub fn mark_texture_uploaded(&mut self) {
+        self.texture_dirty = false;
+    }
+
+    fn apply_style(&self, ctx: &mut Context) {
+        let mut style = self.base_style;
+        if self.mode == FontScaleMode::SizePixels {
+            style.scale_all_sizes(self.applied_scale_factor);
+        }
+        *ctx.style_mut() = style;
+    }
+}
+
+fn assert_valid_scale_factor(scale_factor: f32) {
+    assert!(
+        scale_factor.is_finite() && scale_factor > 0.0,
+        "scale factor must be a positive finite number"
+    );
+}
+
+#[test]
+fn test_scaled_fonts_rebuild_remaps_ids() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let mut fonts = ScaledFonts::new(&mut ctx, 1.0, FontScaleMode::SizePixels);
+    let first = fonts.add_font(&mut ctx, &[FontSource::DefaultFontData { config: None }]);
+    let second = fonts.add_font(
+        &mut ctx,
+        &[FontSource::DefaultFontData {
+            config: Some(FontConfig {
+                size_pixels: 20.0,
+                ..FontConfig::default()
+            }),
+        }],
+    );
+    ctx.fonts().build_rgba32_texture();
+    fonts.mark_texture_uploaded();
+    assert!(fonts.update(&mut ctx).is_none());
+    assert!(!fonts.is_texture_dirty());
+
+    fonts.set_scale_factor(2.0);
+    let remap = fonts.update(&mut ctx).expect("scale factor changed");
+    assert!(fonts.is_texture_dirty());
+    assert!(!fonts.needs_rebuild());
+    let new_first = remap.get(first).unwrap();
+    let new_second = remap.get(second).unwrap();
+    assert_eq!(fonts.fonts(), vec![new_first, new_second]);
+    assert_eq!(ctx.fonts().fonts(), vec![new_first, new_second]);
+    assert_eq!(ctx.fonts().get_font(new_first).unwrap().font_size, 26.0);
+    assert_eq!(ctx.fonts().get_font(new_second).unwrap().font_size, 40.0);
+
+    let mut stored = second;
+    assert!(remap.update(&mut stored));
+    assert_eq!(stored, new_second);
+}
+
+#[test]
+fn test_scaled_fonts_scale_style_from_base() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let base_padding = ctx.style().window_padding;
+    let mut fonts = ScaledFonts::new(&mut ctx, 2.0, FontScaleMode::SizePixels);
+    assert_eq!(
+        ctx.style().window_padding,
+        [base_padding[0] * 2.0, base_padding[1] * 2.0]
+    );
+    fonts.set_scale_factor(1.5);
+    fonts.update(&mut ctx);
+    assert_eq!(
+        ctx.style().window_padding,
+        [base_padding[0] * 1.5, base_padding[1] * 1.5]
+    );
+
+    let mut fonts = ScaledFonts::new(&mut ctx, 1.0, FontScaleMode::RasterizerDensity);
+    let style = *ctx.style();
+    let font = fonts.add_font(&mut ctx, &[FontSource::DefaultFontData { config: None }]);
+    fonts.set_scale_factor(3.0);
+    let remap = fonts.update(&mut ctx).unwrap();
+    assert_eq!(*ctx.style(), style);
+    let font = remap.get(font).unwrap();
+    assert_eq!(ctx.fonts().get_font(font).unwrap().font_size, 13.0);
+}
+
+#[test]
+#[should_panic(expected = "while a frame is in progress")]
+fn test_scaled_fonts_rebuild_mid_frame() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut fonts = ScaledFonts::new(&mut ctx, 1.0, FontScaleMode::SizePixels);
+    fonts.set_scale_factor(2.0);
+    ctx.new_frame();
+    fonts.update(&mut ctx);
+}
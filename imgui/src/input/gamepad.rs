@@ -0,0 +1,557 @@
+//! Backend agnostic gamepad support.
+//!
+//! Backends fill a [`GamepadState`] from whatever gamepad API they use, and a [`GamepadUpdater`]
+//! turns it into the `Key::Gamepad*` events Dear ImGui expects, applying deadzones and only
+//! sending events for keys that changed since the previous frame.
+//!
+//! Gamepad navigation also has to be enabled with [`ConfigFlags::NAV_ENABLE_GAMEPAD`].
+//!
+//! [`ConfigFlags::NAV_ENABLE_GAMEPAD`]: crate::ConfigFlags::NAV_ENABLE_GAMEPAD
+
+use bitflags::bitflags;
+
+use crate::input::keyboard::Key;
+use crate::io::{BackendFlags, InputEvent, Io};
+
+bitflags! {
+    /// Digital gamepad buttons.
+    ///
+    /// Face buttons are identified by their position, not by their label.
+    #[repr(transparent)]
+    pub struct GamepadButtons: u32 {
+        /// Bottom face button (A on Xbox, B on Nintendo, Cross on PlayStation)
+        const FACE_DOWN = 1 << 0;
+        /// Right face button (B on Xbox, A on Nintendo, Circle on PlayStation)
+        const FACE_RIGHT = 1 << 1;
+        /// Left face button (X on Xbox, Y on Nintendo, Square on PlayStation)
+        const FACE_LEFT = 1 << 2;
+        /// Top face button (Y on Xbox, X on Nintendo, Triangle on PlayStation)
+        const FACE_UP = 1 << 3;
+        const DPAD_LEFT = 1 << 4;
+        const DPAD_RIGHT = 1 << 5;
+        const DPAD_UP = 1 << 6;
+        const DPAD_DOWN = 1 << 7;
+        /// Left shoulder button (LB, L, L1)
+        const LEFT_SHOULDER = 1 << 8;
+        /// Right shoulder button (RB, R, R1)
+        const RIGHT_SHOULDER = 1 << 9;
+        /// Left stick click (L3)
+        const LEFT_STICK = 1 << 10;
+        /// Right stick click (R3)
+        const RIGHT_STICK = 1 << 11;
+        /// Start/Menu/Options/+ button
+        const START = 1 << 12;
+        /// Back/View/Share/- button
+        const BACK = 1 << 13;
+    }
+}
+
+impl Default for GamepadButtons {
+    fn default() -> Self {
+        GamepadButtons::empty()
+    }
+}
+
+/// The raw state of a gamepad, as read by the backend
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct GamepadState {
+    /// Whether a gamepad is connected. All other fields are ignored if this is false
+    pub connected: bool,
+    /// Buttons currently held down
+    pub buttons: GamepadButtons,
+    /// Left stick position, from `-1.0` to `1.0` on both axes, with `y` pointing up
+    pub left_stick: [f32; 2],
+    /// Right stick position, from `-1.0` to `1.0` on both axes, with `y` pointing up
+    pub right_stick: [f32; 2],
+    /// Left trigger (LT, ZL, L2) position, from `0.0` to `1.0`
+    pub left_trigger: f32,
+    /// Right trigger (RT, ZR, R2) position, from `0.0` to `1.0`
+    pub right_trigger: f32,
+}
+
+/// Face button layout of a gamepad.
+///
+/// Dear ImGui activates items with [`Key::GamepadFaceDown`] and cancels with
+/// [`Key::GamepadFaceRight`]. The layout decides which physical button does what, following the
+/// platform convention, and how face buttons are labelled.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub enum GamepadLayout {
+    /// A (bottom) activates, B (right) cancels
+    #[default]
+    Xbox,
+    /// A (right) activates, B (bottom) cancels. The face buttons are swapped so the labels match
+    /// their meaning.
+    Nintendo,
+    /// Cross (bottom) activates, Circle (right) cancels
+    PlayStation,
+}
+
+impl GamepadLayout {
+    /// Returns the Dear ImGui key sent for a physical face button.
+    ///
+    /// Returns `None` if `button` isn't a single face button.
+    pub fn face_button_key(self, button: GamepadButtons) -> Option<Key> {
+        let swap = self == GamepadLayout::Nintendo;
+        let key = match button {
+            GamepadButtons::FACE_DOWN if swap => Key::GamepadFaceRight,
+            GamepadButtons::FACE_RIGHT if swap => Key::GamepadFaceDown,
+            GamepadButtons::FACE_LEFT if swap => Key::GamepadFaceUp,
+            GamepadButtons::FACE_UP if swap => Key::GamepadFaceLeft,
+            GamepadButtons::FACE_DOWN => Key::GamepadFaceDown,
+            GamepadButtons::FACE_RIGHT => Key::GamepadFaceRight,
+            GamepadButtons::FACE_LEFT => Key::GamepadFaceLeft,
+            GamepadButtons::FACE_UP => Key::GamepadFaceUp,
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// Returns the label printed on the button that sends `key`, e.g. for showing button prompts.
+    ///
+    /// Returns `None` if `key` isn't one of the gamepad face keys.
+    pub fn face_button_label(self, key: Key) -> Option<&'static str> {
+        let labels = match self {
+            GamepadLayout::Xbox => ["A", "B", "X", "Y"],
+            GamepadLayout::Nintendo => ["A", "B", "X", "Y"],
+            GamepadLayout::PlayStation => ["Cross", "Circle", "Square", "Triangle"],
+        };
+        let index = match key {
+            Key::GamepadFaceDown => 0,
+            Key::GamepadFaceRight => 1,
+            Key::GamepadFaceLeft => 2,
+            Key::GamepadFaceUp => 3,
+            _ => return None,
+        };
+        Some(labels[index])
+    }
+}
+
+/// Configuration of a [`GamepadUpdater`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GamepadConfig {
+    /// Face button layout (default: [`GamepadLayout::Xbox`])
+    pub layout: GamepadLayout,
+    /// Stick movement ignored around the center, from `0.0` to `1.0` (default: `0.25`)
+    pub stick_deadzone: f32,
+    /// Trigger movement ignored at rest, from `0.0` to `1.0` (default: `0.1`)
+    pub trigger_deadzone: f32,
+    /// Analog value, after applying the deadzone, above which an analog key counts as held down
+    /// (default: `0.1`)
+    pub analog_press_threshold: f32,
+}
+
+impl Default for GamepadConfig {
+    fn default() -> Self {
+        GamepadConfig {
+            layout: GamepadLayout::Xbox,
+            stick_deadzone: 0.25,
+            trigger_deadzone: 0.1,
+            analog_press_threshold: 0.1,
+        }
+    }
+}
+
+const DIGITAL_KEYS: [(GamepadButtons, Key); 10] = [
+    (GamepadButtons::DPAD_LEFT, Key::GamepadDpadLeft),
+    (GamepadButtons::DPAD_RIGHT, Key::GamepadDpadRight),
+    (GamepadButtons::DPAD_UP, Key::GamepadDpadUp),
+    (GamepadButtons::DPAD_DOWN, Key::GamepadDpadDown),
+    (GamepadButtons::LEFT_SHOULDER, Key::GamepadL1),
+    (GamepadButtons::RIGHT_SHOULDER, Key::GamepadR1),
+    (GamepadButtons::LEFT_STICK, Key::GamepadL3),
+    (GamepadButtons::RIGHT_STICK, Key::GamepadR3),
+    (GamepadButtons::START, Key::GamepadStart),
+    (GamepadButtons::BACK, Key::GamepadBack),
+];
+
+const FACE_BUTTONS: [GamepadButtons; 4] = [
+    GamepadButtons::FACE_DOWN,
+    GamepadButtons::FACE_RIGHT,
+    GamepadButtons::FACE_LEFT,
+    GamepadButtons::FACE_UP,
+];
+
+/// Every key sent by a [`GamepadUpdater`], in the order of [`GamepadUpdater::keys`]
+const GAMEPAD_KEYS: [Key; 24] = [
+    Key::GamepadDpadLeft,
+    Key::GamepadDpadRight,
+    Key::GamepadDpadUp,
+    Key::GamepadDpadDown,
+    Key::GamepadL1,
+    Key::GamepadR1,
+    Key::GamepadL3,
+    Key::GamepadR3,
+    Key::GamepadStart,
+    Key::GamepadBack,
+    Key::GamepadFaceDown,
+    Key::GamepadFaceRight,
+    Key::GamepadFaceLeft,
+    Key::GamepadFaceUp,
+    Key::GamepadL2,
+    Key::GamepadR2,
+    Key::GamepadLStickLeft,
+    Key::GamepadLStickRight,
+    Key::GamepadLStickUp,
+    Key::GamepadLStickDown,
+    Key::GamepadRStickLeft,
+    Key::GamepadRStickRight,
+    Key::GamepadRStickUp,
+    Key::GamepadRStickDown,
+];
+
+/// State of a single gamepad key as last sent to Dear ImGui
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct KeyState {
+    down: bool,
+    value: f32,
+}
+
+/// Turns [`GamepadState`]s into Dear ImGui gamepad key events.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # fn read_gamepad() -> GamepadState { GamepadState::default() }
+/// ctx.io_mut().config_flags |= ConfigFlags::NAV_ENABLE_GAMEPAD;
+/// let mut gamepad = GamepadUpdater::new(GamepadConfig::default());
+/// loop {
+///     gamepad.update(ctx.io_mut(), &read_gamepad());
+///     let ui = ctx.new_frame();
+///     // ...
+/// #   break;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GamepadUpdater {
+    config: GamepadConfig,
+    keys: [KeyState; GAMEPAD_KEYS.len()],
+}
+
+impl GamepadUpdater {
+    /// Creates an updater with no buttons held down
+    pub fn new(config: GamepadConfig) -> GamepadUpdater {
+        GamepadUpdater {
+            config,
+            keys: Default::default(),
+        }
+    }
+    /// Returns the configuration
+    pub fn config(&self) -> &GamepadConfig {
+        &self.config
+    }
+    /// Changes the configuration. Takes effect on the next update
+    pub fn set_config(&mut self, config: GamepadConfig) {
+        self.config = config;
+    }
+    /// Sends the key events for `state` to `io`, and sets or clears
+    /// [`BackendFlags::HAS_GAMEPAD`] depending on whether a gamepad is connected.
+    ///
+    /// Call this once per frame, before [`Context::new_frame`](crate::Context::new_frame).
+    pub fn update(&mut self, io: &mut Io, state: &GamepadState) {
+        io.backend_flags
+            .set(BackendFlags::HAS_GAMEPAD, state.connected);
+        self.update_with(state, |event| io.push_event(event));
+    }
+    /// Like [`GamepadUpdater::update`], but passes the events to `send` instead of queueing them
+    /// in an [`Io`], e.g. to record them.
+    ///
+    /// Only keys whose state changed since the previous update produce an event. When the
+    /// gamepad gets disconnected, every key still held down is released.
+    pub fn update_with(&mut self, state: &GamepadState, mut send: impl FnMut(InputEvent)) {
+        let new_keys = self.key_states(state);
+        for ((key, old), new) in GAMEPAD_KEYS.iter().zip(&mut self.keys).zip(new_keys) {
+            if *old == new {
+                continue;
+            }
+            *old = new;
+            if is_analog(*key) {
+                send(InputEvent::KeyAnalog {
+                    key: *key,
+                    down: new.down,
+                    value: new.value,
+                });
+            } else {
+                send(InputEvent::Key {
+                    key: *key,
+                    down: new.down,
+                });
+            }
+        }
+    }
+    /// Returns whether each gamepad key is held down and its analog value, as last sent to Dear
+    /// ImGui
+    pub fn keys(&self) -> impl Iterator<Item = (Key, bool, f32)> + '_ {
+        GAMEPAD_KEYS
+            .iter()
+            .zip(&self.keys)
+            .map(|(&key, state)| (key, state.down, state.value))
+    }
+
+    fn key_states(&self, state: &GamepadState) -> [KeyState; GAMEPAD_KEYS.len()] {
+        let mut keys = [KeyState::default(); GAMEPAD_KEYS.len()];
+        if !state.connected {
+            return keys;
+        }
+        let digital = |down: bool| KeyState {
+            down,
+            value: if down { 1.0 } else { 0.0 },
+        };
+        let analog = |value: f32, deadzone: f32| {
+            let value = apply_deadzone(value, deadzone);
+            KeyState {
+                down: value > self.config.analog_press_threshold,
+                value,
+            }
+        };
+        for (index, (button, _)) in DIGITAL_KEYS.iter().enumerate() {
+            keys[index] = digital(state.buttons.contains(*button));
+        }
+        for button in FACE_BUTTONS {
+            let key = self.config.layout.face_button_key(button).unwrap();
+            let index = GAMEPAD_KEYS.iter().position(|&k| k == key).unwrap();
+            keys[index] = digital(state.buttons.contains(button));
+        }
+        let axes = [
+            state.left_trigger,
+            state.right_trigger,
+            -state.left_stick[0],
+            state.left_stick[0],
+            state.left_stick[1],
+            -state.left_stick[1],
+            -state.right_stick[0],
+            state.right_stick[0],
+            state.right_stick[1],
+            -state.right_stick[1],
+        ];
+        for (index, value) in axes.into_iter().enumerate() {
+            let deadzone = if index < 2 {
+                self.config.trigger_deadzone
+            } else {
+                self.config.stick_deadzone
+            };
+            keys[DIGITAL_KEYS.len() + FACE_BUTTONS.len() + index] = analog(value, deadzone);
+        }
+        keys
+    }
+}
+
+fn is_analog(key: Key) -> bool {
+    matches!(
+        key,
+        Key::GamepadL2
+            | Key::GamepadR2
+            | Key::GamepadLStickLeft
+            | Key::GamepadLStickRight
+            | Key::GamepadLStickUp
+            | Key::GamepadLStickDown
+            | Key::GamepadRStickLeft
+            | Key::GamepadRStickRight
+            | Key::GamepadRStickUp
+            | Key::GamepadRStickDown
+    )
+}
+
+/// Rescales `value` so the deadzone maps to `0.0` and the full range still reaches `1.0`.
+///
+/// Negative values (the opposite stick direction) map to `0.0`.
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value <= deadzone || deadzone >= 1.0 {
+        return 0.0;
+    }
+    ((value - deadzone) / (1.0 - deadzone)).min(1.0)
+}
+
+#[cfg(test)]
+fn collect(updater: &mut GamepadUpdater, state: &GamepadState) -> Vec<InputEvent> {
+    let mut events = Vec::new();
+    updater.update_with(state, |event| events.push(event));
+    events
+}
+
+#[test]
+fn test_gamepad_edges() {
+    let mut updater = GamepadUpdater::new(GamepadConfig::default());
+    let mut state = GamepadState {
+        connected: true,
+        ..Default::default()
+    };
+    assert_eq!(collect(&mut updater, &state), vec![]);
+
+    state.buttons = GamepadButtons::FACE_DOWN | GamepadButtons::START;
+    let events = collect(&mut updater, &state);
+    assert_eq!(events.len(), 2);
+    assert!(events.contains(&InputEvent::Key {
+        key: Key::GamepadFaceDown,
+        down: true
+    }));
+    assert!(events.contains(&InputEvent::Key {
+        key: Key::GamepadStart,
+        down: true
+    }));
+    // holding the buttons doesn't repeat the events
+    assert_eq!(collect(&mut updater, &state), vec![]);
+
+    state.buttons = GamepadButtons::START;
+    assert_eq!(
+        collect(&mut updater, &state),
+        vec![InputEvent::Key {
+            key: Key::GamepadFaceDown,
+            down: false
+        }]
+    );
+
+    // disconnecting releases everything
+    state.connected = false;
+    assert_eq!(
+        collect(&mut updater, &state),
+        vec![InputEvent::Key {
+            key: Key::GamepadStart,
+            down: false
+        }]
+    );
+    assert!(updater.keys().all(|(_, down, _)| !down));
+}
+
+#[test]
+fn test_gamepad_deadzones() {
+    let mut updater = GamepadUpdater::new(GamepadConfig::default());
+    let mut state = GamepadState {
+        connected: true,
+        left_stick: [0.2, -0.2],
+        left_trigger: 0.05,
+        ..Default::default()
+    };
+    assert_eq!(collect(&mut updater, &state), vec![]);
+
+    state.left_stick = [1.0, -0.625];
+    state.right_trigger = 1.0;
+    let events = collect(&mut updater, &state);
+    assert_eq!(events.len(), 3);
+    assert!(events.contains(&InputEvent::KeyAnalog {
+        key: Key::GamepadLStickRight,
+        down: true,
+        value: 1.0
+    }));
+    assert!(events.contains(&InputEvent::KeyAnalog {
+        key: Key::GamepadLStickDown,
+        down: true,
+        value: 0.5
+    }));
+    assert!(events.contains(&InputEvent::KeyAnalog {
+        key: Key::GamepadR2,
+        down: true,
+        value: 1.0
+    }));
+
+    // small movements above the deadzone are sent, but aren't presses
+    state.left_stick = [0.3, 0.0];
+    state.right_trigger = 0.0;
+    let events = collect(&mut updater, &state);
+    let (down, value) = events
+        .iter()
+        .find_map(|event| match *event {
+            InputEvent::KeyAnalog {
+                key: Key::GamepadLStickRight,
+                down,
+                value,
+            } => Some((down, value)),
+            _ => None,
+        })
+        .unwrap();
+    assert!(!down);
+    assert!(value > 0.0 && value < 0.1);
+}
+
+#[test]
+fn test_gamepad_layouts() {
+    let mut updater = GamepadUpdater::new(GamepadConfig {
+        layout: GamepadLayout::Nintendo,
+        ..Default::default()
+    });
+    let state = GamepadState {
+        connected: true,
+        buttons: GamepadButtons::FACE_RIGHT,
+        ..Default::default()
+    };
+    assert_eq!(
+        collect(&mut updater, &state),
+        vec![InputEvent::Key {
+            key: Key::GamepadFaceDown,
+            down: true
+        }]
+    );
+    assert_eq!(GamepadLayout::Xbox.face_button_label(Key::A), None);
+}
+
+#[test]
+fn test_gamepad_face_button_labels() {
+    let keys = [
+        Key::GamepadFaceDown,
+        Key::GamepadFaceRight,
+        Key::GamepadFaceLeft,
+        Key::GamepadFaceUp,
+    ];
+    let buttons = [
+        GamepadButtons::FACE_DOWN,
+        GamepadButtons::FACE_RIGHT,
+        GamepadButtons::FACE_LEFT,
+        GamepadButtons::FACE_UP,
+    ];
+    // labels of the keys, and of the physical buttons, in the order above
+    let layouts = [
+        (
+            GamepadLayout::Xbox,
+            ["A", "B", "X", "Y"],
+            ["A", "B", "X", "Y"],
+        ),
+        (
+            GamepadLayout::Nintendo,
+            ["A", "B", "X", "Y"],
+            ["B", "A", "Y", "X"],
+        ),
+        (
+            GamepadLayout::PlayStation,
+            ["Cross", "Circle", "Square", "Triangle"],
+            ["Cross", "Circle", "Square", "Triangle"],
+        ),
+    ];
+    for (layout, key_labels, button_labels) in layouts {
+        for (key, label) in keys.into_iter().zip(key_labels) {
+            assert_eq!(
+                layout.face_button_label(key),
+                Some(label),
+                "{layout:?} {key:?}"
+            );
+        }
+        // the label of the key sent by a button is the label printed on it
+        for (button, label) in buttons.into_iter().zip(button_labels) {
+            let key = layout.face_button_key(button).unwrap();
+            assert_eq!(
+                layout.face_button_label(key),
+                Some(label),
+                "{layout:?} {button:?}"
+            );
+        }
+    }
+    assert_eq!(
+        GamepadLayout::Xbox.face_button_key(GamepadButtons::FACE_DOWN | GamepadButtons::FACE_UP),
+        None
+    );
+}
+
+#[test]
+fn test_gamepad_backend_flags() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let mut updater = GamepadUpdater::new(GamepadConfig::default());
+    let mut state = GamepadState {
+        connected: true,
+        ..Default::default()
+    };
+    updater.update(ctx.io_mut(), &state);
+    assert!(ctx.io().backend_flags.contains(BackendFlags::HAS_GAMEPAD));
+    state.connected = false;
+    updater.update(ctx.io_mut(), &state);
+    assert!(!ctx.io().backend_flags.contains(BackendFlags::HAS_GAMEPAD));
+}
@@ -1,3 +1,6 @@
+use std::fmt;
+use std::str::FromStr;
+
 use crate::sys;
 use crate::Ui;
 
@@ -324,8 +327,203 @@ impl Key {
     ];
     /// Total count of `Key` variants
     pub const COUNT: usize = sys::ImGuiKey_NamedKey_COUNT as usize;
+
+    /// Returns the name of the key, as returned by Dear ImGui's `GetKeyName`.
+    ///
+    /// The name can be parsed back into a `Key` with [`str::parse`].
+    // Note: we do this in Rust (where we have better promises of enums
+    // being of the right type) than in C++ to avoid the FFI. We confirm in
+    // Unit Tests that we are accurate.
+    #[doc(alias = "GetKeyName")]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Key::Tab => "Tab",
+            Key::LeftArrow => "LeftArrow",
+            Key::RightArrow => "RightArrow",
+            Key::UpArrow => "UpArrow",
+            Key::DownArrow => "DownArrow",
+            Key::PageUp => "PageUp",
+            Key::PageDown => "PageDown",
+            Key::Home => "Home",
+            Key::End => "End",
+            Key::Insert => "Insert",
+            Key::Delete => "Delete",
+            Key::Backspace => "Backspace",
+            Key::Space => "Space",
+            Key::Enter => "Enter",
+            Key::Escape => "Escape",
+            Key::LeftCtrl => "LeftCtrl",
+            Key::LeftShift => "LeftShift",
+            Key::LeftAlt => "LeftAlt",
+            Key::LeftSuper => "LeftSuper",
+            Key::RightCtrl => "RightCtrl",
+            Key::RightShift => "RightShift",
+            Key::RightAlt => "RightAlt",
+            Key::RightSuper => "RightSuper",
+            Key::Menu => "Menu",
+            Key::Alpha0 => "0",
+            Key::Alpha1 => "1",
+            Key::Alpha2 => "2",
+            Key::Alpha3 => "3",
+            Key::Alpha4 => "4",
+            Key::Alpha5 => "5",
+            Key::Alpha6 => "6",
+            Key::Alpha7 => "7",
+            Key::Alpha8 => "8",
+            Key::Alpha9 => "9",
+            Key::A => "A",
+            Key::B => "B",
+            Key::C => "C",
+            Key::D => "D",
+            Key::E => "E",
+            Key::F => "F",
+            Key::G => "G",
+            Key::H => "H",
+            Key::I => "I",
+            Key::J => "J",
+            Key::K => "K",
+            Key::L => "L",
+            Key::M => "M",
+            Key::N => "N",
+            Key::O => "O",
+            Key::P => "P",
+            Key::Q => "Q",
+            Key::R => "R",
+            Key::S => "S",
+            Key::T => "T",
+            Key::U => "U",
+            Key::V => "V",
+            Key::W => "W",
+            Key::X => "X",
+            Key::Y => "Y",
+            Key::Z => "Z",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::F16 => "F16",
+            Key::F17 => "F17",
+            Key::F18 => "F18",
+            Key::F19 => "F19",
+            Key::F20 => "F20",
+            Key::F21 => "F21",
+            Key::F22 => "F22",
+            Key::F23 => "F23",
+            Key::F24 => "F24",
+            Key::Apostrophe => "Apostrophe",
+            Key::Comma => "Comma",
+            Key::Minus => "Minus",
+            Key::Period => "Period",
+            Key::Slash => "Slash",
+            Key::Semicolon => "Semicolon",
+            Key::Equal => "Equal",
+            Key::LeftBracket => "LeftBracket",
+            Key::Backslash => "Backslash",
+            Key::RightBracket => "RightBracket",
+            Key::GraveAccent => "GraveAccent",
+            Key::CapsLock => "CapsLock",
+            Key::ScrollLock => "ScrollLock",
+            Key::NumLock => "NumLock",
+            Key::PrintScreen => "PrintScreen",
+            Key::Pause => "Pause",
+            Key::Keypad0 => "Keypad0",
+            Key::Keypad1 => "Keypad1",
+            Key::Keypad2 => "Keypad2",
+            Key::Keypad3 => "Keypad3",
+            Key::Keypad4 => "Keypad4",
+            Key::Keypad5 => "Keypad5",
+            Key::Keypad6 => "Keypad6",
+            Key::Keypad7 => "Keypad7",
+            Key::Keypad8 => "Keypad8",
+            Key::Keypad9 => "Keypad9",
+            Key::KeypadDecimal => "KeypadDecimal",
+            Key::KeypadDivide => "KeypadDivide",
+            Key::KeypadMultiply => "KeypadMultiply",
+            Key::KeypadSubtract => "KeypadSubtract",
+            Key::KeypadAdd => "KeypadAdd",
+            Key::KeypadEnter => "KeypadEnter",
+            Key::KeypadEqual => "KeypadEqual",
+            Key::AppBack => "AppBack",
+            Key::AppForward => "AppForward",
+            Key::GamepadStart => "GamepadStart",
+            Key::GamepadBack => "GamepadBack",
+            Key::GamepadFaceLeft => "GamepadFaceLeft",
+            Key::GamepadFaceRight => "GamepadFaceRight",
+            Key::GamepadFaceUp => "GamepadFaceUp",
+            Key::GamepadFaceDown => "GamepadFaceDown",
+            Key::GamepadDpadLeft => "GamepadDpadLeft",
+            Key::GamepadDpadRight => "GamepadDpadRight",
+            Key::GamepadDpadUp => "GamepadDpadUp",
+            Key::GamepadDpadDown => "GamepadDpadDown",
+            Key::GamepadL1 => "GamepadL1",
+            Key::GamepadR1 => "GamepadR1",
+            Key::GamepadL2 => "GamepadL2",
+            Key::GamepadR2 => "GamepadR2",
+            Key::GamepadL3 => "GamepadL3",
+            Key::GamepadR3 => "GamepadR3",
+            Key::GamepadLStickLeft => "GamepadLStickLeft",
+            Key::GamepadLStickRight => "GamepadLStickRight",
+            Key::GamepadLStickUp => "GamepadLStickUp",
+            Key::GamepadLStickDown => "GamepadLStickDown",
+            Key::GamepadRStickLeft => "GamepadRStickLeft",
+            Key::GamepadRStickRight => "GamepadRStickRight",
+            Key::GamepadRStickUp => "GamepadRStickUp",
+            Key::GamepadRStickDown => "GamepadRStickDown",
+            Key::MouseLeft => "MouseLeft",
+            Key::MouseRight => "MouseRight",
+            Key::MouseMiddle => "MouseMiddle",
+            Key::MouseX1 => "MouseX1",
+            Key::MouseX2 => "MouseX2",
+            Key::MouseWheelX => "MouseWheelX",
+            Key::MouseWheelY => "MouseWheelY",
+            Key::ModCtrl => "ModCtrl",
+            Key::ModShift => "ModShift",
+            Key::ModAlt => "ModAlt",
+            Key::ModSuper => "ModSuper",
+        }
+    }
 }
 
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Key {
+    type Err = ParseKeyError;
+
+    /// Parses a key name as returned by [`Key::name`], ignoring ASCII case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Key::VARIANTS
+            .iter()
+            .find(|key| key.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(ParseKeyError)
+    }
+}
+
+/// The error returned when parsing an unknown key name
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ParseKeyError;
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("unknown key name")
+    }
+}
+impl std::error::Error for ParseKeyError {}
+
 /// Target widget selection for keyboard focus
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum FocusedWidget {
@@ -437,3 +635,18 @@ impl Ui {
         }
     }
 }
+
+#[test]
+fn test_key_variant_names() {
+    for key in Key::VARIANTS.iter() {
+        let their_name = unsafe {
+            let ptr = sys::igGetKeyName(*key as u32);
+            std::ffi::CStr::from_ptr(ptr).to_str().unwrap()
+        };
+        assert_eq!(key.name(), their_name);
+        assert_eq!(key.name().parse::<Key>(), Ok(*key));
+    }
+    assert_eq!("keypadenter".parse::<Key>(), Ok(Key::KeypadEnter));
+    assert_eq!("".parse::<Key>(), Err(ParseKeyError));
+    assert_eq!("None".parse::<Key>(), Err(ParseKeyError));
+}
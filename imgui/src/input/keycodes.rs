@@ -0,0 +1,545 @@
+//! Translation tables from platform key codes to [`Key`].
+//!
+//! Every table maps *physical* keys, so e.g. the key labelled "Q" on an AZERTY keyboard is
+//! reported as [`Key::A`] by the scancode based tables. [`Key::from_x11_keysym`] is the exception,
+//! as X11 keysyms already take the keyboard layout into account.
+
+use super::keyboard::Key;
+
+const LETTERS: [Key; 26] = [
+    Key::A,
+    Key::B,
+    Key::C,
+    Key::D,
+    Key::E,
+    Key::F,
+    Key::G,
+    Key::H,
+    Key::I,
+    Key::J,
+    Key::K,
+    Key::L,
+    Key::M,
+    Key::N,
+    Key::O,
+    Key::P,
+    Key::Q,
+    Key::R,
+    Key::S,
+    Key::T,
+    Key::U,
+    Key::V,
+    Key::W,
+    Key::X,
+    Key::Y,
+    Key::Z,
+];
+
+const DIGITS: [Key; 10] = [
+    Key::Alpha0,
+    Key::Alpha1,
+    Key::Alpha2,
+    Key::Alpha3,
+    Key::Alpha4,
+    Key::Alpha5,
+    Key::Alpha6,
+    Key::Alpha7,
+    Key::Alpha8,
+    Key::Alpha9,
+];
+
+const KEYPAD_DIGITS: [Key; 10] = [
+    Key::Keypad0,
+    Key::Keypad1,
+    Key::Keypad2,
+    Key::Keypad3,
+    Key::Keypad4,
+    Key::Keypad5,
+    Key::Keypad6,
+    Key::Keypad7,
+    Key::Keypad8,
+    Key::Keypad9,
+];
+
+const FUNCTION_KEYS: [Key; 24] = [
+    Key::F1,
+    Key::F2,
+    Key::F3,
+    Key::F4,
+    Key::F5,
+    Key::F6,
+    Key::F7,
+    Key::F8,
+    Key::F9,
+    Key::F10,
+    Key::F11,
+    Key::F12,
+    Key::F13,
+    Key::F14,
+    Key::F15,
+    Key::F16,
+    Key::F17,
+    Key::F18,
+    Key::F19,
+    Key::F20,
+    Key::F21,
+    Key::F22,
+    Key::F23,
+    Key::F24,
+];
+
+/// Keys of the US QWERTY layout, in the row order used by evdev scancodes
+const EVDEV_LETTER_ROWS: [(u16, &[Key]); 3] = [
+    (
+        16,
+        &[
+            Key::Q,
+            Key::W,
+            Key::E,
+            Key::R,
+            Key::T,
+            Key::Y,
+            Key::U,
+            Key::I,
+            Key::O,
+            Key::P,
+        ],
+    ),
+    (
+        30,
+        &[
+            Key::A,
+            Key::S,
+            Key::D,
+            Key::F,
+            Key::G,
+            Key::H,
+            Key::J,
+            Key::K,
+            Key::L,
+        ],
+    ),
+    (
+        44,
+        &[Key::Z, Key::X, Key::C, Key::V, Key::B, Key::N, Key::M],
+    ),
+];
+
+/// Looks up `code` in a table of consecutive codes starting at `first`
+fn lookup<T>(code: T, first: T, table: &[Key]) -> Option<Key>
+where
+    T: Copy + TryInto<usize> + std::ops::Sub<Output = T> + PartialOrd,
+{
+    if code < first {
+        return None;
+    }
+    let index = (code - first).try_into().ok()?;
+    table.get(index).copied()
+}
+
+impl Key {
+    /// Translates a USB HID usage ID from the keyboard/keypad usage page (`0x07`).
+    ///
+    /// Returns `None` for usages without a matching `Key`.
+    pub fn from_hid_usage(usage: u16) -> Option<Key> {
+        let key = match usage {
+            0x04..=0x1d => return lookup(usage, 0x04, &LETTERS),
+            // 1 to 9 come before 0
+            0x1e..=0x26 => return lookup(usage, 0x1e, &DIGITS[1..]),
+            0x27 => Key::Alpha0,
+            0x28 => Key::Enter,
+            0x29 => Key::Escape,
+            0x2a => Key::Backspace,
+            0x2b => Key::Tab,
+            0x2c => Key::Space,
+            0x2d => Key::Minus,
+            0x2e => Key::Equal,
+            0x2f => Key::LeftBracket,
+            0x30 => Key::RightBracket,
+            0x31 => Key::Backslash,
+            0x33 => Key::Semicolon,
+            0x34 => Key::Apostrophe,
+            0x35 => Key::GraveAccent,
+            0x36 => Key::Comma,
+            0x37 => Key::Period,
+            0x38 => Key::Slash,
+            0x39 => Key::CapsLock,
+            0x3a..=0x45 => return lookup(usage, 0x3a, &FUNCTION_KEYS[..12]),
+            0x46 => Key::PrintScreen,
+            0x47 => Key::ScrollLock,
+            0x48 => Key::Pause,
+            0x49 => Key::Insert,
+            0x4a => Key::Home,
+            0x4b => Key::PageUp,
+            0x4c => Key::Delete,
+            0x4d => Key::End,
+            0x4e => Key::PageDown,
+            0x4f => Key::RightArrow,
+            0x50 => Key::LeftArrow,
+            0x51 => Key::DownArrow,
+            0x52 => Key::UpArrow,
+            0x53 => Key::NumLock,
+            0x54 => Key::KeypadDivide,
+            0x55 => Key::KeypadMultiply,
+            0x56 => Key::KeypadSubtract,
+            0x57 => Key::KeypadAdd,
+            0x58 => Key::KeypadEnter,
+            // keypad 1 to 9 come before keypad 0
+            0x59..=0x61 => return lookup(usage, 0x59, &KEYPAD_DIGITS[1..]),
+            0x62 => Key::Keypad0,
+            0x63 => Key::KeypadDecimal,
+            0x65 => Key::Menu,
+            0x67 => Key::KeypadEqual,
+            0x68..=0x73 => return lookup(usage, 0x68, &FUNCTION_KEYS[12..]),
+            0xe0 => Key::LeftCtrl,
+            0xe1 => Key::LeftShift,
+            0xe2 => Key::LeftAlt,
+            0xe3 => Key::LeftSuper,
+            0xe4 => Key::RightCtrl,
+            0xe5 => Key::RightShift,
+            0xe6 => Key::RightAlt,
+            0xe7 => Key::RightSuper,
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// Translates a Linux evdev key code (the `KEY_*` constants of `linux/input-event-codes.h`).
+    ///
+    /// X11 keycodes are evdev key codes offset by 8.
+    ///
+    /// Returns `None` for codes without a matching `Key`.
+    pub fn from_evdev_scancode(code: u16) -> Option<Key> {
+        let key = match code {
+            1 => Key::Escape,
+            // 1 to 9 come before 0
+            2..=10 => return lookup(code, 2, &DIGITS[1..]),
+            11 => Key::Alpha0,
+            12 => Key::Minus,
+            13 => Key::Equal,
+            14 => Key::Backspace,
+            15 => Key::Tab,
+            16..=25 | 30..=38 | 44..=50 => {
+                return EVDEV_LETTER_ROWS
+                    .iter()
+                    .find_map(|&(first, row)| lookup(code, first, row))
+            }
+            26 => Key::LeftBracket,
+            27 => Key::RightBracket,
+            28 => Key::Enter,
+            29 => Key::LeftCtrl,
+            39 => Key::Semicolon,
+            40 => Key::Apostrophe,
+            41 => Key::GraveAccent,
+            42 => Key::LeftShift,
+            43 => Key::Backslash,
+            51 => Key::Comma,
+            52 => Key::Period,
+            53 => Key::Slash,
+            54 => Key::RightShift,
+            55 => Key::KeypadMultiply,
+            56 => Key::LeftAlt,
+            57 => Key::Space,
+            58 => Key::CapsLock,
+            59..=68 => return lookup(code, 59, &FUNCTION_KEYS[..10]),
+            69 => Key::NumLock,
+            70 => Key::ScrollLock,
+            71 => Key::Keypad7,
+            72 => Key::Keypad8,
+            73 => Key::Keypad9,
+            74 => Key::KeypadSubtract,
+            75 => Key::Keypad4,
+            76 => Key::Keypad5,
+            77 => Key::Keypad6,
+            78 => Key::KeypadAdd,
+            79 => Key::Keypad1,
+            80 => Key::Keypad2,
+            81 => Key::Keypad3,
+            82 => Key::Keypad0,
+            83 => Key::KeypadDecimal,
+            87 => Key::F11,
+            88 => Key::F12,
+            96 => Key::KeypadEnter,
+            97 => Key::RightCtrl,
+            98 => Key::KeypadDivide,
+            99 => Key::PrintScreen,
+            100 => Key::RightAlt,
+            102 => Key::Home,
+            103 => Key::UpArrow,
+            104 => Key::PageUp,
+            105 => Key::LeftArrow,
+            106 => Key::RightArrow,
+            107 => Key::End,
+            108 => Key::DownArrow,
+            109 => Key::PageDown,
+            110 => Key::Insert,
+            111 => Key::Delete,
+            117 => Key::KeypadEqual,
+            119 => Key::Pause,
+            125 => Key::LeftSuper,
+            126 => Key::RightSuper,
+            127 => Key::Menu,
+            158 => Key::AppBack,
+            159 => Key::AppForward,
+            183..=194 => return lookup(code, 183, &FUNCTION_KEYS[12..]),
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// Translates an X11 keysym (the `XK_*` constants of `X11/keysymdef.h`).
+    ///
+    /// Both the lower and upper case keysyms of letters are accepted, but shifted symbols (e.g.
+    /// `XK_exclam`) are not, so pass the keysym of the unshifted key (keysym index 0).
+    ///
+    /// Returns `None` for keysyms without a matching `Key`.
+    pub fn from_x11_keysym(keysym: u32) -> Option<Key> {
+        let key = match keysym {
+            0x0020 => Key::Space,
+            0x0027 => Key::Apostrophe,
+            0x002c => Key::Comma,
+            0x002d => Key::Minus,
+            0x002e => Key::Period,
+            0x002f => Key::Slash,
+            0x0030..=0x0039 => return lookup(keysym, 0x0030, &DIGITS),
+            0x003b => Key::Semicolon,
+            0x003d => Key::Equal,
+            0x0041..=0x005a => return lookup(keysym, 0x0041, &LETTERS),
+            0x005b => Key::LeftBracket,
+            0x005c => Key::Backslash,
+            0x005d => Key::RightBracket,
+            0x0060 => Key::GraveAccent,
+            0x0061..=0x007a => return lookup(keysym, 0x0061, &LETTERS),
+            0xff08 => Key::Backspace,
+            0xff09 => Key::Tab,
+            0xff0d => Key::Enter,
+            0xff13 => Key::Pause,
+            0xff14 => Key::ScrollLock,
+            0xff1b => Key::Escape,
+            0xff50 => Key::Home,
+            0xff51 => Key::LeftArrow,
+            0xff52 => Key::UpArrow,
+            0xff53 => Key::RightArrow,
+            0xff54 => Key::DownArrow,
+            0xff55 => Key::PageUp,
+            0xff56 => Key::PageDown,
+            0xff57 => Key::End,
+            0xff61 => Key::PrintScreen,
+            0xff63 => Key::Insert,
+            0xff67 => Key::Menu,
+            0xff7f => Key::NumLock,
+            0xff8d => Key::KeypadEnter,
+            // keypad keys with num lock off
+            0xff95 => Key::Keypad7,
+            0xff96 => Key::Keypad4,
+            0xff97 => Key::Keypad8,
+            0xff98 => Key::Keypad6,
+            0xff99 => Key::Keypad2,
+            0xff9a => Key::Keypad9,
+            0xff9b => Key::Keypad3,
+            0xff9c => Key::Keypad1,
+            0xff9d => Key::Keypad5,
+            0xff9e => Key::Keypad0,
+            0xff9f => Key::KeypadDecimal,
+            0xffaa => Key::KeypadMultiply,
+            0xffab => Key::KeypadAdd,
+            0xffad => Key::KeypadSubtract,
+            0xffae => Key::KeypadDecimal,
+            0xffaf => Key::KeypadDivide,
+            0xffb0..=0xffb9 => return lookup(keysym, 0xffb0, &KEYPAD_DIGITS),
+            0xffbd => Key::KeypadEqual,
+            0xffbe..=0xffd5 => return lookup(keysym, 0xffbe, &FUNCTION_KEYS),
+            0xffe1 => Key::LeftShift,
+            0xffe2 => Key::RightShift,
+            0xffe3 => Key::LeftCtrl,
+            0xffe4 => Key::RightCtrl,
+            0xffe5 => Key::CapsLock,
+            0xffe9 => Key::LeftAlt,
+            0xffea => Key::RightAlt,
+            0xffeb => Key::LeftSuper,
+            0xffec => Key::RightSuper,
+            0xffff => Key::Delete,
+            // XF86XK_Back and XF86XK_Forward
+            0x1008ff26 => Key::AppBack,
+            0x1008ff27 => Key::AppForward,
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// Translates a W3C `KeyboardEvent.code` value, e.g. `"KeyA"` or `"ArrowLeft"`.
+    ///
+    /// Returns `None` for codes without a matching `Key`.
+    pub fn from_w3c_code(code: &str) -> Option<Key> {
+        if let Some(letter) = code.strip_prefix("Key") {
+            return match letter.as_bytes() {
+                &[c @ b'A'..=b'Z'] => lookup(c, b'A', &LETTERS),
+                _ => None,
+            };
+        }
+        if let Some(digit) = code.strip_prefix("Digit") {
+            return match digit.as_bytes() {
+                &[c @ b'0'..=b'9'] => lookup(c, b'0', &DIGITS),
+                _ => None,
+            };
+        }
+        if let Some(digit) = code.strip_prefix("Numpad") {
+            if let &[c @ b'0'..=b'9'] = digit.as_bytes() {
+                return lookup(c, b'0', &KEYPAD_DIGITS);
+            }
+        }
+        if let Some(number) = code.strip_prefix('F') {
+            // reject "F01" and the like
+            if !number.starts_with('0') {
+                if let Ok(number) = number.parse::<usize>() {
+                    return FUNCTION_KEYS.get(number.wrapping_sub(1)).copied();
+                }
+            }
+        }
+        let key = match code {
+            "Escape" => Key::Escape,
+            "Minus" => Key::Minus,
+            "Equal" => Key::Equal,
+            "Backspace" => Key::Backspace,
+            "Tab" => Key::Tab,
+            "BracketLeft" => Key::LeftBracket,
+            "BracketRight" => Key::RightBracket,
+            "Enter" => Key::Enter,
+            "ControlLeft" => Key::LeftCtrl,
+            "Semicolon" => Key::Semicolon,
+            "Quote" => Key::Apostrophe,
+            "Backquote" => Key::GraveAccent,
+            "ShiftLeft" => Key::LeftShift,
+            "Backslash" => Key::Backslash,
+            "Comma" => Key::Comma,
+            "Period" => Key::Period,
+            "Slash" => Key::Slash,
+            "ShiftRight" => Key::RightShift,
+            "NumpadMultiply" => Key::KeypadMultiply,
+            "AltLeft" => Key::LeftAlt,
+            "Space" => Key::Space,
+            "CapsLock" => Key::CapsLock,
+            "Pause" => Key::Pause,
+            "ScrollLock" => Key::ScrollLock,
+            "NumLock" => Key::NumLock,
+            "NumpadSubtract" => Key::KeypadSubtract,
+            "NumpadAdd" => Key::KeypadAdd,
+            "NumpadDecimal" => Key::KeypadDecimal,
+            "NumpadEqual" => Key::KeypadEqual,
+            "NumpadEnter" => Key::KeypadEnter,
+            "ControlRight" => Key::RightCtrl,
+            "NumpadDivide" => Key::KeypadDivide,
+            "PrintScreen" => Key::PrintScreen,
+            "AltRight" => Key::RightAlt,
+            "Home" => Key::Home,
+            "ArrowUp" => Key::UpArrow,
+            "PageUp" => Key::PageUp,
+            "ArrowLeft" => Key::LeftArrow,
+            "ArrowRight" => Key::RightArrow,
+            "End" => Key::End,
+            "ArrowDown" => Key::DownArrow,
+            "PageDown" => Key::PageDown,
+            "Insert" => Key::Insert,
+            "Delete" => Key::Delete,
+            // older browsers report the Windows/Command keys as "OSLeft"/"OSRight"
+            "MetaLeft" | "OSLeft" => Key::LeftSuper,
+            "MetaRight" | "OSRight" => Key::RightSuper,
+            "ContextMenu" => Key::Menu,
+            "BrowserBack" => Key::AppBack,
+            "BrowserForward" => Key::AppForward,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+/// Collects every key a table produces, checking each code only maps to one key
+#[cfg(test)]
+fn table_keys(codes: impl Iterator<Item = Option<Key>>) -> Vec<Key> {
+    let mut keys: Vec<Key> = codes.flatten().collect();
+    let count = keys.len();
+    keys.sort_by_key(|&key| key as u32);
+    keys.dedup();
+    assert_eq!(keys.len(), count, "two codes map to the same key");
+    keys
+}
+
+#[test]
+fn test_hid_usage() {
+    assert_eq!(Key::from_hid_usage(0x04), Some(Key::A));
+    assert_eq!(Key::from_hid_usage(0x1d), Some(Key::Z));
+    assert_eq!(Key::from_hid_usage(0x1e), Some(Key::Alpha1));
+    assert_eq!(Key::from_hid_usage(0x27), Some(Key::Alpha0));
+    assert_eq!(Key::from_hid_usage(0x45), Some(Key::F12));
+    assert_eq!(Key::from_hid_usage(0x68), Some(Key::F13));
+    assert_eq!(Key::from_hid_usage(0x73), Some(Key::F24));
+    assert_eq!(Key::from_hid_usage(0x59), Some(Key::Keypad1));
+    assert_eq!(Key::from_hid_usage(0x62), Some(Key::Keypad0));
+    assert_eq!(Key::from_hid_usage(0xe7), Some(Key::RightSuper));
+    assert_eq!(Key::from_hid_usage(0x00), None);
+    assert_eq!(Key::from_hid_usage(0x74), None);
+    table_keys((0..=u16::MAX).map(Key::from_hid_usage));
+}
+
+#[test]
+fn test_evdev_scancode() {
+    assert_eq!(Key::from_evdev_scancode(1), Some(Key::Escape));
+    assert_eq!(Key::from_evdev_scancode(2), Some(Key::Alpha1));
+    assert_eq!(Key::from_evdev_scancode(11), Some(Key::Alpha0));
+    assert_eq!(Key::from_evdev_scancode(16), Some(Key::Q));
+    assert_eq!(Key::from_evdev_scancode(25), Some(Key::P));
+    assert_eq!(Key::from_evdev_scancode(30), Some(Key::A));
+    assert_eq!(Key::from_evdev_scancode(38), Some(Key::L));
+    assert_eq!(Key::from_evdev_scancode(44), Some(Key::Z));
+    assert_eq!(Key::from_evdev_scancode(50), Some(Key::M));
+    assert_eq!(Key::from_evdev_scancode(68), Some(Key::F10));
+    assert_eq!(Key::from_evdev_scancode(183), Some(Key::F13));
+    assert_eq!(Key::from_evdev_scancode(194), Some(Key::F24));
+    assert_eq!(Key::from_evdev_scancode(0), None);
+    assert_eq!(Key::from_evdev_scancode(0x110), None);
+    let keys = table_keys((0..=u16::MAX).map(Key::from_evdev_scancode));
+    for key in LETTERS {
+        assert!(keys.contains(&key), "{:?} has no scancode", key);
+    }
+}
+
+#[test]
+fn test_x11_keysym() {
+    assert_eq!(Key::from_x11_keysym(0x0061), Some(Key::A));
+    assert_eq!(Key::from_x11_keysym(0x0041), Some(Key::A));
+    assert_eq!(Key::from_x11_keysym(0x0030), Some(Key::Alpha0));
+    assert_eq!(Key::from_x11_keysym(0xffbe), Some(Key::F1));
+    assert_eq!(Key::from_x11_keysym(0xffd5), Some(Key::F24));
+    assert_eq!(Key::from_x11_keysym(0xffb5), Some(Key::Keypad5));
+    assert_eq!(Key::from_x11_keysym(0xffff), Some(Key::Delete));
+    assert_eq!(Key::from_x11_keysym(0x1008ff26), Some(Key::AppBack));
+    // XK_exclam
+    assert_eq!(Key::from_x11_keysym(0x0021), None);
+}
+
+#[test]
+fn test_w3c_code() {
+    assert_eq!(Key::from_w3c_code("KeyA"), Some(Key::A));
+    assert_eq!(Key::from_w3c_code("KeyZ"), Some(Key::Z));
+    assert_eq!(Key::from_w3c_code("Digit0"), Some(Key::Alpha0));
+    assert_eq!(Key::from_w3c_code("Numpad7"), Some(Key::Keypad7));
+    assert_eq!(Key::from_w3c_code("NumpadEnter"), Some(Key::KeypadEnter));
+    assert_eq!(Key::from_w3c_code("F1"), Some(Key::F1));
+    assert_eq!(Key::from_w3c_code("F24"), Some(Key::F24));
+    assert_eq!(Key::from_w3c_code("ArrowLeft"), Some(Key::LeftArrow));
+    assert_eq!(Key::from_w3c_code("OSLeft"), Some(Key::LeftSuper));
+    for code in [
+        "",
+        "Key",
+        "Keya",
+        "KeyAA",
+        "Digit10",
+        "F0",
+        "F01",
+        "F25",
+        "Fn",
+        "Unidentified",
+    ] {
+        assert_eq!(Key::from_w3c_code(code), None, "{:?}", code);
+    }
+}
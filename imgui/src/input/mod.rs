@@ -1,2 +1,7 @@
+pub mod gamepad;
 pub mod keyboard;
+mod keycodes;
 pub mod mouse;
+pub mod recording;
+pub mod routing;
+pub mod touch;
@@ -0,0 +1,462 @@
+//! Recording input fed to [`Io`] and replaying it into another context.
+//!
+//! An [`InputRecorder`] captures every [`InputEvent`] and the per-frame [`Io`] state that Dear
+//! ImGui reads (delta time, display size and framebuffer scale). The resulting
+//! [`InputRecording`] can be saved with [`InputRecording::to_bytes`] and replayed with an
+//! [`InputPlayer`]. When the application code is deterministic, replaying a recording into a
+//! fresh [`Context`](crate::Context) produces the same draw data as the recorded session.
+
+use std::fmt;
+
+use crate::binary::{BinaryReader, BinaryWriter};
+use crate::input::keyboard::Key;
+use crate::input::mouse::{MouseButton, MouseSource};
+use crate::io::{InputEvent, Io};
+
+const RECORDING_MAGIC: &[u8; 8] = b"IMGUIREC";
+const RECORDING_FORMAT_VERSION: u32 = 1;
+
+/// The input of a single recorded frame
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecordedFrame {
+    /// Time elapsed since the previous frame, in seconds
+    pub delta_time: f32,
+    /// Main display size, in pixels
+    pub display_size: [f32; 2],
+    /// Main display framebuffer scale
+    pub display_framebuffer_scale: [f32; 2],
+    /// Input events queued before the frame started, in order
+    pub events: Vec<InputEvent>,
+}
+
+/// A sequence of recorded frames
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputRecording {
+    /// The recorded frames, in order
+    pub frames: Vec<RecordedFrame>,
+}
+
+/// An error returned when reading an input recording
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InputRecordingError {
+    /// The data is not an input recording, or it is truncated or corrupted
+    InvalidData,
+    /// The recording was written in a format this version of imgui-rs can't read
+    UnsupportedVersion,
+}
+
+impl fmt::Display for InputRecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            InputRecordingError::InvalidData => "Invalid input recording data",
+            InputRecordingError::UnsupportedVersion => "Unsupported input recording version",
+        })
+    }
+}
+
+impl std::error::Error for InputRecordingError {}
+
+/// Records the input fed to [`Io`].
+///
+/// Queue events through [`InputRecorder::push_event`] instead of [`Io::push_event`] or the
+/// `Io::add_*_event` functions, and call [`InputRecorder::end_frame_input`] right before
+/// [`Context::new_frame`](crate::Context::new_frame).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// let mut recorder = InputRecorder::new();
+/// loop {
+///     // platform event handling
+///     recorder.push_event(ctx.io_mut(), InputEvent::MousePos([10.0, 20.0]));
+///
+///     recorder.end_frame_input(ctx.io());
+///     let ui = ctx.new_frame();
+///     // ...
+/// #   break;
+/// }
+/// std::fs::write("input.rec", recorder.finish().to_bytes()).unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct InputRecorder {
+    recording: InputRecording,
+    events: Vec<InputEvent>,
+}
+
+impl InputRecorder {
+    /// Creates a recorder with an empty recording
+    pub fn new() -> InputRecorder {
+        InputRecorder::default()
+    }
+    /// Records `event` and queues it in `io`
+    pub fn push_event(&mut self, io: &mut Io, event: InputEvent) {
+        self.events.push(event);
+        io.push_event(event);
+    }
+    /// Ends the input of the current frame, recording the frame state of `io`.
+    ///
+    /// Call this after setting `io.delta_time` and `io.display_size`, right before starting the
+    /// frame.
+    pub fn end_frame_input(&mut self, io: &Io) {
+        self.recording.frames.push(RecordedFrame {
+            delta_time: io.delta_time,
+            display_size: io.display_size,
+            display_framebuffer_scale: io.display_framebuffer_scale,
+            events: std::mem::take(&mut self.events),
+        });
+    }
+    /// Returns the frames recorded so far
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+    /// Stops recording and returns the recording.
+    ///
+    /// Events pushed since the last [`InputRecorder::end_frame_input`] are dropped.
+    pub fn finish(self) -> InputRecording {
+        self.recording
+    }
+}
+
+/// Replays an [`InputRecording`] frame by frame
+#[derive(Clone, Debug)]
+pub struct InputPlayer {
+    recording: InputRecording,
+    next_frame: usize,
+}
+
+impl InputPlayer {
+    /// Creates a player starting at the first frame of `recording`
+    pub fn new(recording: InputRecording) -> InputPlayer {
+        InputPlayer {
+            recording,
+            next_frame: 0,
+        }
+    }
+    /// Feeds the input of the next recorded frame into `io`.
+    ///
+    /// Call this right before [`Context::new_frame`](crate::Context::new_frame). Returns false,
+    /// without touching `io`, once every frame has been replayed.
+    pub fn play_frame(&mut self, io: &mut Io) -> bool {
+        let frame = match self.recording.frames.get(self.next_frame) {
+            Some(frame) => frame,
+            None => return false,
+        };
+        io.delta_time = frame.delta_time;
+        io.display_size = frame.display_size;
+        io.display_framebuffer_scale = frame.display_framebuffer_scale;
+        for &event in &frame.events {
+            io.push_event(event);
+        }
+        self.next_frame += 1;
+        true
+    }
+    /// Returns true if every frame has been replayed
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.recording.frames.len()
+    }
+    /// Returns the index of the next frame to be replayed
+    pub fn position(&self) -> usize {
+        self.next_frame
+    }
+    /// Restarts the replay from the first frame
+    pub fn rewind(&mut self) {
+        self.next_frame = 0;
+    }
+    /// Returns the recording being replayed
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+}
+
+// Event tags of the serialized format. These must never change meaning.
+const TAG_MOUSE_POS: u8 = 0;
+const TAG_MOUSE_BUTTON: u8 = 1;
+const TAG_MOUSE_WHEEL: u8 = 2;
+const TAG_MOUSE_SOURCE: u8 = 3;
+#[cfg_attr(not(feature = "docking"), allow(dead_code))]
+const TAG_MOUSE_VIEWPORT: u8 = 4;
+const TAG_KEY: u8 = 5;
+const TAG_KEY_ANALOG: u8 = 6;
+const TAG_TEXT: u8 = 7;
+const TAG_FOCUS: u8 = 8;
+
+impl InputRecording {
+    /// Serializes the recording into a compact, platform independent binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = BinaryWriter(Vec::new());
+        w.bytes(RECORDING_MAGIC);
+        w.u32(RECORDING_FORMAT_VERSION);
+        w.u32(self.frames.len() as u32);
+        for frame in &self.frames {
+            w.f32(frame.delta_time);
+            w.f32_2(frame.display_size);
+            w.f32_2(frame.display_framebuffer_scale);
+            w.u32(frame.events.len() as u32);
+            for event in &frame.events {
+                write_event(&mut w, event);
+            }
+        }
+        w.0
+    }
+    /// Deserializes a recording written by [`InputRecording::to_bytes`]
+    pub fn from_bytes(data: &[u8]) -> Result<InputRecording, InputRecordingError> {
+        let mut r = BinaryReader::new(data, InputRecordingError::InvalidData);
+        if r.bytes(RECORDING_MAGIC.len())? != RECORDING_MAGIC {
+            return Err(InputRecordingError::InvalidData);
+        }
+        if r.u32()? != RECORDING_FORMAT_VERSION {
+            return Err(InputRecordingError::UnsupportedVersion);
+        }
+        let frame_count = r.u32()?;
+        let mut frames = Vec::new();
+        for _ in 0..frame_count {
+            let delta_time = r.f32()?;
+            let display_size = r.f32_2()?;
+            let display_framebuffer_scale = r.f32_2()?;
+            let event_count = r.u32()?;
+            let mut events = Vec::new();
+            for _ in 0..event_count {
+                events.push(read_event(&mut r)?);
+            }
+            frames.push(RecordedFrame {
+                delta_time,
+                display_size,
+                display_framebuffer_scale,
+                events,
+            });
+        }
+        if !r.is_empty() {
+            return Err(InputRecordingError::InvalidData);
+        }
+        Ok(InputRecording { frames })
+    }
+}
+
+/// Writes an event as its tag followed by its fields
+fn write_event(w: &mut BinaryWriter, event: &InputEvent) {
+    match *event {
+        InputEvent::MousePos(pos) => {
+            w.u8(TAG_MOUSE_POS);
+            w.f32_2(pos);
+        }
+        InputEvent::MouseButton { button, down } => {
+            w.u8(TAG_MOUSE_BUTTON);
+            w.u8(button as u8);
+            w.u8(down as u8);
+        }
+        InputEvent::MouseWheel(wheel) => {
+            w.u8(TAG_MOUSE_WHEEL);
+            w.f32_2(wheel);
+        }
+        InputEvent::MouseSource(source) => {
+            w.u8(TAG_MOUSE_SOURCE);
+            w.u32(source as u32);
+        }
+        #[cfg(feature = "docking")]
+        InputEvent::MouseViewport(viewport_id) => {
+            w.u8(TAG_MOUSE_VIEWPORT);
+            w.u32(viewport_id.0);
+        }
+        InputEvent::Key { key, down } => {
+            w.u8(TAG_KEY);
+            w.u32(key as u32);
+            w.u8(down as u8);
+        }
+        InputEvent::KeyAnalog { key, down, value } => {
+            w.u8(TAG_KEY_ANALOG);
+            w.u32(key as u32);
+            w.u8(down as u8);
+            w.f32(value);
+        }
+        InputEvent::Text(character) => {
+            w.u8(TAG_TEXT);
+            w.u32(character as u32);
+        }
+        InputEvent::Focus(focused) => {
+            w.u8(TAG_FOCUS);
+            w.u8(focused as u8);
+        }
+    }
+}
+
+fn read_key(r: &mut BinaryReader<'_, InputRecordingError>) -> Result<Key, InputRecordingError> {
+    let value = r.u32()?;
+    Key::VARIANTS
+        .iter()
+        .copied()
+        .find(|&key| key as u32 == value)
+        .ok_or(InputRecordingError::InvalidData)
+}
+
+/// Reads an event written by [`write_event`]
+fn read_event(
+    r: &mut BinaryReader<'_, InputRecordingError>,
+) -> Result<InputEvent, InputRecordingError> {
+    let event = match r.u8()? {
+        TAG_MOUSE_POS => InputEvent::MousePos(r.f32_2()?),
+        TAG_MOUSE_BUTTON => InputEvent::MouseButton {
+            button: *MouseButton::VARIANTS
+                .get(r.u8()? as usize)
+                .ok_or(InputRecordingError::InvalidData)?,
+            down: r.bool()?,
+        },
+        TAG_MOUSE_WHEEL => InputEvent::MouseWheel(r.f32_2()?),
+        TAG_MOUSE_SOURCE => InputEvent::MouseSource(
+            *MouseSource::VARIANTS
+                .get(r.u32()? as usize)
+                .ok_or(InputRecordingError::InvalidData)?,
+        ),
+        #[cfg(feature = "docking")]
+        TAG_MOUSE_VIEWPORT => InputEvent::MouseViewport(crate::Id(r.u32()?)),
+        TAG_KEY => InputEvent::Key {
+            key: read_key(r)?,
+            down: r.bool()?,
+        },
+        TAG_KEY_ANALOG => InputEvent::KeyAnalog {
+            key: read_key(r)?,
+            down: r.bool()?,
+            value: r.f32()?,
+        },
+        TAG_TEXT => {
+            InputEvent::Text(char::from_u32(r.u32()?).ok_or(InputRecordingError::InvalidData)?)
+        }
+        TAG_FOCUS => InputEvent::Focus(r.bool()?),
+        // includes viewport events, which can't be replayed without the docking feature
+        _ => return Err(InputRecordingError::InvalidData),
+    };
+    Ok(event)
+}
+
+#[cfg(test)]
+fn test_events() -> Vec<Vec<InputEvent>> {
+    vec![
+        vec![InputEvent::Focus(true), InputEvent::MousePos([40.0, 40.0])],
+        vec![InputEvent::MouseButton {
+            button: MouseButton::Left,
+            down: true,
+        }],
+        vec![
+            InputEvent::MousePos([140.0, 90.0]),
+            InputEvent::MouseWheel([0.0, -1.0]),
+        ],
+        vec![
+            InputEvent::MouseButton {
+                button: MouseButton::Left,
+                down: false,
+            },
+            InputEvent::MouseSource(MouseSource::Pen),
+        ],
+        vec![
+            InputEvent::Key {
+                key: Key::Tab,
+                down: true,
+            },
+            InputEvent::KeyAnalog {
+                key: Key::GamepadL2,
+                down: true,
+                value: 0.5,
+            },
+            InputEvent::Text('ß'),
+        ],
+        vec![],
+    ]
+}
+
+/// Runs a small deterministic UI for every frame of input, returning the draw data contents
+#[cfg(test)]
+fn run_frames(
+    ctx: &mut crate::Context,
+    mut feed_frame: impl FnMut(&mut crate::Context, usize) -> bool,
+) -> Vec<u32> {
+    let mut output = Vec::new();
+    let mut counter = 0;
+    let mut text = String::new();
+    let mut frame = 0;
+    while feed_frame(ctx, frame) {
+        let ui = ctx.new_frame();
+        ui.window("Replay")
+            .size([200.0, 100.0], crate::Condition::Always)
+            .build(|| {
+                if ui.button("Count") {
+                    counter += 1;
+                }
+                ui.input_text("Text", &mut text).build();
+                ui.text(format!("{} {:?}", counter, ui.io().mouse_pos));
+            });
+        let draw_data = ctx.render();
+        for draw_list in draw_data.draw_lists() {
+            for vertex in draw_list.vtx_buffer() {
+                output.extend([vertex.pos[0].to_bits(), vertex.pos[1].to_bits()]);
+                output.extend([vertex.uv[0].to_bits(), vertex.uv[1].to_bits()]);
+                output.push(u32::from_le_bytes(vertex.col));
+            }
+            output.extend(draw_list.idx_buffer().iter().map(|&idx| idx as u32));
+        }
+        frame += 1;
+    }
+    output
+}
+
+#[test]
+fn test_record_and_replay() {
+    let events = test_events();
+    let (recording, recorded_output) = {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut recorder = InputRecorder::new();
+        let output = run_frames(&mut ctx, |ctx, frame| {
+            let Some(frame_events) = events.get(frame) else {
+                return false;
+            };
+            for &event in frame_events {
+                recorder.push_event(ctx.io_mut(), event);
+            }
+            ctx.io_mut().delta_time = 1.0 / (50.0 + frame as f32);
+            recorder.end_frame_input(ctx.io());
+            true
+        });
+        (recorder.finish(), output)
+    };
+    assert_eq!(recording.frames.len(), events.len());
+    assert_eq!(recording.frames[1].delta_time, 1.0 / 51.0);
+
+    let recording = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut player = InputPlayer::new(recording);
+    let replayed_output = run_frames(&mut ctx, |ctx, _| player.play_frame(ctx.io_mut()));
+    assert!(player.is_finished());
+    assert!(recorded_output == replayed_output);
+}
+
+#[test]
+fn test_recording_serialization() {
+    let recording = InputRecording {
+        frames: test_events()
+            .into_iter()
+            .map(|events| RecordedFrame {
+                delta_time: 0.25,
+                display_size: [640.0, 480.0],
+                display_framebuffer_scale: [2.0, 2.0],
+                events,
+            })
+            .collect(),
+    };
+    let bytes = recording.to_bytes();
+    assert_eq!(InputRecording::from_bytes(&bytes), Ok(recording));
+
+    assert_eq!(
+        InputRecording::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(InputRecordingError::InvalidData)
+    );
+    assert_eq!(
+        InputRecording::from_bytes(b"not a recording"),
+        Err(InputRecordingError::InvalidData)
+    );
+    let mut future = bytes.clone();
+    future[8] = 99;
+    assert_eq!(
+        InputRecording::from_bytes(&future),
+        Err(InputRecordingError::UnsupportedVersion)
+    );
+}
@@ -0,0 +1,523 @@
+//! Key ownership and shortcut routing.
+//!
+//! By default every piece of code polling the keyboard sees every key. Key owners let one part
+//! of the UI claim keys, e.g. a focused 3D viewport claiming WASD, so that owner-aware queries
+//! elsewhere ignore them. Shortcut routing picks a single receiver for a key chord among
+//! everything that asks for it, based on focus.
+//!
+//! The owner-aware queries only filter input for code that passes an owner. Plain
+//! [`Ui::is_key_down`] and friends ignore ownership, unless a key was locked with
+//! [`InputFlags::LOCK_THIS_FRAME`] or [`InputFlags::LOCK_UNTIL_RELEASE`].
+
+use bitflags::bitflags;
+
+use crate::input::keyboard::Key;
+use crate::sys;
+use crate::{Id, Ui};
+
+bitflags! {
+    /// Flags for key ownership, owner-aware key queries and shortcuts
+    #[repr(transparent)]
+    pub struct InputFlags: u32 {
+        /// Enable repeat. Return true on successive repeats
+        const REPEAT = sys::ImGuiInputFlags_Repeat;
+        /// Repeat rate: regular (default)
+        const REPEAT_RATE_DEFAULT = sys::ImGuiInputFlags_RepeatRateDefault;
+        /// Repeat rate: fast
+        const REPEAT_RATE_NAV_MOVE = sys::ImGuiInputFlags_RepeatRateNavMove;
+        /// Repeat rate: faster
+        const REPEAT_RATE_NAV_TWEAK = sys::ImGuiInputFlags_RepeatRateNavTweak;
+        /// Stop repeating when released (default for everything except shortcuts)
+        const REPEAT_UNTIL_RELEASE = sys::ImGuiInputFlags_RepeatUntilRelease;
+        /// Stop repeating when released or when the modifiers change (default for shortcuts)
+        const REPEAT_UNTIL_KEY_MODS_CHANGE = sys::ImGuiInputFlags_RepeatUntilKeyModsChange;
+        /// Stop repeating when released or when the modifiers leave the none state
+        const REPEAT_UNTIL_KEY_MODS_CHANGE_FROM_NONE =
+            sys::ImGuiInputFlags_RepeatUntilKeyModsChangeFromNone;
+        /// Stop repeating when released or when any other key is pressed
+        const REPEAT_UNTIL_OTHER_KEY_PRESS = sys::ImGuiInputFlags_RepeatUntilOtherKeyPress;
+        /// Route to the active item only
+        const ROUTE_ACTIVE = sys::ImGuiInputFlags_RouteActive;
+        /// Route to windows in the focus stack (default for shortcuts). The deepest focused
+        /// window takes the input, and the active item takes it over that window
+        const ROUTE_FOCUSED = sys::ImGuiInputFlags_RouteFocused;
+        /// Global route, with lower priority than focused routes
+        const ROUTE_GLOBAL = sys::ImGuiInputFlags_RouteGlobal;
+        /// Don't register a route, poll the keys directly
+        const ROUTE_ALWAYS = sys::ImGuiInputFlags_RouteAlways;
+        /// Option for [`ROUTE_GLOBAL`](Self::ROUTE_GLOBAL): higher priority than focused routes
+        const ROUTE_OVER_FOCUSED = sys::ImGuiInputFlags_RouteOverFocused;
+        /// Option for [`ROUTE_GLOBAL`](Self::ROUTE_GLOBAL): higher priority than the active item
+        const ROUTE_OVER_ACTIVE = sys::ImGuiInputFlags_RouteOverActive;
+        /// Option for [`ROUTE_GLOBAL`](Self::ROUTE_GLOBAL): only route while a Dear ImGui
+        /// window has focus
+        const ROUTE_UNLESS_BG_FOCUSED = sys::ImGuiInputFlags_RouteUnlessBgFocused;
+        /// Option for [`ROUTE_FOCUSED`](Self::ROUTE_FOCUSED): route from the root window
+        /// rather than the current window
+        const ROUTE_FROM_ROOT_WINDOW = sys::ImGuiInputFlags_RouteFromRootWindow;
+        /// [`Ui::set_next_item_shortcut`] only: show the shortcut in the item's tooltip
+        const TOOLTIP = sys::ImGuiInputFlags_Tooltip;
+        /// Reading the key requires its explicit owner until the end of the frame, even for code
+        /// that isn't owner-aware
+        const LOCK_THIS_FRAME = sys::ImGuiInputFlags_LockThisFrame;
+        /// Reading the key requires its explicit owner until it's released, even for code that
+        /// isn't owner-aware
+        const LOCK_UNTIL_RELEASE = sys::ImGuiInputFlags_LockUntilRelease;
+        /// [`Ui::set_item_key_owner_with_flags`] only: claim the key when the item is hovered
+        const COND_HOVERED = sys::ImGuiInputFlags_CondHovered;
+        /// [`Ui::set_item_key_owner_with_flags`] only: claim the key when the item is active
+        const COND_ACTIVE = sys::ImGuiInputFlags_CondActive;
+    }
+}
+
+impl InputFlags {
+    const REPEAT_MASK: InputFlags = InputFlags::from_bits_truncate(
+        InputFlags::REPEAT.bits
+            | InputFlags::REPEAT_RATE_DEFAULT.bits
+            | InputFlags::REPEAT_RATE_NAV_MOVE.bits
+            | InputFlags::REPEAT_RATE_NAV_TWEAK.bits
+            | InputFlags::REPEAT_UNTIL_RELEASE.bits
+            | InputFlags::REPEAT_UNTIL_KEY_MODS_CHANGE.bits
+            | InputFlags::REPEAT_UNTIL_KEY_MODS_CHANGE_FROM_NONE.bits
+            | InputFlags::REPEAT_UNTIL_OTHER_KEY_PRESS.bits,
+    );
+    const ROUTE_TYPE_MASK: InputFlags = InputFlags::from_bits_truncate(
+        InputFlags::ROUTE_ACTIVE.bits
+            | InputFlags::ROUTE_FOCUSED.bits
+            | InputFlags::ROUTE_GLOBAL.bits
+            | InputFlags::ROUTE_ALWAYS.bits,
+    );
+    const ROUTE_OPTIONS_MASK: InputFlags = InputFlags::from_bits_truncate(
+        InputFlags::ROUTE_OVER_FOCUSED.bits
+            | InputFlags::ROUTE_OVER_ACTIVE.bits
+            | InputFlags::ROUTE_UNLESS_BG_FOCUSED.bits
+            | InputFlags::ROUTE_FROM_ROOT_WINDOW.bits,
+    );
+    const LOCK_MASK: InputFlags = InputFlags::from_bits_truncate(
+        InputFlags::LOCK_THIS_FRAME.bits | InputFlags::LOCK_UNTIL_RELEASE.bits,
+    );
+    const COND_MASK: InputFlags = InputFlags::from_bits_truncate(
+        InputFlags::COND_HOVERED.bits | InputFlags::COND_ACTIVE.bits,
+    );
+
+    /// Panics with a helpful message instead of letting Dear ImGui abort on its assertion
+    fn check_supported(self, supported: InputFlags, function: &str) {
+        assert!(
+            supported.contains(self),
+            "{function} doesn't support {:?}",
+            self - supported
+        );
+    }
+
+    /// Checks the routing flags the way `SetShortcutRouting` asserts them
+    fn check_routing(self) {
+        let route_type = self & InputFlags::ROUTE_TYPE_MASK;
+        assert!(
+            route_type.bits.count_ones() <= 1,
+            "only one route type can be used, got {route_type:?}"
+        );
+        let global_options = InputFlags::ROUTE_OVER_FOCUSED
+            | InputFlags::ROUTE_OVER_ACTIVE
+            | InputFlags::ROUTE_UNLESS_BG_FOCUSED;
+        assert!(
+            !self.intersects(global_options)
+                || route_type.is_empty()
+                || route_type == InputFlags::ROUTE_GLOBAL,
+            "{:?} requires ROUTE_GLOBAL",
+            self & global_options
+        );
+    }
+}
+
+bitflags! {
+    /// Keyboard modifiers of a [`KeyChord`]
+    #[repr(transparent)]
+    pub struct KeyMods: u32 {
+        const CTRL = sys::ImGuiMod_Ctrl;
+        const SHIFT = sys::ImGuiMod_Shift;
+        const ALT = sys::ImGuiMod_Alt;
+        /// Cmd/Super/Windows
+        const SUPER = sys::ImGuiMod_Super;
+    }
+}
+
+/// A key combined with modifiers, e.g. Ctrl+S
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// let save = KeyChord::new(Key::S).with_mods(KeyMods::CTRL);
+/// let escape: KeyChord = Key::Escape.into();
+/// ```
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct KeyChord {
+    pub key: Key,
+    pub mods: KeyMods,
+}
+
+impl KeyChord {
+    /// Creates a chord of `key` without modifiers
+    pub const fn new(key: Key) -> KeyChord {
+        KeyChord {
+            key,
+            mods: KeyMods::empty(),
+        }
+    }
+    /// Adds modifiers to the chord
+    pub const fn with_mods(mut self, mods: KeyMods) -> KeyChord {
+        self.mods = KeyMods::from_bits_truncate(self.mods.bits | mods.bits);
+        self
+    }
+    /// Returns the chord as an `ImGuiKeyChord`
+    pub fn raw(self) -> sys::ImGuiKeyChord {
+        (self.key as u32 | self.mods.bits()) as sys::ImGuiKeyChord
+    }
+}
+
+impl From<Key> for KeyChord {
+    fn from(key: Key) -> Self {
+        KeyChord::new(key)
+    }
+}
+
+/// Owner used by owner-aware key queries
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum KeyOwner {
+    /// Accept keys regardless of their owner, unless they were locked
+    Any,
+    /// Only accept keys that have no owner
+    NoOwner,
+    /// Accept keys that have no owner or are owned by this id
+    Id(Id),
+}
+
+impl KeyOwner {
+    /// Returns the `ImGuiID` Dear ImGui uses for this owner
+    pub fn raw(self) -> sys::ImGuiID {
+        match self {
+            KeyOwner::Any => 0,
+            KeyOwner::NoOwner => u32::MAX,
+            KeyOwner::Id(id) => id.0,
+        }
+    }
+}
+
+impl From<Id> for KeyOwner {
+    fn from(id: Id) -> Self {
+        KeyOwner::Id(id)
+    }
+}
+
+/// # Input: Key ownership and shortcut routing
+impl Ui {
+    /// Returns the current owner of a key, or `None` if it has no owner.
+    #[doc(alias = "GetKeyOwner")]
+    pub fn key_owner(&self, key: Key) -> Option<Id> {
+        match unsafe { sys::igGetKeyOwner(key as u32) } {
+            0 | u32::MAX => None,
+            id => Some(Id(id)),
+        }
+    }
+
+    /// Claims `key` for `owner`, so owner-aware queries with a different owner ignore it.
+    ///
+    /// Only [`InputFlags::LOCK_THIS_FRAME`] and [`InputFlags::LOCK_UNTIL_RELEASE`] are
+    /// supported. Locking also hides the key from code that isn't owner-aware. Use
+    /// [`KeyOwner::NoOwner`] to release a key, or [`KeyOwner::Any`] with a lock flag to hide it
+    /// from everyone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other flags are passed, or if `owner` is [`KeyOwner::Any`] without a lock flag.
+    #[doc(alias = "SetKeyOwner")]
+    pub fn set_key_owner(&self, key: Key, owner: impl Into<KeyOwner>, flags: InputFlags) {
+        let owner = owner.into();
+        flags.check_supported(InputFlags::LOCK_MASK, "set_key_owner");
+        assert!(
+            owner != KeyOwner::Any || flags.intersects(InputFlags::LOCK_MASK),
+            "KeyOwner::Any can only be used to lock a key"
+        );
+        unsafe { sys::igSetKeyOwner(key as u32, owner.raw(), flags.bits() as i32) }
+    }
+
+    /// Claims the keys of `chord`, including its modifiers, for `owner`.
+    ///
+    /// See [`Ui::set_key_owner`].
+    #[doc(alias = "SetKeyOwnersForKeyChord")]
+    pub fn set_key_owners_for_chord(
+        &self,
+        chord: impl Into<KeyChord>,
+        owner: impl Into<KeyOwner>,
+        flags: InputFlags,
+    ) {
+        let owner = owner.into();
+        flags.check_supported(InputFlags::LOCK_MASK, "set_key_owners_for_chord");
+        assert!(
+            owner != KeyOwner::Any || flags.intersects(InputFlags::LOCK_MASK),
+            "KeyOwner::Any can only be used to lock keys"
+        );
+        unsafe {
+            sys::igSetKeyOwnersForKeyChord(chord.into().raw(), owner.raw(), flags.bits() as i32)
+        }
+    }
+
+    /// Claims `key` for the last item if it's hovered or active.
+    #[doc(alias = "SetItemKeyOwner")]
+    pub fn set_item_key_owner(&self, key: Key) {
+        self.set_item_key_owner_with_flags(key, InputFlags::empty())
+    }
+
+    /// Claims `key` for the last item if it's hovered or active.
+    ///
+    /// Supports the lock flags like [`Ui::set_key_owner`], and [`InputFlags::COND_HOVERED`]
+    /// and [`InputFlags::COND_ACTIVE`] to only claim the key in one of the two cases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other flags are passed.
+    #[doc(alias = "SetItemKeyOwner")]
+    pub fn set_item_key_owner_with_flags(&self, key: Key, flags: InputFlags) {
+        flags.check_supported(
+            InputFlags::LOCK_MASK | InputFlags::COND_MASK,
+            "set_item_key_owner_with_flags",
+        );
+        unsafe { sys::igSetItemKeyOwner_InputFlags(key as u32, flags.bits() as i32) }
+    }
+
+    /// Returns true if `key` has no owner, or is owned by `owner`.
+    #[doc(alias = "TestKeyOwner")]
+    pub fn test_key_owner(&self, key: Key, owner: impl Into<KeyOwner>) -> bool {
+        unsafe { sys::igTestKeyOwner(key as u32, owner.into().raw()) }
+    }
+
+    /// Returns true if the key is being held and is available to `owner`.
+    #[doc(alias = "IsKeyDown")]
+    pub fn is_key_down_with_owner(&self, key: Key, owner: impl Into<KeyOwner>) -> bool {
+        unsafe { sys::igIsKeyDown_ID(key as u32, owner.into().raw()) }
+    }
+
+    /// Returns true if the key was pressed and is available to `owner`.
+    ///
+    /// Unlike [`Ui::is_key_pressed`], this doesn't repeat unless [`InputFlags::REPEAT`] is
+    /// passed. Only the repeat flags are supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other flags are passed.
+    #[doc(alias = "IsKeyPressed")]
+    pub fn is_key_pressed_with_owner(
+        &self,
+        key: Key,
+        flags: InputFlags,
+        owner: impl Into<KeyOwner>,
+    ) -> bool {
+        flags.check_supported(InputFlags::REPEAT_MASK, "is_key_pressed_with_owner");
+        unsafe {
+            sys::igIsKeyPressed_InputFlags(key as u32, flags.bits() as i32, owner.into().raw())
+        }
+    }
+
+    /// Returns true if the key was released and is available to `owner`.
+    #[doc(alias = "IsKeyReleased")]
+    pub fn is_key_released_with_owner(&self, key: Key, owner: impl Into<KeyOwner>) -> bool {
+        unsafe { sys::igIsKeyReleased_ID(key as u32, owner.into().raw()) }
+    }
+
+    /// Returns true if the chord was pressed, ignoring routing.
+    ///
+    /// Only the repeat flags are supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if other flags are passed.
+    #[doc(alias = "IsKeyChordPressed")]
+    pub fn is_key_chord_pressed_with_owner(
+        &self,
+        chord: impl Into<KeyChord>,
+        flags: InputFlags,
+        owner: impl Into<KeyOwner>,
+    ) -> bool {
+        flags.check_supported(InputFlags::REPEAT_MASK, "is_key_chord_pressed_with_owner");
+        unsafe {
+            sys::igIsKeyChordPressed_InputFlags(
+                chord.into().raw(),
+                flags.bits() as i32,
+                owner.into().raw(),
+            )
+        }
+    }
+
+    /// Returns true if the chord was pressed and routed to the current window.
+    ///
+    /// Routes to the focused window by default, see the `ROUTE_*` flags for other policies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if flags other than the repeat and routing flags are passed, or if more than one
+    /// route type is passed.
+    #[doc(alias = "Shortcut")]
+    pub fn shortcut(&self, chord: impl Into<KeyChord>, flags: InputFlags) -> bool {
+        self.shortcut_with_owner(chord, flags, KeyOwner::Any)
+    }
+
+    /// Returns true if the chord was pressed and routed to `owner`.
+    ///
+    /// With [`KeyOwner::Any`], the owner is the current focus scope, like [`Ui::shortcut`].
+    ///
+    /// # Panics
+    ///
+    /// See [`Ui::shortcut`].
+    #[doc(alias = "Shortcut")]
+    pub fn shortcut_with_owner(
+        &self,
+        chord: impl Into<KeyChord>,
+        flags: InputFlags,
+        owner: impl Into<KeyOwner>,
+    ) -> bool {
+        flags.check_supported(
+            InputFlags::REPEAT_MASK | InputFlags::ROUTE_TYPE_MASK | InputFlags::ROUTE_OPTIONS_MASK,
+            "shortcut",
+        );
+        flags.check_routing();
+        unsafe { sys::igShortcut_ID(chord.into().raw(), flags.bits() as i32, owner.into().raw()) }
+    }
+
+    /// Sets a shortcut that activates the next item, e.g. a button.
+    ///
+    /// Supports the same flags as [`Ui::shortcut`], plus [`InputFlags::TOOLTIP`].
+    ///
+    /// # Panics
+    ///
+    /// See [`Ui::shortcut`].
+    #[doc(alias = "SetNextItemShortcut")]
+    pub fn set_next_item_shortcut(&self, chord: impl Into<KeyChord>, flags: InputFlags) {
+        flags.check_supported(
+            InputFlags::REPEAT_MASK
+                | InputFlags::ROUTE_TYPE_MASK
+                | InputFlags::ROUTE_OPTIONS_MASK
+                | InputFlags::TOOLTIP,
+            "set_next_item_shortcut",
+        );
+        flags.check_routing();
+        unsafe { sys::igSetNextItemShortcut(chord.into().raw(), flags.bits() as i32) }
+    }
+
+    /// Requests the route of a chord for `owner`, and returns true if `owner` currently has it.
+    ///
+    /// Unlike [`Ui::shortcut`], the default route is [`InputFlags::ROUTE_GLOBAL`] with
+    /// [`InputFlags::ROUTE_OVER_FOCUSED`] and [`InputFlags::ROUTE_OVER_ACTIVE`], and this doesn't
+    /// check whether the chord is pressed. Routes are resolved at the start of the next frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if flags other than the routing flags are passed, or if more than one route type
+    /// is passed.
+    #[doc(alias = "SetShortcutRouting")]
+    pub fn set_shortcut_routing(
+        &self,
+        chord: impl Into<KeyChord>,
+        flags: InputFlags,
+        owner: Id,
+    ) -> bool {
+        flags.check_supported(
+            InputFlags::ROUTE_TYPE_MASK | InputFlags::ROUTE_OPTIONS_MASK,
+            "set_shortcut_routing",
+        );
+        flags.check_routing();
+        assert!(
+            owner.0 != 0 && owner.0 != u32::MAX,
+            "set_shortcut_routing needs an explicit owner"
+        );
+        unsafe { sys::igSetShortcutRouting(chord.into().raw(), flags.bits() as i32, owner.0) }
+    }
+
+    /// Returns true if the chord is routed to `owner`.
+    #[doc(alias = "TestShortcutRouting")]
+    pub fn test_shortcut_routing(
+        &self,
+        chord: impl Into<KeyChord>,
+        owner: impl Into<KeyOwner>,
+    ) -> bool {
+        unsafe { sys::igTestShortcutRouting(chord.into().raw(), owner.into().raw()) }
+    }
+}
+
+#[cfg(test)]
+fn press(ctx: &mut crate::Context, key: Key, down: bool) {
+    ctx.io_mut().add_key_event(key, down);
+}
+
+#[test]
+fn test_key_owner() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    press(&mut ctx, Key::W, true);
+    let ui = ctx.new_frame();
+    let viewport = ui.new_id_str("viewport");
+    let text_input = ui.new_id_str("text input");
+
+    assert_eq!(ui.key_owner(Key::W), None);
+    assert!(ui.is_key_down_with_owner(Key::W, text_input));
+    ui.set_key_owner(Key::W, viewport, InputFlags::empty());
+    assert_eq!(ui.key_owner(Key::W), Some(viewport));
+    assert!(ui.test_key_owner(Key::W, viewport));
+    assert!(!ui.test_key_owner(Key::W, text_input));
+    assert!(ui.is_key_down_with_owner(Key::W, viewport));
+    assert!(ui.is_key_pressed_with_owner(Key::W, InputFlags::empty(), viewport));
+    assert!(!ui.is_key_down_with_owner(Key::W, text_input));
+    assert!(!ui.is_key_down_with_owner(Key::W, KeyOwner::NoOwner));
+    // code that isn't owner-aware still sees the key...
+    assert!(ui.is_key_down(Key::W));
+
+    // ...unless it's locked
+    ui.set_key_owner(Key::W, viewport, InputFlags::LOCK_THIS_FRAME);
+    assert!(!ui.is_key_down(Key::W));
+    assert!(ui.is_key_down_with_owner(Key::W, viewport));
+
+    ui.set_key_owner(Key::W, KeyOwner::NoOwner, InputFlags::empty());
+    assert_eq!(ui.key_owner(Key::W), None);
+    assert!(ui.is_key_down_with_owner(Key::W, text_input));
+    ctx.render();
+}
+
+#[test]
+fn test_shortcut_routing() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let chord = KeyChord::new(Key::S).with_mods(KeyMods::CTRL);
+    assert_eq!(chord.raw(), (sys::ImGuiKey_S | sys::ImGuiMod_Ctrl) as i32);
+
+    // routes are resolved on the next frame, so request them every frame
+    let mut routed = [false; 2];
+    for frame in 0..3 {
+        if frame == 2 {
+            press(&mut ctx, Key::ModCtrl, true);
+            press(&mut ctx, Key::S, true);
+        }
+        let ui = ctx.new_frame();
+        let low = ui.new_id_str("low");
+        let high = ui.new_id_str("high");
+        ui.set_shortcut_routing(chord, InputFlags::ROUTE_GLOBAL, low);
+        ui.set_shortcut_routing(
+            chord,
+            InputFlags::ROUTE_GLOBAL | InputFlags::ROUTE_OVER_FOCUSED,
+            high,
+        );
+        routed = [
+            ui.test_shortcut_routing(chord, low),
+            ui.test_shortcut_routing(chord, high),
+        ];
+        if frame == 2 {
+            assert!(ui.is_key_chord_pressed_with_owner(chord, InputFlags::empty(), high));
+        }
+        ctx.render();
+    }
+    assert_eq!(routed, [false, true]);
+}
+
+#[test]
+#[should_panic(expected = "KeyOwner::Any can only be used to lock a key")]
+fn test_set_key_owner_any_without_lock() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    ui.set_key_owner(Key::W, KeyOwner::Any, InputFlags::empty());
+}
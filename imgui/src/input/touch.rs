@@ -0,0 +1,701 @@
+//! Touch gestures.
+//!
+//! Dear ImGui only knows about a single mouse. A [`TouchGestures`] recognizer consumes raw
+//! multi-touch points from the backend and turns them into mouse and wheel events:
+//!
+//! - a single finger taps and drags with the left mouse button,
+//! - a long press without moving clicks the right mouse button,
+//! - two fingers scroll, and keep scrolling for a while after being lifted (kinetic scrolling),
+//! - two fingers pinch to zoom. Dear ImGui has no zooming, so the zoom is exposed through
+//!   [`Ui::zoom_delta`] for the application to use.
+
+use std::collections::HashMap;
+
+use crate::input::mouse::{MouseButton, MouseSource};
+use crate::io::InputEvent;
+use crate::{Context, Ui};
+
+/// Phase of a touch point
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum TouchPhase {
+    /// A finger touched the screen
+    Started,
+    /// A finger moved
+    Moved,
+    /// A finger was lifted
+    Ended,
+    /// The touch was cancelled by the system, e.g. because the window lost focus
+    Cancelled,
+}
+
+/// A raw touch event from the backend
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TouchEvent {
+    /// Identifies the finger. Must stay the same from `Started` to `Ended`/`Cancelled`
+    pub id: u64,
+    /// Phase of the touch
+    pub phase: TouchPhase,
+    /// Position, in the same coordinates as [`Io::mouse_pos`](crate::Io::mouse_pos)
+    pub pos: [f32; 2],
+}
+
+/// Configuration of a [`TouchGestures`] recognizer
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GestureConfig {
+    /// Seconds a finger has to stay down without moving to right click (default: `0.5`)
+    pub long_press_duration: f32,
+    /// Distance in pixels a finger may move before a touch turns into a drag (default: `10.0`)
+    pub drag_threshold: f32,
+    /// Pixels of finger movement per mouse wheel step (default: `40.0`)
+    pub pixels_per_wheel_step: f32,
+    /// Whether scrolling continues after the fingers are lifted (default: `true`)
+    pub kinetic_scrolling: bool,
+    /// Rate at which kinetic scrolling slows down, per second (default: `4.0`)
+    pub kinetic_friction: f32,
+    /// Speed in pixels per second below which kinetic scrolling stops (default: `20.0`)
+    pub kinetic_min_speed: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        GestureConfig {
+            long_press_duration: 0.5,
+            drag_threshold: 10.0,
+            pixels_per_wheel_step: 40.0,
+            kinetic_scrolling: true,
+            kinetic_friction: 4.0,
+            kinetic_min_speed: 20.0,
+        }
+    }
+}
+
+/// Gestures recognized during a frame
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GestureState {
+    /// Number of fingers currently touching the screen
+    pub touch_count: usize,
+    /// Whether two or more fingers are scrolling or zooming
+    pub multi_touch: bool,
+    /// Whether the content is still scrolling after the fingers were lifted
+    pub kinetic: bool,
+    /// Whether a long press was recognized (and sent as a right click) this frame
+    pub long_press: bool,
+    /// Scrolled distance in pixels this frame, including kinetic scrolling
+    pub scroll_delta: [f32; 2],
+    /// Zoom factor this frame, e.g. `1.1` when the fingers moved 10% apart. `1.0` if not zooming
+    pub zoom_delta: f32,
+    /// Center of the fingers while zooming
+    pub zoom_center: [f32; 2],
+}
+
+impl Default for GestureState {
+    fn default() -> Self {
+        GestureState {
+            touch_count: 0,
+            multi_touch: false,
+            kinetic: false,
+            long_press: false,
+            scroll_delta: [0.0; 2],
+            zoom_delta: 1.0,
+            zoom_center: [0.0; 2],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Mode {
+    Idle,
+    /// A single finger is down, but it isn't known yet whether it taps, drags or long presses
+    Pending {
+        id: u64,
+        start: [f32; 2],
+        time: f64,
+    },
+    /// A single finger drags with the left mouse button down
+    Dragging {
+        id: u64,
+    },
+    /// A long press was sent, the finger only hovers until it's lifted
+    LongPressed {
+        id: u64,
+    },
+    /// Two or more fingers scroll and zoom. `center` is `None` while only one of them is left
+    MultiTouch {
+        center: Option<[f32; 2]>,
+        spread: f32,
+    },
+}
+
+/// Turns raw multi-touch points into mouse, wheel and zoom input.
+///
+/// Feed every touch event to [`TouchGestures::touch`] as it arrives, then call
+/// [`TouchGestures::update`] once per frame, after setting [`Io::delta_time`] and before
+/// [`Context::new_frame`].
+///
+/// [`Io::delta_time`]: crate::Io::delta_time
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// let mut gestures = TouchGestures::new(GestureConfig::default());
+/// loop {
+///     // for every touch event from the windowing library
+///     gestures.touch(TouchEvent { id: 0, phase: TouchPhase::Started, pos: [10.0, 20.0] });
+///     gestures.update(&mut ctx);
+///     let ui = ctx.new_frame();
+///     let zoom = ui.zoom_delta();
+///     // ...
+/// #   break;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TouchGestures {
+    config: GestureConfig,
+    queue: Vec<TouchEvent>,
+    touches: HashMap<u64, [f32; 2]>,
+    mode: Mode,
+    time: f64,
+    /// Smoothed multi-touch scroll velocity, in pixels per second
+    velocity: [f32; 2],
+    kinetic_velocity: Option<[f32; 2]>,
+    state: GestureState,
+}
+
+impl TouchGestures {
+    /// Creates a recognizer with no fingers down
+    pub fn new(config: GestureConfig) -> TouchGestures {
+        TouchGestures {
+            config,
+            queue: Vec::new(),
+            touches: HashMap::new(),
+            mode: Mode::Idle,
+            time: 0.0,
+            velocity: [0.0; 2],
+            kinetic_velocity: None,
+            state: GestureState::default(),
+        }
+    }
+    /// Returns the configuration
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+    /// Changes the configuration
+    pub fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+    /// Queues a touch event, to be handled by the next update
+    pub fn touch(&mut self, event: TouchEvent) {
+        self.queue.push(event);
+    }
+    /// Returns the gestures recognized by the last update
+    pub fn state(&self) -> &GestureState {
+        &self.state
+    }
+    /// Handles the queued touch events, sends the resulting input to the context's [`Io`], and
+    /// makes the recognized gestures available through [`Ui::gestures`] for the next frame.
+    ///
+    /// [`Io`]: crate::Io
+    pub fn update(&mut self, ctx: &mut Context) {
+        let delta_time = ctx.io().delta_time;
+        let io = ctx.io_mut();
+        self.update_with(delta_time, |event| io.push_event(event));
+        ctx.ui_mut().gestures = self.state;
+    }
+    /// Like [`TouchGestures::update`], but passes the events to `send` instead of queueing them
+    /// in a context, e.g. to test gestures or to record them.
+    pub fn update_with(
+        &mut self,
+        delta_time: f32,
+        mut send: impl FnMut(InputEvent),
+    ) -> &GestureState {
+        self.time += f64::from(delta_time);
+        self.state = GestureState::default();
+        let mut scrolled = [0.0; 2];
+        let mut lifted = false;
+        for event in std::mem::take(&mut self.queue) {
+            self.handle(event, &mut scrolled, &mut lifted, &mut send);
+        }
+        let multi_touch = matches!(self.mode, Mode::MultiTouch { .. });
+        if multi_touch {
+            self.scroll_and_zoom(&mut scrolled, &mut send);
+        }
+
+        if let Mode::Pending { id, time, .. } = self.mode {
+            if self.time - time >= f64::from(self.config.long_press_duration) {
+                send(InputEvent::MouseButton {
+                    button: MouseButton::Right,
+                    down: true,
+                });
+                send(InputEvent::MouseButton {
+                    button: MouseButton::Right,
+                    down: false,
+                });
+                self.mode = Mode::LongPressed { id };
+                self.state.long_press = true;
+            }
+        }
+
+        if delta_time > 0.0 && (multi_touch || lifted) {
+            // smoothed, so a single uneven frame doesn't decide the fling
+            for (velocity, scrolled) in self.velocity.iter_mut().zip(scrolled) {
+                *velocity = 0.5 * *velocity + 0.5 * scrolled / delta_time;
+            }
+        }
+        if lifted {
+            let speed = self.velocity[0].hypot(self.velocity[1]);
+            if self.config.kinetic_scrolling && speed >= self.config.kinetic_min_speed {
+                self.kinetic_velocity = Some(self.velocity);
+            }
+            self.velocity = [0.0; 2];
+        } else if let Some(velocity) = &mut self.kinetic_velocity {
+            let delta = [velocity[0] * delta_time, velocity[1] * delta_time];
+            let decay = (-self.config.kinetic_friction * delta_time).exp();
+            velocity[0] *= decay;
+            velocity[1] *= decay;
+            if velocity[0].hypot(velocity[1]) < self.config.kinetic_min_speed {
+                self.kinetic_velocity = None;
+            }
+            send_scroll(&self.config, delta, &mut send);
+            scrolled[0] += delta[0];
+            scrolled[1] += delta[1];
+        }
+
+        self.state.touch_count = self.touches.len();
+        self.state.multi_touch = multi_touch;
+        self.state.kinetic = self.kinetic_velocity.is_some();
+        self.state.scroll_delta = scrolled;
+        &self.state
+    }
+
+    fn handle(
+        &mut self,
+        event: TouchEvent,
+        scrolled: &mut [f32; 2],
+        lifted: &mut bool,
+        send: &mut impl FnMut(InputEvent),
+    ) {
+        let TouchEvent { id, phase, pos } = event;
+        match phase {
+            TouchPhase::Started => {
+                if let Mode::MultiTouch { .. } = self.mode {
+                    // don't lose the movement before the new finger changes the center
+                    self.scroll_and_zoom(scrolled, send);
+                }
+                self.touches.insert(id, pos);
+                self.kinetic_velocity = None;
+                match self.mode {
+                    Mode::Idle => {
+                        send(InputEvent::MouseSource(MouseSource::Touchscreen));
+                        send(InputEvent::MousePos(pos));
+                        self.mode = Mode::Pending {
+                            id,
+                            start: pos,
+                            time: self.time,
+                        };
+                    }
+                    Mode::Dragging { .. } => {
+                        send(InputEvent::MouseButton {
+                            button: MouseButton::Left,
+                            down: false,
+                        });
+                        self.start_multi_touch(send);
+                    }
+                    Mode::Pending { .. } | Mode::LongPressed { .. } | Mode::MultiTouch { .. } => {
+                        self.start_multi_touch(send)
+                    }
+                }
+            }
+            TouchPhase::Moved => {
+                match self.touches.get_mut(&id) {
+                    Some(touch) => *touch = pos,
+                    None => return,
+                }
+                match self.mode {
+                    Mode::Pending {
+                        id: pending, start, ..
+                    } if pending == id => {
+                        let distance = (pos[0] - start[0]).hypot(pos[1] - start[1]);
+                        if distance > self.config.drag_threshold {
+                            send(InputEvent::MouseButton {
+                                button: MouseButton::Left,
+                                down: true,
+                            });
+                            send(InputEvent::MousePos(pos));
+                            self.mode = Mode::Dragging { id };
+                        }
+                    }
+                    Mode::Dragging { id: dragging } | Mode::LongPressed { id: dragging }
+                        if dragging == id =>
+                    {
+                        send(InputEvent::MousePos(pos))
+                    }
+                    // handled once all of the frame's movements are known
+                    _ => (),
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                if !self.touches.contains_key(&id) {
+                    return;
+                }
+                if let Mode::MultiTouch { .. } = self.mode {
+                    self.scroll_and_zoom(scrolled, send);
+                }
+                self.touches.remove(&id);
+                let ended = phase == TouchPhase::Ended;
+                match self.mode {
+                    Mode::Pending { id: pending, .. } if pending == id => {
+                        if ended {
+                            send(InputEvent::MouseButton {
+                                button: MouseButton::Left,
+                                down: true,
+                            });
+                            send(InputEvent::MouseButton {
+                                button: MouseButton::Left,
+                                down: false,
+                            });
+                        }
+                        self.mode = Mode::Idle;
+                    }
+                    Mode::Dragging { id: dragging } if dragging == id => {
+                        send(InputEvent::MouseButton {
+                            button: MouseButton::Left,
+                            down: false,
+                        });
+                        self.mode = Mode::Idle;
+                    }
+                    Mode::LongPressed { id: pressed } if pressed == id => self.mode = Mode::Idle,
+                    Mode::MultiTouch { .. } if self.touches.is_empty() => {
+                        self.mode = Mode::Idle;
+                        // cancelled touches shouldn't fling the content
+                        *lifted = ended;
+                    }
+                    Mode::MultiTouch { .. } => self.start_multi_touch(send),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    /// Scrolls and zooms by how much the fingers moved since the last call
+    fn scroll_and_zoom(&mut self, scrolled: &mut [f32; 2], send: &mut impl FnMut(InputEvent)) {
+        let Mode::MultiTouch {
+            center: Some(old_center),
+            spread: old_spread,
+        } = self.mode
+        else {
+            return;
+        };
+        let (center, spread) = self.touch_center();
+        let delta = [center[0] - old_center[0], center[1] - old_center[1]];
+        if delta != [0.0; 2] {
+            send(InputEvent::MousePos(center));
+            send_scroll(&self.config, delta, send);
+            scrolled[0] += delta[0];
+            scrolled[1] += delta[1];
+        }
+        if old_spread > 0.0 && spread > 0.0 {
+            self.state.zoom_delta *= spread / old_spread;
+        }
+        self.state.zoom_center = center;
+        self.mode = Mode::MultiTouch {
+            center: Some(center),
+            spread,
+        };
+    }
+
+    /// Starts, or restarts after the number of fingers changed, scrolling and zooming
+    fn start_multi_touch(&mut self, send: &mut impl FnMut(InputEvent)) {
+        if self.touches.len() < 2 {
+            self.mode = Mode::MultiTouch {
+                center: None,
+                spread: 0.0,
+            };
+            return;
+        }
+        let (center, spread) = self.touch_center();
+        send(InputEvent::MousePos(center));
+        self.mode = Mode::MultiTouch {
+            center: Some(center),
+            spread,
+        };
+    }
+
+    /// Returns the center of all fingers, and their average distance to it
+    fn touch_center(&self) -> ([f32; 2], f32) {
+        let count = self.touches.len() as f32;
+        let mut center = [0.0; 2];
+        for pos in self.touches.values() {
+            center[0] += pos[0] / count;
+            center[1] += pos[1] / count;
+        }
+        let spread = self
+            .touches
+            .values()
+            .map(|pos| (pos[0] - center[0]).hypot(pos[1] - center[1]))
+            .sum::<f32>()
+            / count;
+        (center, spread)
+    }
+}
+
+/// Sends a scroll of `delta` pixels as wheel events. Moving the fingers down scrolls up, like
+/// turning the mouse wheel forward
+fn send_scroll(config: &GestureConfig, delta: [f32; 2], send: &mut impl FnMut(InputEvent)) {
+    if delta == [0.0; 2] || config.pixels_per_wheel_step <= 0.0 {
+        return;
+    }
+    send(InputEvent::MouseWheel([
+        delta[0] / config.pixels_per_wheel_step,
+        delta[1] / config.pixels_per_wheel_step,
+    ]));
+}
+
+/// # Input: Touch gestures
+impl Ui {
+    /// Returns the touch gestures recognized for this frame.
+    ///
+    /// Only filled in when a [`TouchGestures`] recognizer is updated before each frame.
+    pub fn gestures(&self) -> &GestureState {
+        &self.gestures
+    }
+    /// Returns the pinch zoom factor for this frame, `1.0` if not zooming.
+    ///
+    /// Multiply the zoom level of e.g. a canvas by this every frame.
+    pub fn zoom_delta(&self) -> f32 {
+        self.gestures.zoom_delta
+    }
+}
+
+#[cfg(test)]
+const DT: f32 = 1.0 / 60.0;
+
+#[cfg(test)]
+fn touch(id: u64, phase: TouchPhase, pos: [f32; 2]) -> TouchEvent {
+    TouchEvent { id, phase, pos }
+}
+
+#[cfg(test)]
+fn frame(gestures: &mut TouchGestures, events: &[TouchEvent]) -> Vec<InputEvent> {
+    for event in events {
+        gestures.touch(*event);
+    }
+    let mut sent = Vec::new();
+    gestures.update_with(DT, |event| sent.push(event));
+    sent
+}
+
+#[cfg(test)]
+fn left(down: bool) -> InputEvent {
+    InputEvent::MouseButton {
+        button: MouseButton::Left,
+        down,
+    }
+}
+
+#[cfg(test)]
+fn wheel(events: &[InputEvent]) -> [f32; 2] {
+    let mut total = [0.0; 2];
+    for event in events {
+        if let InputEvent::MouseWheel(wheel) = event {
+            total[0] += wheel[0];
+            total[1] += wheel[1];
+        }
+    }
+    total
+}
+
+#[test]
+fn test_tap_and_drag() {
+    let mut gestures = TouchGestures::new(GestureConfig::default());
+    let sent = frame(
+        &mut gestures,
+        &[touch(1, TouchPhase::Started, [10.0, 10.0])],
+    );
+    assert_eq!(
+        sent,
+        vec![
+            InputEvent::MouseSource(MouseSource::Touchscreen),
+            InputEvent::MousePos([10.0, 10.0])
+        ]
+    );
+    // small movements don't start a drag, lifting the finger taps
+    let sent = frame(
+        &mut gestures,
+        &[
+            touch(1, TouchPhase::Moved, [12.0, 10.0]),
+            touch(1, TouchPhase::Ended, [12.0, 10.0]),
+        ],
+    );
+    assert_eq!(sent, vec![left(true), left(false)]);
+
+    frame(
+        &mut gestures,
+        &[touch(2, TouchPhase::Started, [10.0, 10.0])],
+    );
+    let sent = frame(&mut gestures, &[touch(2, TouchPhase::Moved, [50.0, 10.0])]);
+    assert_eq!(sent, vec![left(true), InputEvent::MousePos([50.0, 10.0])]);
+    let sent = frame(&mut gestures, &[touch(2, TouchPhase::Ended, [50.0, 10.0])]);
+    assert_eq!(sent, vec![left(false)]);
+    assert_eq!(gestures.state().touch_count, 0);
+}
+
+#[test]
+fn test_long_press() {
+    let mut gestures = TouchGestures::new(GestureConfig::default());
+    frame(
+        &mut gestures,
+        &[touch(1, TouchPhase::Started, [10.0, 10.0])],
+    );
+    let mut right_clicks = 0;
+    for _ in 0..40 {
+        let sent = frame(&mut gestures, &[]);
+        if sent.contains(&InputEvent::MouseButton {
+            button: MouseButton::Right,
+            down: true,
+        }) {
+            right_clicks += 1;
+            assert!(gestures.state().long_press);
+        }
+    }
+    assert_eq!(right_clicks, 1);
+    // lifting the finger afterwards doesn't click
+    let sent = frame(&mut gestures, &[touch(1, TouchPhase::Ended, [10.0, 10.0])]);
+    assert_eq!(sent, vec![]);
+}
+
+#[test]
+fn test_two_finger_scroll_and_pinch() {
+    let mut gestures = TouchGestures::new(GestureConfig {
+        kinetic_scrolling: false,
+        ..Default::default()
+    });
+    frame(
+        &mut gestures,
+        &[
+            touch(1, TouchPhase::Started, [100.0, 100.0]),
+            touch(2, TouchPhase::Started, [200.0, 100.0]),
+        ],
+    );
+    assert!(gestures.state().multi_touch);
+
+    let sent = frame(
+        &mut gestures,
+        &[
+            touch(1, TouchPhase::Moved, [100.0, 140.0]),
+            touch(2, TouchPhase::Moved, [200.0, 140.0]),
+        ],
+    );
+    assert!(!sent.contains(&left(true)));
+    assert_eq!(wheel(&sent), [0.0, 1.0]);
+    assert_eq!(gestures.state().scroll_delta, [0.0, 40.0]);
+    assert_eq!(gestures.state().zoom_delta, 1.0);
+
+    frame(
+        &mut gestures,
+        &[
+            touch(1, TouchPhase::Moved, [50.0, 140.0]),
+            touch(2, TouchPhase::Moved, [250.0, 140.0]),
+        ],
+    );
+    assert_eq!(gestures.state().zoom_delta, 2.0);
+    assert_eq!(gestures.state().zoom_center, [150.0, 140.0]);
+    assert_eq!(gestures.state().scroll_delta, [0.0, 0.0]);
+
+    let sent = frame(
+        &mut gestures,
+        &[
+            touch(1, TouchPhase::Ended, [50.0, 140.0]),
+            touch(2, TouchPhase::Ended, [250.0, 140.0]),
+        ],
+    );
+    assert_eq!(sent, vec![]);
+    assert!(!gestures.state().kinetic);
+}
+
+#[test]
+fn test_kinetic_scroll() {
+    let mut gestures = TouchGestures::new(GestureConfig::default());
+    frame(
+        &mut gestures,
+        &[
+            touch(1, TouchPhase::Started, [100.0, 100.0]),
+            touch(2, TouchPhase::Started, [200.0, 100.0]),
+        ],
+    );
+    let mut y = 100.0;
+    for _ in 0..5 {
+        y -= 10.0;
+        frame(
+            &mut gestures,
+            &[
+                touch(1, TouchPhase::Moved, [100.0, y]),
+                touch(2, TouchPhase::Moved, [200.0, y]),
+            ],
+        );
+    }
+    frame(
+        &mut gestures,
+        &[
+            touch(1, TouchPhase::Ended, [100.0, y]),
+            touch(2, TouchPhase::Ended, [200.0, y]),
+        ],
+    );
+    assert!(gestures.state().kinetic);
+
+    // keeps scrolling down, slower and slower, until it stops
+    let mut last = f32::NEG_INFINITY;
+    let mut frames = 0;
+    while gestures.state().kinetic {
+        let sent = frame(&mut gestures, &[]);
+        let scroll = wheel(&sent)[1];
+        assert!(scroll <= 0.0 && scroll >= last);
+        last = scroll;
+        frames += 1;
+        assert!(frames < 600);
+    }
+    assert!(frames > 10);
+
+    // touching the screen stops kinetic scrolling, and cancelled touches don't fling
+    frame(
+        &mut gestures,
+        &[touch(3, TouchPhase::Started, [100.0, 100.0])],
+    );
+    assert!(!gestures.state().kinetic);
+    frame(
+        &mut gestures,
+        &[touch(4, TouchPhase::Started, [200.0, 100.0])],
+    );
+    for y in [150.0, 200.0, 250.0] {
+        frame(
+            &mut gestures,
+            &[
+                touch(3, TouchPhase::Moved, [100.0, y]),
+                touch(4, TouchPhase::Moved, [200.0, y]),
+            ],
+        );
+    }
+    frame(
+        &mut gestures,
+        &[
+            touch(3, TouchPhase::Cancelled, [100.0, 250.0]),
+            touch(4, TouchPhase::Cancelled, [200.0, 250.0]),
+        ],
+    );
+    assert!(!gestures.state().kinetic);
+}
+
+#[test]
+fn test_ui_zoom_delta() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut gestures = TouchGestures::new(GestureConfig::default());
+    gestures.touch(touch(1, TouchPhase::Started, [100.0, 100.0]));
+    gestures.touch(touch(2, TouchPhase::Started, [200.0, 100.0]));
+    gestures.update(&mut ctx);
+    gestures.touch(touch(2, TouchPhase::Moved, [400.0, 100.0]));
+    gestures.update(&mut ctx);
+    let ui = ctx.new_frame();
+    assert_eq!(ui.zoom_delta(), 3.0);
+    assert_eq!(ui.io().mouse_source, MouseSource::Touchscreen);
+}
@@ -6,6 +6,9 @@ use std::ptr;
 use crate::internal::DataTypeKind;
 use crate::math::*;
 use crate::sys;
+use crate::widget::expression::Expressions;
+use crate::widget::format::{self, DisplayFormat};
+use crate::widget::validation::{self, Validated};
 use crate::Ui;
 
 bitflags!(
@@ -272,6 +275,16 @@ where
         }
     }
 
+    /// Checks the edited text with `validator` before committing it to the string.
+    ///
+    /// See [`Validated`].
+    pub fn validate<V>(self, validator: V) -> Validated<'static, Self, V>
+    where
+        V: FnOnce(&String) -> Result<(), String>,
+    {
+        Validated::new(self, validator)
+    }
+
     /// Builds the string editor, performing string editing operations.
     ///
     /// # String Editing
@@ -348,6 +361,40 @@ where
     }
 }
 
+impl<'ui, 'p, L, H, T> validation::private::Sealed<'ui, 'p> for InputText<'ui, 'p, L, H, T>
+where
+    L: AsRef<str>,
+    H: AsRef<str>,
+    T: InputTextCallbackHandler,
+{
+    type Value = String;
+
+    fn ui(&self) -> &'ui Ui {
+        self.ui
+    }
+
+    fn label(&self) -> &str {
+        self.label.as_ref()
+    }
+
+    fn value(&self) -> &String {
+        self.buf
+    }
+
+    fn build_draft(self, draft: &mut String) -> &'p mut String {
+        InputText {
+            label: self.label,
+            hint: self.hint,
+            buf: draft,
+            callback_handler: self.callback_handler,
+            flags: self.flags,
+            ui: self.ui,
+        }
+        .build();
+        self.buf
+    }
+}
+
 #[must_use]
 pub struct InputTextMultiline<'ui, 'p, L, T = PassthroughCallback> {
     label: L,
@@ -577,9 +624,10 @@ impl<'ui, 'p, L: AsRef<str>> InputFloat<'ui, 'p, L> {
     }
 
     pub fn build(self) -> bool {
-        let (one, two) = self
-            .ui
-            .scratch_txt_with_opt(self.label, self.display_format);
+        let display_format = self
+            .display_format
+            .map(|v| format::valid_format(v, f32::KIND));
+        let (one, two) = self.ui.scratch_txt_with_opt(self.label, display_format);
 
         unsafe {
             sys::igInputFloat(
@@ -638,12 +686,14 @@ macro_rules! impl_input_floatn {
             }
 
             pub fn build(self) -> bool {
+                let display_format = self
+                    .display_format
+                    .as_ref()
+                    .map(|v| format::valid_format(v.as_ref(), f32::KIND));
                 let value: $MINT_TARGET = (*self.value).into();
                 let mut value: [f32; $N] = value.into();
 
-                let (one, two) = self
-                    .ui
-                    .scratch_txt_with_opt(self.label, self.display_format);
+                let (one, two) = self.ui.scratch_txt_with_opt(self.label, display_format);
 
                 let changed = unsafe {
                     sys::$igInputFloatN(one, value.as_mut_ptr(), two, self.flags.bits() as i32)
@@ -748,9 +798,12 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind> InputScalar<'ui, 'p, T, L> {
     }
 }
 
-impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalar<'ui, 'p, T, L, F> {
-    /// Sets the display format using *a C-style printf string*
-    pub fn display_format<F2: AsRef<str>>(
+impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: DisplayFormat<T>> InputScalar<'ui, 'p, T, L, F> {
+    /// Sets the display format, using either *a C-style printf string* or a [`FormatWith`]
+    /// closure
+    ///
+    /// [`FormatWith`]: crate::FormatWith
+    pub fn display_format<F2: DisplayFormat<T>>(
         self,
         display_format: F2,
     ) -> InputScalar<'ui, 'p, T, L, F2> {
@@ -764,14 +817,46 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalar<'ui, 'p
             ui: self.ui,
         }
     }
+    /// Evaluates arithmetic expressions such as `1920/2` or `+=15` typed in the input.
+    ///
+    /// This replaces the display format, which can be set with
+    /// [`Expressions::display_format`].
+    pub fn expressions<'e>(
+        self,
+        expressions: Expressions<'e>,
+    ) -> InputScalar<'ui, 'p, T, L, Expressions<'e>> {
+        self.display_format(expressions)
+    }
+    /// Checks the edited value with `validator` before committing it to the bound value.
+    ///
+    /// See [`Validated`].
+    pub fn validate<V>(self, validator: V) -> Validated<'static, Self, V>
+    where
+        V: FnOnce(&T) -> Result<(), String>,
+    {
+        Validated::new(self, validator)
+    }
     /// Builds an input scalar that is bound to the given value.
     ///
     /// Returns true if the value was changed.
     pub fn build(self) -> bool {
+        let display_format =
+            match format::native_format(self.display_format.as_ref().map(|f| f.kind())) {
+                Ok(display_format) => display_format,
+                Err(format) => {
+                    return format::build_input_scalar(
+                        self.ui,
+                        self.label.as_ref(),
+                        self.value,
+                        self.step,
+                        self.step_fast,
+                        format,
+                        self.flags,
+                    );
+                }
+            };
         unsafe {
-            let (one, two) = self
-                .ui
-                .scratch_txt_with_opt(self.label, self.display_format);
+            let (one, two) = self.ui.scratch_txt_with_opt(&self.label, display_format);
 
             sys::igInputScalar(
                 one,
@@ -806,6 +891,41 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalar<'ui, 'p
     impl_text_flags!(InputScalar);
 }
 
+impl<'ui, 'p, T, L, F> validation::private::Sealed<'ui, 'p> for InputScalar<'ui, 'p, T, L, F>
+where
+    T: DataTypeKind + PartialEq + 'static,
+    L: AsRef<str>,
+    F: DisplayFormat<T>,
+{
+    type Value = T;
+
+    fn ui(&self) -> &'ui Ui {
+        self.ui
+    }
+
+    fn label(&self) -> &str {
+        self.label.as_ref()
+    }
+
+    fn value(&self) -> &T {
+        self.value
+    }
+
+    fn build_draft(self, draft: &mut T) -> &'p mut T {
+        InputScalar {
+            value: draft,
+            label: self.label,
+            step: self.step,
+            step_fast: self.step_fast,
+            display_format: self.display_format,
+            flags: self.flags,
+            ui: self.ui,
+        }
+        .build();
+        self.value
+    }
+}
+
 /// Builder for an input scalar widget.
 #[must_use]
 pub struct InputScalarN<'ui, 'p, T, L, F = &'static str> {
@@ -854,10 +974,12 @@ impl<'ui, 'p, L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> InputScalarN<'ui, '
     ///
     /// Returns true if any value was changed.
     pub fn build(self) -> bool {
+        let display_format = self
+            .display_format
+            .as_ref()
+            .map(|v| format::valid_format(v.as_ref(), T::KIND));
         unsafe {
-            let (one, two) = self
-                .ui
-                .scratch_txt_with_opt(self.label, self.display_format);
+            let (one, two) = self.ui.scratch_txt_with_opt(self.label, display_format);
 
             sys::igInputScalarN(
                 one,
@@ -116,6 +116,34 @@ bitflags! {
     }
 }
 
+/// A single input event, as queued by the `Io::add_*_event` functions.
+///
+/// Platform backends, input recorders and tests can describe input with this type and feed it to
+/// Dear ImGui with [`Io::push_event`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    /// The mouse moved to the given position. See [`Io::add_mouse_pos_event`]
+    MousePos([f32; 2]),
+    /// A mouse button was pressed or released. See [`Io::add_mouse_button_event`]
+    MouseButton { button: MouseButton, down: bool },
+    /// The mouse wheel was scrolled. See [`Io::add_mouse_wheel_event`]
+    MouseWheel([f32; 2]),
+    /// The following mouse events come from the given kind of device. See
+    /// [`Io::add_mouse_source_event`]
+    MouseSource(MouseSource),
+    /// The mouse hovers the given viewport. See [`Io::add_mouse_viewport_event`]
+    #[cfg(feature = "docking")]
+    MouseViewport(crate::Id),
+    /// A key was pressed or released. See [`Io::add_key_event`]
+    Key { key: Key, down: bool },
+    /// An analog key (e.g. a gamepad trigger) changed. See [`Io::add_key_analog_event`]
+    KeyAnalog { key: Key, down: bool, value: f32 },
+    /// A character was typed. See [`Io::add_input_character`]
+    Text(char),
+    /// The application gained or lost focus. See [`Io::add_focus_event`]
+    Focus(bool),
+}
+
 /// Settings and inputs/outputs for imgui-rs
 #[repr(C)]
 pub struct Io {
@@ -472,6 +500,107 @@ impl Io {
             sys::ImGuiIO_AddKeyAnalogEvent(self.raw_mut(), key as u32, down, value);
         }
     }
+
+    /// Queue a UTF-16 code unit of character input.
+    ///
+    /// Surrogate pairs can be passed one code unit at a time.
+    #[doc(alias = "AddInputCharacterUTF16")]
+    pub fn add_input_character_utf16(&mut self, c: u16) {
+        unsafe {
+            sys::ImGuiIO_AddInputCharacterUTF16(self.raw_mut(), c);
+        }
+    }
+
+    /// Queue a change of the device the following mouse events come from
+    #[doc(alias = "AddMouseSourceEvent")]
+    pub fn add_mouse_source_event(&mut self, source: MouseSource) {
+        unsafe {
+            sys::ImGuiIO_AddMouseSourceEvent(self.raw_mut(), source as sys::ImGuiMouseSource);
+        }
+    }
+
+    /// Queue a change of the viewport hovered by the mouse.
+    ///
+    /// Only needed by platform backends that can tell which viewport is hovered, ignoring
+    /// viewports that don't accept input.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "AddMouseViewportEvent")]
+    pub fn add_mouse_viewport_event(&mut self, viewport_id: crate::Id) {
+        unsafe {
+            sys::ImGuiIO_AddMouseViewportEvent(self.raw_mut(), viewport_id.0);
+        }
+    }
+
+    /// Sets the native keycode and scancode of the last key event.
+    ///
+    /// This is only used by legacy code expecting native key indices, and must be called right
+    /// after [`Io::add_key_event`].
+    #[doc(alias = "SetKeyEventNativeData")]
+    pub fn set_key_event_native_data(
+        &mut self,
+        key: Key,
+        native_keycode: i32,
+        native_scancode: i32,
+    ) {
+        unsafe {
+            sys::ImGuiIO_SetKeyEventNativeData(
+                self.raw_mut(),
+                key as u32,
+                native_keycode,
+                native_scancode,
+                -1,
+            );
+        }
+    }
+
+    /// Sets whether new input events are accepted (default `true`).
+    ///
+    /// While this is `false`, events passed to the `add_*_event` functions are dropped.
+    #[doc(alias = "SetAppAcceptingEvents")]
+    pub fn set_app_accepting_events(&mut self, accepting_events: bool) {
+        unsafe {
+            sys::ImGuiIO_SetAppAcceptingEvents(self.raw_mut(), accepting_events);
+        }
+    }
+
+    /// Returns true if new input events are accepted. See [`Io::set_app_accepting_events`]
+    pub fn app_accepting_events(&self) -> bool {
+        self.app_accepting_events
+    }
+
+    /// Drops all queued input events that haven't been processed by a `new_frame` yet
+    #[doc(alias = "ClearEventsQueue")]
+    pub fn clear_events_queue(&mut self) {
+        unsafe {
+            sys::ImGuiIO_ClearEventsQueue(self.raw_mut());
+        }
+    }
+
+    /// Clears the current mouse state (position, buttons and wheel)
+    #[doc(alias = "ClearInputMouse")]
+    pub fn clear_input_mouse(&mut self) {
+        unsafe {
+            sys::ImGuiIO_ClearInputMouse(self.raw_mut());
+        }
+    }
+
+    /// Queue an input event by calling the matching `add_*_event` function
+    pub fn push_event(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::MousePos(pos) => self.add_mouse_pos_event(pos),
+            InputEvent::MouseButton { button, down } => self.add_mouse_button_event(button, down),
+            InputEvent::MouseWheel(wheel) => self.add_mouse_wheel_event(wheel),
+            InputEvent::MouseSource(source) => self.add_mouse_source_event(source),
+            #[cfg(feature = "docking")]
+            InputEvent::MouseViewport(viewport_id) => self.add_mouse_viewport_event(viewport_id),
+            InputEvent::Key { key, down } => self.add_key_event(key, down),
+            InputEvent::KeyAnalog { key, down, value } => {
+                self.add_key_analog_event(key, down, value)
+            }
+            InputEvent::Text(character) => self.add_input_character(character),
+            InputEvent::Focus(focused) => self.add_focus_event(focused),
+        }
+    }
 }
 
 impl Index<MouseButton> for Io {
@@ -650,3 +779,37 @@ fn test_io_memory_layout() {
         .join()
         .unwrap();
 }
+
+#[test]
+fn test_push_event() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let io = ctx.io_mut();
+    // process every event in the first frame
+    io.config_input_trickle_event_queue = false;
+    io.push_event(InputEvent::MousePos([10.0, 20.0]));
+    io.push_event(InputEvent::MouseButton {
+        button: MouseButton::Right,
+        down: true,
+    });
+    io.push_event(InputEvent::Key {
+        key: Key::A,
+        down: true,
+    });
+    io.push_event(InputEvent::Text('x'));
+    let ui = ctx.new_frame();
+    assert!(ui.is_key_down(Key::A));
+    let io = ui.io();
+    assert_eq!(io.mouse_pos, [10.0, 20.0]);
+    assert!(io[MouseButton::Right]);
+    assert_eq!(io.peek_input_characters(), "x");
+    ctx.render();
+
+    let io = ctx.io_mut();
+    io.push_event(InputEvent::MousePos([30.0, 40.0]));
+    io.clear_events_queue();
+    io.set_app_accepting_events(false);
+    io.push_event(InputEvent::MousePos([50.0, 60.0]));
+    assert!(!io.app_accepting_events());
+    ctx.new_frame();
+    assert_eq!(ctx.io().mouse_pos, [10.0, 20.0]);
+}
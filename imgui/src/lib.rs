@@ -98,16 +98,23 @@ pub use self::docking_utils::*;
 pub use self::drag_drop::{DragDropFlags, DragDropSource, DragDropTarget};
 pub use self::draw_list::{ChannelsSplit, DrawListMut};
 pub use self::fonts::atlas::*;
+pub use self::fonts::cache::*;
 pub use self::fonts::font::*;
 pub use self::fonts::glyph::*;
 pub use self::fonts::glyph_ranges::*;
+pub use self::fonts::scaled::*;
+pub use self::input::gamepad::*;
 pub use self::input::keyboard::*;
 pub use self::input::mouse::*;
+pub use self::input::recording::*;
+pub use self::input::routing::*;
+pub use self::input::touch::*;
 pub use self::input_widget::*;
 pub use self::io::*;
 pub use self::layout::*;
 pub use self::list_clipper::ListClipper;
 pub use self::platform_io::*;
+pub use self::platform_services::*;
 pub use self::plothistogram::PlotHistogram;
 pub use self::plotlines::PlotLines;
 pub use self::popups::*;
@@ -120,21 +127,45 @@ pub use self::style::*;
 #[cfg(feature = "tables-api")]
 pub use self::tables::*;
 pub use self::text_filter::*;
+pub use self::typing_select::*;
 pub use self::utils::*;
+pub use self::widget::autocomplete::*;
+pub use self::widget::code_editor::*;
 pub use self::widget::color_editors::*;
 pub use self::widget::combo_box::*;
+pub use self::widget::custom_widget::{ButtonBehaviorFlags, CustomWidget, ItemState, Rect};
 pub use self::widget::drag::*;
+pub use self::widget::enum_variants::EnumVariants;
+pub use self::widget::expression::{ExpressionError, Expressions};
+pub use self::widget::filtered_combo::{ComboFilterMode, FilteredCombo};
+pub use self::widget::format::{DisplayFormat, FormatWith};
 pub use self::widget::image::*;
+#[cfg(feature = "tables-api")]
+pub use self::widget::inspect::{Inspect, InspectOptions};
+pub use self::widget::knob::Knob;
 pub use self::widget::list_box::*;
 pub use self::widget::menu::*;
 pub use self::widget::misc::*;
+pub use self::widget::plot::{Plot, PlotContent, PlotPoint, PlotRange, PlotResponse, PlotSeries};
 pub use self::widget::progress_bar::*;
+pub use self::widget::radio_group::{ItemTooltipFn, RadioGroup};
+pub use self::widget::segmented_control::SegmentedControl;
 pub use self::widget::selectable::*;
 pub use self::widget::slider::*;
+pub use self::widget::spinner::{LoadingBar, Spinner};
+pub use self::widget::splitter::{SplitDirection, SplitPanes, Splitter};
 pub use self::widget::tab::*;
+pub use self::widget::toggle::Toggle;
 pub use self::widget::tree::*;
+pub use self::widget::validation::{Form, Validated};
 pub use self::window::child_window::*;
 pub use self::window::*;
+
+#[cfg(feature = "derive")]
+pub use imgui_derive::EnumVariants;
+#[cfg(feature = "derive")]
+pub use imgui_derive::Inspect;
+
 use internal::RawCast;
 use math::*;
 
@@ -144,6 +175,7 @@ mod string;
 #[macro_use]
 mod tokens;
 
+mod binary;
 mod clipboard;
 pub mod color;
 mod columns;
@@ -164,6 +196,7 @@ mod layout;
 mod list_clipper;
 mod math;
 mod platform_io;
+mod platform_services;
 mod plothistogram;
 mod plotlines;
 mod popups;
@@ -175,6 +208,7 @@ mod tables;
 #[cfg(test)]
 mod test;
 pub mod text_filter;
+mod typing_select;
 mod utils;
 mod widget;
 mod window;
@@ -184,6 +218,10 @@ mod window;
 #[doc(hidden)]
 pub use core as __core;
 
+// Lets the tests use the derive macros, which refer to `::imgui`
+#[cfg(all(test, feature = "derive"))]
+extern crate self as imgui;
+
 /// Returns the underlying Dear ImGui library version
 #[doc(alias = "GetVersion")]
 pub fn dear_imgui_version() -> &'static str {
@@ -215,6 +253,14 @@ impl Context {
 pub struct Ui {
     /// our scratch sheet
     buffer: cell::UnsafeCell<string::UiBuffer>,
+    /// touch gestures recognized for this frame
+    gestures: GestureState,
+    /// value of the numeric widget whose text input was activated last, as raw bytes
+    text_input_initial: cell::Cell<Option<(sys::ImGuiID, [u8; 8])>>,
+    /// error of the text typed in the active numeric widget text input
+    text_input_error: cell::RefCell<Option<(sys::ImGuiID, String)>>,
+    /// states of the widgets kept between frames, by id
+    widget_states: Box<widget::state::WidgetStates>,
 }
 
 impl Ui {
@@ -513,6 +559,16 @@ impl<'ui> Ui {
         InputText::new(self, label, buf)
     }
 
+    /// Edits text in a single line input widget, with a dropdown of candidates to complete
+    /// the text. See [`AutocompleteInput`].
+    pub fn input_text_autocomplete<'p, L: AsRef<str>>(
+        &'ui self,
+        label: L,
+        buf: &'p mut String,
+    ) -> AutocompleteInput<'ui, 'p, L> {
+        AutocompleteInput::new(self, label, buf)
+    }
+
     /// Edits text in a multi line widget. Similar to [`Self::input_text`]
     /// but requires specifying a size. [`Self::content_region_avail`]
     /// can be useful to make this take up all avaialble space
@@ -890,6 +946,29 @@ impl<'ui> Ui {
         PlotHistogram::new(self, label, values)
     }
 
+    /// Plot `count` floats returned by `getter` as a "sparkline" style plot, see
+    /// [`PlotLines::from_fn`]
+    #[doc(alias = "PlotLines")]
+    pub fn plot_lines_fn<'p, Label: AsRef<str>>(
+        &'ui self,
+        label: Label,
+        count: usize,
+        getter: impl FnMut(usize) -> f32 + 'p,
+    ) -> PlotLines<'ui, 'p, Label> {
+        PlotLines::from_fn(self, label, count, getter)
+    }
+
+    /// Plot `count` floats returned by `getter` as a histogram, see [`PlotHistogram::from_fn`]
+    #[doc(alias = "PlotHistogram")]
+    pub fn plot_histogram_fn<'p, Label: AsRef<str>>(
+        &'ui self,
+        label: Label,
+        count: usize,
+        getter: impl FnMut(usize) -> f32 + 'p,
+    ) -> PlotHistogram<'ui, 'p, Label> {
+        PlotHistogram::from_fn(self, label, count, getter)
+    }
+
     /// Calculate the size required for a given text string.
     ///
     /// This is the same as [calc_text_size_with_opts](Self::calc_text_size_with_opts)
@@ -117,6 +117,15 @@ impl<'ui> ListClipperToken<'ui> {
         unsafe { (*self.list_clipper).DisplayEnd }
     }
 
+    /// Forces the item at `index` to be displayed, even if it is outside of the visible range.
+    ///
+    /// Call this before the first call to `step`, e.g. to submit an item that is about to be
+    /// scrolled to or focused.
+    #[doc(alias = "IncludeItemByIndex")]
+    pub fn include_item_by_index(&mut self, index: i32) {
+        unsafe { sys::ImGuiListClipper_IncludeItemByIndex(self.list_clipper, index) }
+    }
+
     /// Get an iterator which outputs all visible indexes. This is the
     /// recommended way of using the clipper.
     pub fn iter(self) -> ListClipperIterator<'ui> {
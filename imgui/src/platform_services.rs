@@ -0,0 +1,221 @@
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::process;
+use std::rc::Rc;
+
+use crate::clipboard::ClipboardBackend;
+use crate::Ui;
+
+/// Text input (IME) state reported by Dear ImGui, e.g. to position the candidate window of an
+/// input method editor
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PlatformImeData {
+    /// Whether an input method editor should be shown, i.e. a text input is active
+    pub want_visible: bool,
+    /// Position of the text cursor, in the same coordinates as
+    /// [`Io::mouse_pos`](crate::Io::mouse_pos)
+    pub input_pos: [f32; 2],
+    /// Height of the text line at the cursor
+    pub input_line_height: f32,
+}
+
+/// Trait for the services Dear ImGui needs from the platform: text input (IME) positioning,
+/// opening URLs and the clipboard.
+///
+/// Every method has a default implementation that does nothing, so implementors only need to
+/// provide the services their platform supports.
+///
+/// Install an implementation with [`Context::set_platform_services`].
+///
+/// [`Context::set_platform_services`]: crate::Context::set_platform_services
+pub trait PlatformServices: 'static {
+    /// Called when the text input state changes, e.g. when a text input gains focus or its
+    /// cursor moves
+    fn set_ime_data(&mut self, data: &PlatformImeData) {
+        let _ = data;
+    }
+    /// Opens `url` in the platform's browser or file manager, e.g. for
+    /// [`Ui::text_link_open_url`]. Returns true on success
+    fn open_url(&mut self, url: &str) -> bool {
+        let _ = url;
+        false
+    }
+    /// Returns the current clipboard contents, or None if the clipboard is empty or cannot be
+    /// accessed
+    fn clipboard_text(&mut self) -> Option<String> {
+        None
+    }
+    /// Sets the clipboard contents
+    fn set_clipboard_text(&mut self, text: &str) {
+        let _ = text;
+    }
+}
+
+pub(crate) type SharedPlatformServices = Rc<RefCell<dyn PlatformServices>>;
+
+pub(crate) struct PlatformServicesContext {
+    pub(crate) services: SharedPlatformServices,
+}
+
+impl fmt::Debug for PlatformServicesContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlatformServicesContext")
+            .field("services", &Rc::as_ptr(&self.services))
+            .finish()
+    }
+}
+
+/// Forwards the clipboard part of [`PlatformServices`] to the regular clipboard support
+pub(crate) struct PlatformServicesClipboard(pub(crate) SharedPlatformServices);
+
+impl ClipboardBackend for PlatformServicesClipboard {
+    fn get(&mut self) -> Option<String> {
+        self.0.borrow_mut().clipboard_text()
+    }
+    fn set(&mut self, value: &str) {
+        self.0.borrow_mut().set_clipboard_text(value)
+    }
+}
+
+pub(crate) unsafe extern "C" fn open_in_shell(
+    _ctx: *mut sys::ImGuiContext,
+    path: *const c_char,
+) -> bool {
+    let result = catch_unwind(|| {
+        let user_data = unsafe { (*sys::igGetPlatformIO()).Platform_OpenInShellUserData };
+
+        let ctx = &*(user_data as *const PlatformServicesContext);
+        let url = CStr::from_ptr(path).to_string_lossy();
+        let opened = ctx.services.borrow_mut().open_url(&url);
+        opened
+    });
+    result.unwrap_or_else(|_| {
+        eprintln!("URL opener panicked");
+        process::abort();
+    })
+}
+
+pub(crate) unsafe extern "C" fn set_ime_data(
+    _ctx: *mut sys::ImGuiContext,
+    _viewport: *mut sys::ImGuiViewport,
+    data: *mut sys::ImGuiPlatformImeData,
+) {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let user_data = unsafe { (*sys::igGetPlatformIO()).Platform_ImeUserData };
+
+        let ctx = &*(user_data as *const PlatformServicesContext);
+        let data = &*data;
+        ctx.services.borrow_mut().set_ime_data(&PlatformImeData {
+            want_visible: data.WantVisible,
+            input_pos: data.InputPos.into(),
+            input_line_height: data.InputLineHeight,
+        });
+    }));
+    result.unwrap_or_else(|_| {
+        eprintln!("IME data setter panicked");
+        process::abort();
+    });
+}
+
+/// # Platform services
+impl Ui {
+    /// Opens `url` using the platform, the same way [`Ui::text_link_open_url`] does when clicked.
+    ///
+    /// Returns false if the URL couldn't be opened, or if the platform can't open URLs.
+    pub fn open_url(&self, url: impl AsRef<str>) -> bool {
+        let platform_io = unsafe { sys::igGetPlatformIO() };
+        match unsafe { (*platform_io).Platform_OpenInShellFn } {
+            Some(open_in_shell_fn) => unsafe {
+                open_in_shell_fn(sys::igGetCurrentContext(), self.scratch_txt(url))
+            },
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Default)]
+struct Recorder {
+    urls: Vec<String>,
+    ime: Vec<PlatformImeData>,
+    clipboard: Option<String>,
+}
+
+#[cfg(test)]
+struct MockServices(Rc<RefCell<Recorder>>);
+
+#[cfg(test)]
+impl PlatformServices for MockServices {
+    fn set_ime_data(&mut self, data: &PlatformImeData) {
+        self.0.borrow_mut().ime.push(*data);
+    }
+    fn open_url(&mut self, url: &str) -> bool {
+        self.0.borrow_mut().urls.push(url.to_owned());
+        true
+    }
+    fn clipboard_text(&mut self) -> Option<String> {
+        self.0.borrow().clipboard.clone()
+    }
+    fn set_clipboard_text(&mut self, text: &str) {
+        self.0.borrow_mut().clipboard = Some(text.to_owned());
+    }
+}
+
+#[test]
+fn test_platform_services() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    ctx.set_platform_services(MockServices(recorder.clone()));
+
+    let ui = ctx.new_frame();
+    assert!(ui.open_url("https://github.com/imgui-rs/imgui-rs"));
+    ui.set_clipboard_text("copied");
+    assert_eq!(ui.clipboard_text().as_deref(), Some("copied"));
+    ctx.render();
+    assert_eq!(
+        recorder.borrow().urls,
+        vec!["https://github.com/imgui-rs/imgui-rs"]
+    );
+    assert_eq!(recorder.borrow().clipboard.as_deref(), Some("copied"));
+
+    let ime = unsafe { (*sys::igGetPlatformIO()).Platform_SetImeDataFn.unwrap() };
+    let mut data = sys::ImGuiPlatformImeData {
+        WantVisible: true,
+        InputPos: sys::ImVec2::new(10.0, 20.0),
+        InputLineHeight: 13.0,
+    };
+    unsafe { ime(sys::igGetCurrentContext(), std::ptr::null_mut(), &mut data) };
+    assert_eq!(
+        recorder.borrow().ime,
+        vec![PlatformImeData {
+            want_visible: true,
+            input_pos: [10.0, 20.0],
+            input_line_height: 13.0,
+        }]
+    );
+}
+
+#[test]
+fn test_text_link_open_url() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    ctx.set_platform_services(MockServices(recorder.clone()));
+    let frame = |ctx: &mut crate::Context| {
+        crate::test::test_frame(ctx, [300.0, 100.0], |ui| {
+            ui.text_link_open_url("imgui-rs", "https://github.com/imgui-rs/imgui-rs");
+            let [min_x, min_y] = ui.item_rect_min();
+            let [max_x, max_y] = ui.item_rect_max();
+            [(min_x + max_x) / 2.0, (min_y + max_y) / 2.0]
+        })
+    };
+
+    let link_pos = frame(&mut ctx);
+    crate::test::click(&mut ctx, link_pos, frame);
+    assert_eq!(
+        recorder.borrow().urls,
+        vec!["https://github.com/imgui-rs/imgui-rs"]
+    );
+}
@@ -1,12 +1,12 @@
-use std::os::raw::c_float;
-use std::{f32, mem};
+use std::f32;
 
 use super::Ui;
+use crate::plotlines::{plot, PlotValues};
 
 #[must_use]
 pub struct PlotHistogram<'ui, 'p, Label, Overlay = &'static str> {
     label: Label,
-    values: &'p [f32],
+    values: PlotValues<'p>,
     values_offset: usize,
     overlay_text: Option<Overlay>,
     scale_min: f32,
@@ -17,6 +17,29 @@ pub struct PlotHistogram<'ui, 'p, Label, Overlay = &'static str> {
 
 impl<'ui, 'p, Label: AsRef<str>> PlotHistogram<'ui, 'p, Label> {
     pub fn new(ui: &'ui Ui, label: Label, values: &'p [f32]) -> Self {
+        Self::with_values(ui, label, PlotValues::Slice(values))
+    }
+
+    /// Plots `count` values returned by `getter` for the indices `0..count`, e.g. from a
+    /// `VecDeque` or a field of a slice of structs, without copying them.
+    pub fn from_fn(
+        ui: &'ui Ui,
+        label: Label,
+        count: usize,
+        getter: impl FnMut(usize) -> f32 + 'p,
+    ) -> Self {
+        Self::with_values(ui, label, PlotValues::Getter(count, Box::new(getter)))
+    }
+
+    /// Plots the values of a ring buffer, from the oldest value at `start` to the newest one
+    /// before it.
+    ///
+    /// This is the same as [`new`](Self::new) with [`values_offset`](Self::values_offset).
+    pub fn from_ring_buffer(ui: &'ui Ui, label: Label, values: &'p [f32], start: usize) -> Self {
+        Self::new(ui, label, values).values_offset(start)
+    }
+
+    fn with_values(ui: &'ui Ui, label: Label, values: PlotValues<'p>) -> Self {
         PlotHistogram {
             label,
             values,
@@ -31,6 +54,8 @@ impl<'ui, 'p, Label: AsRef<str>> PlotHistogram<'ui, 'p, Label> {
 }
 
 impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Label, Overlay> {
+    /// Sets the index of the first value to plot, the values before it being plotted after the
+    /// last one
     pub fn values_offset(mut self, values_offset: usize) -> Self {
         self.values_offset = values_offset;
         self
@@ -67,21 +92,25 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Lab
         self
     }
 
+    /// Builds the plot.
     pub fn build(self) {
-        unsafe {
-            let (label, overlay_text) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
+        self.build_hovered();
+    }
 
-            sys::igPlotHistogram_FloatPtr(
-                label,
-                self.values.as_ptr() as *const c_float,
-                self.values.len() as i32,
-                self.values_offset as i32,
-                overlay_text,
-                self.scale_min,
-                self.scale_max,
-                self.graph_size.into(),
-                mem::size_of::<f32>() as i32,
-            );
-        }
+    /// Builds the plot, returning the index of the hovered value, if any.
+    ///
+    /// The index is the one of the value in the slice, or the one passed to the getter of
+    /// [`from_fn`](Self::from_fn), even with a [`values_offset`](Self::values_offset).
+    pub fn build_hovered(self) -> Option<usize> {
+        let (label, overlay_text) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
+        plot(
+            sys::ImGuiPlotType_Histogram,
+            label,
+            overlay_text,
+            self.values,
+            self.values_offset,
+            [self.scale_min, self.scale_max],
+            self.graph_size,
+        )
     }
 }
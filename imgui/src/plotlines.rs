@@ -1,12 +1,14 @@
-use std::os::raw::c_float;
-use std::{f32, mem};
+use std::f32;
+use std::os::raw::{c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::process;
 
 use super::Ui;
 
 #[must_use]
 pub struct PlotLines<'ui, 'p, Label, Overlay = &'static str> {
     label: Label,
-    values: &'p [f32],
+    values: PlotValues<'p>,
     values_offset: usize,
     overlay_text: Option<Overlay>,
     scale_min: f32,
@@ -17,6 +19,43 @@ pub struct PlotLines<'ui, 'p, Label, Overlay = &'static str> {
 
 impl<'ui, 'p, Label: AsRef<str>> PlotLines<'ui, 'p, Label> {
     pub fn new(ui: &'ui Ui, label: Label, values: &'p [f32]) -> Self {
+        Self::with_values(ui, label, PlotValues::Slice(values))
+    }
+
+    /// Plots `count` values returned by `getter` for the indices `0..count`, e.g. from a
+    /// `VecDeque` or a field of a slice of structs, without copying them.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # use std::collections::VecDeque;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// let frame_times: VecDeque<f32> = VecDeque::from([16.6, 16.7, 33.3, 16.6]);
+    /// let hovered = PlotLines::from_fn(ui, "frame times", frame_times.len(), |i| frame_times[i])
+    ///     .scale_min(0.0)
+    ///     .build_hovered();
+    /// if let Some(index) = hovered {
+    ///     println!("frame {} took {}ms", index, frame_times[index]);
+    /// }
+    /// ```
+    pub fn from_fn(
+        ui: &'ui Ui,
+        label: Label,
+        count: usize,
+        getter: impl FnMut(usize) -> f32 + 'p,
+    ) -> Self {
+        Self::with_values(ui, label, PlotValues::Getter(count, Box::new(getter)))
+    }
+
+    /// Plots the values of a ring buffer, from the oldest value at `start` to the newest one
+    /// before it.
+    ///
+    /// This is the same as [`new`](Self::new) with [`values_offset`](Self::values_offset).
+    pub fn from_ring_buffer(ui: &'ui Ui, label: Label, values: &'p [f32], start: usize) -> Self {
+        Self::new(ui, label, values).values_offset(start)
+    }
+
+    fn with_values(ui: &'ui Ui, label: Label, values: PlotValues<'p>) -> Self {
         PlotLines {
             label,
             values,
@@ -31,6 +70,8 @@ impl<'ui, 'p, Label: AsRef<str>> PlotLines<'ui, 'p, Label> {
 }
 
 impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label, Overlay> {
+    /// Sets the index of the first value to plot, the values before it being plotted after the
+    /// last one
     pub fn values_offset(mut self, values_offset: usize) -> Self {
         self.values_offset = values_offset;
         self
@@ -67,21 +108,116 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label,
         self
     }
 
+    /// Builds the plot.
     pub fn build(self) {
-        unsafe {
-            let (label, overlay) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
-
-            sys::igPlotLines_FloatPtr(
-                label,
-                self.values.as_ptr() as *const c_float,
-                self.values.len() as i32,
-                self.values_offset as i32,
-                overlay,
-                self.scale_min,
-                self.scale_max,
-                self.graph_size.into(),
-                mem::size_of::<f32>() as i32,
-            );
-        }
+        self.build_hovered();
+    }
+
+    /// Builds the plot, returning the index of the hovered value, if any.
+    ///
+    /// The index is the one of the value in the slice, or the one passed to the getter of
+    /// [`from_fn`](Self::from_fn), even with a [`values_offset`](Self::values_offset).
+    pub fn build_hovered(self) -> Option<usize> {
+        let (label, overlay) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
+        plot(
+            sys::ImGuiPlotType_Lines,
+            label,
+            overlay,
+            self.values,
+            self.values_offset,
+            [self.scale_min, self.scale_max],
+            self.graph_size,
+        )
     }
 }
+
+/// The values of a [`PlotLines`] or [`PlotHistogram`](crate::PlotHistogram)
+pub(crate) enum PlotValues<'p> {
+    Slice(&'p [f32]),
+    /// Number of values and the function returning them by index
+    Getter(usize, Box<dyn FnMut(usize) -> f32 + 'p>),
+}
+
+/// Draws a plot with `igPlotEx`, which unlike the public functions returns the hovered index
+pub(crate) fn plot(
+    plot_type: sys::ImGuiPlotType,
+    label: *const std::os::raw::c_char,
+    overlay: *const std::os::raw::c_char,
+    values: PlotValues<'_>,
+    values_offset: usize,
+    [scale_min, scale_max]: [f32; 2],
+    graph_size: [f32; 2],
+) -> Option<usize> {
+    unsafe extern "C" fn get_value(data: *mut c_void, index: c_int) -> f32 {
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let getter = &mut *(data as *mut &mut dyn FnMut(usize) -> f32);
+            getter(index as usize)
+        }));
+        result.unwrap_or_else(|_| {
+            eprintln!("Plot value getter panicked");
+            process::abort();
+        })
+    }
+
+    let mut values = values;
+    let mut slice_getter;
+    let (count, mut getter): (usize, &mut dyn FnMut(usize) -> f32) = match &mut values {
+        PlotValues::Slice(values) => {
+            let values = *values;
+            slice_getter = move |index: usize| values[index];
+            (values.len(), &mut slice_getter)
+        }
+        PlotValues::Getter(count, getter) => (*count, getter.as_mut()),
+    };
+    let hovered = unsafe {
+        sys::igPlotEx(
+            plot_type,
+            label,
+            Some(get_value),
+            &mut getter as *mut _ as *mut c_void,
+            count as c_int,
+            values_offset as c_int,
+            overlay,
+            scale_min,
+            scale_max,
+            graph_size.into(),
+        )
+    };
+    // Dear ImGui returns the position in plot order, starting at the offset
+    usize::try_from(hovered)
+        .ok()
+        .map(|hovered| (hovered + values_offset) % count)
+}
+
+#[test]
+fn test_plot_lines_hovered_index() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let ring = [5.0, 6.0, 7.0, 8.0, 1.0, 2.0, 3.0, 4.0];
+    let frame = |ctx: &mut crate::Context, mouse: [f32; 2]| {
+        ctx.io_mut().mouse_pos = mouse;
+        let mut seen = Vec::new();
+        let hovered = crate::test::test_frame(ctx, [400.0, 300.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 10.0]);
+            let plot = PlotLines::from_ring_buffer(ui, "##ring", &ring, 4);
+            let ring_hovered = plot.graph_size([200.0, 50.0]).build_hovered();
+            ui.set_cursor_screen_pos([10.0, 100.0]);
+            let plot = PlotLines::from_fn(ui, "##fn", 4, |i| {
+                seen.push(i);
+                i as f32
+            });
+            (ring_hovered, plot.graph_size([200.0, 50.0]).build_hovered())
+        });
+        (hovered, seen)
+    };
+
+    frame(&mut ctx, [-100.0, -100.0]);
+    let ((ring_hovered, fn_hovered), seen) = frame(&mut ctx, [-100.0, -100.0]);
+    assert_eq!((ring_hovered, fn_hovered), (None, None));
+    assert!(seen.iter().all(|&i| i < 4));
+    // The hovered index is the one of the value in the ring buffer
+    let ((ring_hovered, _), _) = frame(&mut ctx, [200.0, 35.0]);
+    assert_eq!(ring_hovered.map(|i| ring[i]), Some(7.0));
+    frame(&mut ctx, [20.0, 125.0]);
+    let ((_, fn_hovered), _) = frame(&mut ctx, [20.0, 125.0]);
+    assert_eq!(fn_hovered, Some(0));
+}
@@ -179,6 +179,33 @@ impl Ui {
         }
     }
 
+    /// Construct a popup with the given window flags, e.g.
+    /// [`WindowFlags::NO_FOCUS_ON_APPEARING`] to keep the keyboard focus in the widget that
+    /// opened it.
+    ///
+    /// This should be called *per frame*, whereas [`open_popup`](Self::open_popup) should be called *once*
+    /// to signal that this popup is active.
+    #[doc(alias = "BeginPopup")]
+    pub fn begin_popup_with_flags(
+        &self,
+        str_id: impl AsRef<str>,
+        flags: WindowFlags,
+    ) -> Option<PopupToken<'_>> {
+        let render = unsafe { sys::igBeginPopup(self.scratch_txt(str_id), flags.bits() as i32) };
+
+        if render {
+            Some(PopupToken::new(self))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the popup is open at the current level of the popup stack.
+    #[doc(alias = "IsPopupOpen")]
+    pub fn is_popup_open(&self, str_id: impl AsRef<str>) -> bool {
+        unsafe { sys::igIsPopupOpen_Str(self.scratch_txt(str_id), 0) }
+    }
+
     /// Construct a popup that can have any kind of content.
     ///
     /// This should be called *per frame*, whereas [`open_popup`](Self::open_popup) should be called *once*
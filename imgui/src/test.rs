@@ -2,6 +2,7 @@ use parking_lot::{ReentrantMutex, ReentrantMutexGuard};
 use std::ptr;
 
 use crate::context::Context;
+use crate::{Condition, Key, MouseButton, Ui};
 
 pub static TEST_MUTEX: ReentrantMutex<()> = parking_lot::const_reentrant_mutex(());
 
@@ -21,3 +22,65 @@ pub fn test_ctx_initialized() -> (ReentrantMutexGuard<'static, ()>, Context) {
     ctx.fonts().build_rgba32_texture();
     (guard, ctx)
 }
+
+/// Like [`test_ctx_initialized`], but all the input events queued before a frame are processed in
+/// that frame, so that tests can simulate input one step per frame
+pub fn test_ctx_input() -> (ReentrantMutexGuard<'static, ()>, Context) {
+    let (guard, mut ctx) = test_ctx_initialized();
+    ctx.io_mut().config_input_trickle_event_queue = false;
+    (guard, ctx)
+}
+
+/// Runs a frame with a window of the given size at the origin of the screen, in which `build`
+/// adds the items under test. Items below y = 40 are clear of the title bar.
+pub fn test_frame<R>(ctx: &mut Context, size: [f32; 2], build: impl FnOnce(&Ui) -> R) -> R {
+    let ui = ctx.new_frame();
+    let result = ui
+        .window("test")
+        .position([0.0, 0.0], Condition::Always)
+        .size(size, Condition::Always)
+        .build(|| build(ui))
+        .expect("the test window is visible");
+    ctx.render();
+    result
+}
+
+/// Moves the mouse to `pos` and presses the left button, running `frame` after each step.
+/// Returns what the last frame returns.
+pub fn press<R>(ctx: &mut Context, pos: [f32; 2], mut frame: impl FnMut(&mut Context) -> R) -> R {
+    ctx.io_mut().mouse_pos = pos;
+    frame(ctx);
+    ctx.io_mut().add_mouse_button_event(MouseButton::Left, true);
+    frame(ctx)
+}
+
+/// Releases the left mouse button and runs `frame`, returning what it returns
+pub fn release<R>(ctx: &mut Context, mut frame: impl FnMut(&mut Context) -> R) -> R {
+    ctx.io_mut()
+        .add_mouse_button_event(MouseButton::Left, false);
+    frame(ctx)
+}
+
+/// Clicks the left mouse button at `pos`, running `frame` after each step. Returns what the
+/// frame in which the button is released returns.
+pub fn click<R>(ctx: &mut Context, pos: [f32; 2], mut frame: impl FnMut(&mut Context) -> R) -> R {
+    press(ctx, pos, &mut frame);
+    release(ctx, frame)
+}
+
+/// Presses and releases each key in turn, running `frame` after each step. Returns what the last
+/// frame returns.
+pub fn press_keys<R>(
+    ctx: &mut Context,
+    keys: &[Key],
+    mut frame: impl FnMut(&mut Context) -> R,
+) -> R {
+    let mut result = None;
+    for &key in keys {
+        ctx.io_mut().add_key_event(key, true);
+        frame(ctx);
+        ctx.io_mut().add_key_event(key, false);
+        result = Some(frame(ctx));
+    }
+    result.expect("at least one key is pressed")
+}
@@ -0,0 +1,302 @@
+//! Typing-select, a.k.a. type-ahead find: typing the start of an item's label while a list or
+//! tree is focused moves to that item.
+//!
+//! Two search modes are supported:
+//!
+//! - typing several characters searches for the item with the longest matching prefix, e.g.
+//!   typing "do" in a file list moves to "documents";
+//! - with [`TypingSelectFlags::ALLOW_SINGLE_CHAR_MODE`], typing the same character repeatedly
+//!   cycles through the items starting with that character, e.g. "d", "d", "d" moves to
+//!   "desktop", then "documents", then "downloads".
+//!
+//! Matching is case-insensitive for ASCII characters. The typed text is cleared after a short
+//! delay, when the focus moves, or when escape or enter is pressed.
+
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::{process, slice, str};
+
+use bitflags::bitflags;
+
+use crate::sys;
+use crate::Ui;
+
+bitflags! {
+    /// Flags for typing-select
+    #[repr(transparent)]
+    pub struct TypingSelectFlags: u32 {
+        /// Backspace deletes the last typed character instead of clearing the search.
+        ///
+        /// When using this, make sure the request is only made once per frame, e.g. only for
+        /// the focused window.
+        const ALLOW_BACKSPACE = sys::ImGuiTypingSelectFlags_AllowBackspace;
+        /// Typing the same character repeatedly cycles through the items starting with it
+        const ALLOW_SINGLE_CHAR_MODE = sys::ImGuiTypingSelectFlags_AllowSingleCharMode;
+    }
+}
+
+/// Builder for a typing-select search over a list of items.
+///
+/// This consumes the characters typed this frame, so only use it for the list that should
+/// receive them, typically when its window is focused.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let files = ["desktop", "documents", "downloads", "music", "pictures"];
+/// let mut selected = 0;
+/// ui.window("Files").build(|| {
+///     let mut found = None;
+///     if ui.is_window_focused() {
+///         found = TypingSelect::new(files.len())
+///             .flags(TypingSelectFlags::ALLOW_SINGLE_CHAR_MODE)
+///             .nav_item(Some(selected))
+///             .find(ui, |index| files[index]);
+///     }
+///     for (index, file) in files.iter().enumerate() {
+///         if found == Some(index) {
+///             selected = index;
+///             ui.set_keyboard_focus_here();
+///         }
+///         if ui.selectable_config(file).selected(index == selected).build() {
+///             selected = index;
+///         }
+///         if found == Some(index) {
+///             ui.set_scroll_here_y();
+///         }
+///     }
+/// });
+/// ```
+///
+/// When the list is clipped with a [`ListClipper`](crate::ListClipper), use
+/// `ListClipperToken::include_item_by_index` so that the found item gets submitted.
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct TypingSelect {
+    items_count: usize,
+    flags: TypingSelectFlags,
+    nav_item: Option<usize>,
+}
+
+impl TypingSelect {
+    /// Creates a typing-select search over `items_count` items
+    pub fn new(items_count: usize) -> Self {
+        TypingSelect {
+            items_count,
+            flags: TypingSelectFlags::empty(),
+            nav_item: None,
+        }
+    }
+    /// Replaces the typing-select flags
+    pub fn flags(mut self, flags: TypingSelectFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    /// Sets the index of the currently focused or selected item.
+    ///
+    /// In single character mode, the search continues after this item. Without it, the search
+    /// always returns the first item starting with the typed character.
+    pub fn nav_item(mut self, index: Option<usize>) -> Self {
+        self.nav_item = index;
+        self
+    }
+    /// Consumes the typed characters and returns the index of the item to focus and scroll to,
+    /// if the user typed something this frame and an item matches.
+    ///
+    /// `label` returns the label of the item at the given index.
+    pub fn find<F, S>(&self, ui: &Ui, label: F) -> Option<usize>
+    where
+        F: FnMut(usize) -> S,
+        S: AsRef<str>,
+    {
+        ui.typing_select_request(self.flags)?
+            .find_match(self.items_count, self.nav_item, label)
+    }
+}
+
+/// A typing-select request, i.e. the text typed so far by the user.
+///
+/// Returned by [`Ui::typing_select_request`].
+#[derive(Debug)]
+pub struct TypingSelectRequest<'ui> {
+    raw: *mut sys::ImGuiTypingSelectRequest,
+    _phantom: PhantomData<&'ui Ui>,
+}
+
+impl TypingSelectRequest<'_> {
+    /// Returns the text typed so far, e.g. to display it in a tooltip.
+    ///
+    /// It's better not to display it in single character mode.
+    pub fn search(&self) -> &str {
+        let bytes = unsafe {
+            let raw = &*self.raw;
+            slice::from_raw_parts(raw.SearchBuffer as *const u8, raw.SearchBufferLen as usize)
+        };
+        match str::from_utf8(bytes) {
+            Ok(search) => search,
+            // Unpaired surrogates are encoded as is by Dear ImGui
+            Err(e) => unsafe { str::from_utf8_unchecked(&bytes[..e.valid_up_to()]) },
+        }
+    }
+    /// Returns true if characters were typed this frame, i.e. a new item should be selected
+    pub fn select_request(&self) -> bool {
+        unsafe { (*self.raw).SelectRequest }
+    }
+    /// Returns true if the search is in single character mode, i.e. the same character was
+    /// typed repeatedly
+    pub fn single_char_mode(&self) -> bool {
+        unsafe { (*self.raw).SingleCharMode }
+    }
+    /// Returns the index of the item matching the request, if characters were typed this frame
+    /// and an item matches.
+    ///
+    /// `nav_item` is the index of the currently focused or selected item, `label` returns the
+    /// label of the item at the given index.
+    #[doc(alias = "TypingSelectFindMatch")]
+    pub fn find_match<F, S>(
+        &self,
+        items_count: usize,
+        nav_item: Option<usize>,
+        label: F,
+    ) -> Option<usize>
+    where
+        F: FnMut(usize) -> S,
+        S: AsRef<str>,
+    {
+        let mut getter = LabelGetter {
+            label,
+            buf: Vec::new(),
+        };
+        let nav_item_idx = nav_item.map_or(-1, |index| index.min(i32::MAX as usize) as c_int);
+        let index = unsafe {
+            sys::igTypingSelectFindMatch(
+                self.raw,
+                items_count.min(i32::MAX as usize) as c_int,
+                Some(item_name::<F, S>),
+                &mut getter as *mut LabelGetter<F> as *mut c_void,
+                nav_item_idx,
+            )
+        };
+        usize::try_from(index).ok()
+    }
+}
+
+struct LabelGetter<F> {
+    label: F,
+    buf: Vec<u8>,
+}
+
+unsafe extern "C" fn item_name<F, S>(user_data: *mut c_void, index: c_int) -> *const c_char
+where
+    F: FnMut(usize) -> S,
+    S: AsRef<str>,
+{
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let getter = &mut *(user_data as *mut LabelGetter<F>);
+        let label = (getter.label)(index as usize);
+        getter.buf.clear();
+        getter.buf.extend_from_slice(label.as_ref().as_bytes());
+        getter.buf.push(b'\0');
+        getter.buf.as_ptr() as *const c_char
+    }));
+    result.unwrap_or_else(|_| {
+        eprintln!("Typing-select label getter panicked");
+        process::abort();
+    })
+}
+
+/// # Typing-select
+impl Ui {
+    /// Consumes the characters typed this frame and returns the typing-select request, or None
+    /// if nothing has been typed recently.
+    ///
+    /// Only call this for the part of the UI that should receive the typed characters, e.g.
+    /// when its window is focused. See [`TypingSelect`] for a simpler interface.
+    #[doc(alias = "GetTypingSelectRequest")]
+    pub fn typing_select_request(
+        &self,
+        flags: TypingSelectFlags,
+    ) -> Option<TypingSelectRequest<'_>> {
+        let raw = unsafe { sys::igGetTypingSelectRequest(flags.bits() as i32) };
+        if raw.is_null() {
+            None
+        } else {
+            Some(TypingSelectRequest {
+                raw,
+                _phantom: PhantomData,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+const FILES: [&str; 5] = ["Desktop", "documents", "Downloads", "music", "pictures"];
+
+#[cfg(test)]
+fn type_and_find(
+    ctx: &mut crate::Context,
+    chars: &str,
+    flags: TypingSelectFlags,
+    nav_item: Option<usize>,
+) -> (Option<usize>, String) {
+    for c in chars.chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    let ui = ctx.new_frame();
+    let mut result = (None, String::new());
+    ui.window("files").build(|| {
+        if let Some(request) = ui.typing_select_request(flags) {
+            result.1 = request.search().to_owned();
+        }
+        result.0 = TypingSelect::new(FILES.len())
+            .flags(flags)
+            .nav_item(nav_item)
+            .find(ui, |index| FILES[index]);
+    });
+    ctx.render();
+    result
+}
+
+#[test]
+fn test_typing_select_prefix() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let flags = TypingSelectFlags::ALLOW_SINGLE_CHAR_MODE;
+
+    assert_eq!(
+        type_and_find(&mut ctx, "", flags, None),
+        (None, String::new())
+    );
+    assert_eq!(
+        type_and_find(&mut ctx, "dow", flags, None),
+        (Some(2), "dow".to_owned())
+    );
+    // Nothing typed this frame: the search is kept, but there's nothing to select
+    assert_eq!(
+        type_and_find(&mut ctx, "", flags, None),
+        (None, "dow".to_owned())
+    );
+    // Longest matching prefix
+    assert_eq!(
+        type_and_find(&mut ctx, "x", flags, None),
+        (Some(2), "dowx".to_owned())
+    );
+}
+
+#[test]
+fn test_typing_select_single_char() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let flags = TypingSelectFlags::ALLOW_SINGLE_CHAR_MODE;
+
+    type_and_find(&mut ctx, "", flags, None);
+    let mut nav_item = None;
+    let mut found = Vec::new();
+    for _ in 0..4 {
+        nav_item = type_and_find(&mut ctx, "D", flags, nav_item).0;
+        found.push(nav_item);
+    }
+    assert_eq!(found, [Some(0), Some(1), Some(2), Some(0)]);
+}
@@ -0,0 +1,506 @@
+//! Text input with an autocomplete dropdown.
+
+use crate::sys;
+use crate::{
+    HistoryDirection, Id, InputFlags, InputText, InputTextCallback, InputTextCallbackHandler,
+    InputTextFlags, Key, StyleColor, TextCallbackData, Ui, WindowFlags,
+};
+
+const POPUP_ID: &str = "##autocomplete";
+const HIGHLIGHT_ID: &str = "##autocomplete_highlight";
+const FLAGS_ID: &str = "##autocomplete_flags";
+
+/// Builder for a single line text input showing a dropdown of candidates below it as the user
+/// types.
+///
+/// The candidates are returned by a closure, given the current text. While the dropdown is
+/// shown, the up and down arrows highlight a candidate, Enter replaces the text with the
+/// highlighted candidate and Escape dismisses the dropdown. Candidates can also be clicked.
+/// The keyboard focus stays in the text field.
+///
+/// Typed characters found in a candidate, in order and ignoring ASCII case, are highlighted.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let commands = ["build", "bench", "check", "clean", "clippy", "doc", "test"];
+/// let mut command = String::new();
+/// ui.input_text_autocomplete("Command", &mut command)
+///     .hint("cargo ...")
+///     .build(|text| {
+///         commands
+///             .into_iter()
+///             .filter(|command| !text.is_empty() && command.starts_with(text))
+///             .collect::<Vec<_>>()
+///     });
+/// ```
+#[must_use]
+pub struct AutocompleteInput<'ui, 'p, L, H = &'static str> {
+    label: L,
+    hint: Option<H>,
+    buf: &'p mut String,
+    flags: InputTextFlags,
+    max_visible_items: usize,
+    ui: &'ui Ui,
+}
+
+impl<'ui, 'p, L: AsRef<str>> AutocompleteInput<'ui, 'p, L> {
+    /// Creates a new text input with an autocomplete dropdown, editing the given string.
+    pub fn new(ui: &'ui Ui, label: L, buf: &'p mut String) -> Self {
+        AutocompleteInput {
+            label,
+            hint: None,
+            buf,
+            flags: InputTextFlags::empty(),
+            max_visible_items: 8,
+            ui,
+        }
+    }
+}
+
+impl<'ui, 'p, L: AsRef<str>, H: AsRef<str>> AutocompleteInput<'ui, 'p, L, H> {
+    /// Sets the hint displayed in the input text background.
+    pub fn hint<H2: AsRef<str>>(self, hint: H2) -> AutocompleteInput<'ui, 'p, L, H2> {
+        AutocompleteInput {
+            label: self.label,
+            hint: Some(hint),
+            buf: self.buf,
+            flags: self.flags,
+            max_visible_items: self.max_visible_items,
+            ui: self.ui,
+        }
+    }
+    /// Replaces the input text flags.
+    ///
+    /// The callback flags are set by the widget. Multiline flags aren't supported.
+    pub fn flags(mut self, flags: InputTextFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    /// Sets the number of candidates visible without scrolling. Defaults to 8.
+    pub fn max_visible_items(mut self, max_visible_items: usize) -> Self {
+        self.max_visible_items = max_visible_items.max(1);
+        self
+    }
+    /// Builds the text input and its dropdown.
+    ///
+    /// `candidates` returns the candidates for the given text. It's only called while the
+    /// text input or its dropdown is active. The dropdown is hidden when there are no
+    /// candidates.
+    ///
+    /// Returns true if the text was changed, either by typing or by accepting a candidate.
+    pub fn build<F, I, S>(self, mut candidates: F) -> bool
+    where
+        F: FnMut(&str) -> I,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let ui = self.ui;
+        let label = self.label.as_ref();
+        let mut collect = |text: &str| -> Vec<String> {
+            candidates(text)
+                .into_iter()
+                .map(|candidate| candidate.as_ref().to_owned())
+                .collect()
+        };
+
+        let (popup_id, state_ids, popup_open) = {
+            let _id = ui.push_id(label);
+            let state_ids = [ui.new_id_str(HIGHLIGHT_ID), ui.new_id_str(FLAGS_ID)];
+            (
+                ui.new_id_str(POPUP_ID),
+                state_ids,
+                ui.is_popup_open(POPUP_ID),
+            )
+        };
+        let mut state = State::load(state_ids);
+        let mut list = if popup_open {
+            collect(self.buf)
+        } else {
+            Vec::new()
+        };
+        if state.highlight.is_some_and(|index| index >= list.len()) {
+            state.highlight = None;
+        }
+
+        // Keys handled by the dropdown, before the text input sees them
+        let mut accepted = None;
+        if popup_open && state.active && !state.dismissed {
+            if ui.is_key_pressed(Key::Escape) {
+                state.dismissed = true;
+                ui.set_key_owner(Key::Escape, popup_id, InputFlags::LOCK_THIS_FRAME);
+            } else if ui.is_key_pressed(Key::Enter) || ui.is_key_pressed(Key::KeypadEnter) {
+                accepted = state.highlight.map(|index| list[index].clone());
+            }
+        }
+        if state.refocus {
+            // A candidate was clicked, which took the focus away from the text input
+            ui.set_keyboard_focus_here();
+            state.refocus = false;
+            state.cursor_to_end = true;
+        }
+
+        let mut handler = Handler {
+            highlight: state.highlight,
+            candidates_count: if popup_open && !state.dismissed {
+                list.len()
+            } else {
+                0
+            },
+            accepted: accepted.as_deref(),
+            cursor_to_end: state.cursor_to_end,
+            highlight_moved: false,
+        };
+        let mut callbacks = InputTextCallback::ALWAYS;
+        if handler.candidates_count > 0 {
+            callbacks |= InputTextCallback::HISTORY;
+        }
+        let mut changed = {
+            let input = InputText::new(ui, label, self.buf)
+                .flags(self.flags | InputTextFlags::CALLBACK_RESIZE)
+                .callback(callbacks, &mut handler);
+            // Accepting a candidate with Enter keeps the text input active. Dear ImGui has no
+            // per-item flag for it, so the global option is set for this item only
+            let _keep_active = accepted.is_some().then(EnterKeepActive::set);
+            match self.hint {
+                Some(hint) => input.hint(hint).build(),
+                None => input.build(),
+            }
+        };
+        let highlight_moved = handler.highlight_moved;
+        state.highlight = handler.highlight;
+        state.cursor_to_end = handler.cursor_to_end;
+        state.active = ui.is_item_active();
+        if ui.is_item_activated() || (changed && accepted.is_none()) {
+            state.dismissed = false;
+            state.highlight = None;
+        }
+        if accepted.is_some() {
+            state.dismissed = true;
+            state.highlight = None;
+        }
+        if changed || (!popup_open && state.active && !state.dismissed) {
+            list = collect(self.buf);
+        }
+
+        let [min_x, _] = ui.item_rect_min();
+        let [max_x, max_y] = ui.item_rect_max();
+        let show = state.active && !state.dismissed && !list.is_empty();
+        let _id = ui.push_id(label);
+        if show && !popup_open {
+            ui.open_popup(POPUP_ID);
+        }
+        if show || popup_open {
+            let window_padding = unsafe { (*sys::igGetStyle()).WindowPadding };
+            let max_height = ui.text_line_height_with_spacing() * self.max_visible_items as f32
+                + window_padding.y * 2.0;
+            unsafe {
+                sys::igSetNextWindowPos(
+                    sys::ImVec2::new(min_x, max_y),
+                    crate::Condition::Always as i32,
+                    sys::ImVec2::zero(),
+                );
+                sys::igSetNextWindowSizeConstraints(
+                    sys::ImVec2::new(max_x - min_x, 0.0),
+                    sys::ImVec2::new(max_x - min_x, max_height),
+                    None,
+                    std::ptr::null_mut(),
+                );
+            }
+            let flags = WindowFlags::NO_TITLE_BAR
+                | WindowFlags::NO_MOVE
+                | WindowFlags::NO_RESIZE
+                | WindowFlags::NO_SAVED_SETTINGS
+                | WindowFlags::NO_FOCUS_ON_APPEARING
+                | WindowFlags::NO_NAV
+                | WindowFlags::ALWAYS_AUTO_RESIZE;
+            if let Some(_popup) = ui.begin_popup_with_flags(POPUP_ID, flags) {
+                // The dropdown gets the focus while a candidate is clicked
+                if state.dismissed || list.is_empty() || !(state.active || ui.is_window_focused()) {
+                    ui.close_current_popup();
+                } else if let Some(index) =
+                    candidate_list(ui, &list, self.buf, state.highlight, highlight_moved)
+                {
+                    *self.buf = list[index].clone();
+                    changed = true;
+                    state.dismissed = true;
+                    state.highlight = None;
+                    state.refocus = true;
+                    ui.close_current_popup();
+                }
+            }
+        }
+        state.store(state_ids);
+        changed
+    }
+}
+
+/// Draws the candidates, returning the index of the clicked one
+fn candidate_list(
+    ui: &Ui,
+    list: &[String],
+    text: &str,
+    highlight: Option<usize>,
+    scroll_to_highlight: bool,
+) -> Option<usize> {
+    let text_color = ui.style_color(StyleColor::Text);
+    let match_color = ui.style_color(StyleColor::TextLink);
+    let mut clicked = None;
+    for (index, candidate) in list.iter().enumerate() {
+        let _id = ui.push_id_usize(index);
+        let [mut x, y] = ui.cursor_screen_pos();
+        let highlighted = highlight == Some(index);
+        if ui
+            .selectable_config("##candidate")
+            .selected(highlighted)
+            .build()
+        {
+            clicked = Some(index);
+        }
+        if highlighted && scroll_to_highlight {
+            ui.set_scroll_here_y();
+        }
+        let draw_list = ui.get_window_draw_list();
+        for (part, matched) in match_parts(candidate, text) {
+            let color = if matched { match_color } else { text_color };
+            draw_list.add_text([x, y], color, part);
+            x += ui.calc_text_size(part)[0];
+        }
+    }
+    clicked
+}
+
+/// Splits `candidate` into parts matching or not the characters of `text`, in order and
+/// ignoring ASCII case
+fn match_parts<'c>(candidate: &'c str, text: &str) -> Vec<(&'c str, bool)> {
+    let mut typed = text.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut parts: Vec<(&str, bool)> = Vec::new();
+    let mut part_start = 0;
+    let mut part_matched = false;
+    for (pos, c) in candidate.char_indices() {
+        let matched = typed.next_if(|t| t.eq_ignore_ascii_case(&c)).is_some();
+        if matched != part_matched && pos > part_start {
+            parts.push((&candidate[part_start..pos], part_matched));
+            part_start = pos;
+        }
+        part_matched = matched;
+    }
+    if part_start < candidate.len() {
+        parts.push((&candidate[part_start..], part_matched));
+    }
+    parts
+}
+
+/// Handles the keys and buffer changes of the text input
+struct Handler<'a> {
+    highlight: Option<usize>,
+    candidates_count: usize,
+    accepted: Option<&'a str>,
+    cursor_to_end: bool,
+    highlight_moved: bool,
+}
+
+impl InputTextCallbackHandler for &mut Handler<'_> {
+    fn on_history(&mut self, dir: HistoryDirection, _: TextCallbackData) {
+        let count = self.candidates_count;
+        self.highlight = Some(match (dir, self.highlight) {
+            (HistoryDirection::Down, Some(index)) => (index + 1) % count,
+            (HistoryDirection::Down, None) => 0,
+            (HistoryDirection::Up, Some(index)) => (index + count - 1) % count,
+            (HistoryDirection::Up, None) => count - 1,
+        });
+        self.highlight_moved = true;
+    }
+
+    fn on_always(&mut self, mut data: TextCallbackData) {
+        if let Some(accepted) = self.accepted.take() {
+            data.clear();
+            data.push_str(accepted);
+            self.cursor_to_end = true;
+        }
+        if self.cursor_to_end {
+            let end = data.str().len();
+            data.set_cursor_pos(end);
+            data.clear_selection();
+            self.cursor_to_end = false;
+        }
+    }
+}
+
+/// Persistent state of an autocomplete input, kept in Dear ImGui's state storage
+#[derive(Default)]
+struct State {
+    highlight: Option<usize>,
+    /// The text input was active last frame
+    active: bool,
+    /// The dropdown was closed with Escape or by accepting a candidate, until the next edit
+    dismissed: bool,
+    /// The text input should get the focus back
+    refocus: bool,
+    /// The text cursor should move to the end of the text
+    cursor_to_end: bool,
+}
+
+impl State {
+    const ACTIVE: i32 = 1;
+    const DISMISSED: i32 = 2;
+    const REFOCUS: i32 = 4;
+    const CURSOR_TO_END: i32 = 8;
+
+    /// Loads the state stored under the highlight and flags ids
+    fn load([highlight_id, flags_id]: [Id; 2]) -> Self {
+        let (highlight, bits) = unsafe {
+            let storage = sys::igGetStateStorage();
+            (
+                sys::ImGuiStorage_GetInt(storage, highlight_id.0, -1),
+                sys::ImGuiStorage_GetInt(storage, flags_id.0, 0),
+            )
+        };
+        State {
+            highlight: usize::try_from(highlight).ok(),
+            active: bits & Self::ACTIVE != 0,
+            dismissed: bits & Self::DISMISSED != 0,
+            refocus: bits & Self::REFOCUS != 0,
+            cursor_to_end: bits & Self::CURSOR_TO_END != 0,
+        }
+    }
+
+    fn store(&self, [highlight_id, flags_id]: [Id; 2]) {
+        let highlight = self.highlight.map_or(-1, |index| index as i32);
+        let bits = [
+            (self.active, Self::ACTIVE),
+            (self.dismissed, Self::DISMISSED),
+            (self.refocus, Self::REFOCUS),
+            (self.cursor_to_end, Self::CURSOR_TO_END),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |bits, (_, bit)| bits | bit);
+        unsafe {
+            let storage = sys::igGetStateStorage();
+            sys::ImGuiStorage_SetInt(storage, highlight_id.0, highlight);
+            sys::ImGuiStorage_SetInt(storage, flags_id.0, bits);
+        }
+    }
+}
+
+/// Sets `io.ConfigInputTextEnterKeepActive` until dropped, restoring the previous value even
+/// if the text input callback panics
+struct EnterKeepActive(bool);
+
+impl EnterKeepActive {
+    fn set() -> Self {
+        let io = unsafe { &mut *sys::igGetIO() };
+        let previous = io.ConfigInputTextEnterKeepActive;
+        io.ConfigInputTextEnterKeepActive = true;
+        EnterKeepActive(previous)
+    }
+}
+
+impl Drop for EnterKeepActive {
+    fn drop(&mut self) {
+        unsafe { (*sys::igGetIO()).ConfigInputTextEnterKeepActive = self.0 };
+    }
+}
+
+#[cfg(test)]
+const COMMANDS: [&str; 7] = ["build", "bench", "check", "clean", "clippy", "doc", "test"];
+
+/// Builds the autocomplete input, returning whether it's active and whether its dropdown
+/// is open
+#[cfg(test)]
+fn frame(ctx: &mut crate::Context, text: &mut String, focus: bool) -> (bool, bool) {
+    let ui = ctx.new_frame();
+    let mut result = (false, false);
+    ui.window("autocomplete").build(|| {
+        if focus {
+            ui.set_keyboard_focus_here();
+        }
+        ui.input_text_autocomplete("command", text).build(|text| {
+            COMMANDS
+                .into_iter()
+                .filter(|command| !text.is_empty() && command.starts_with(text))
+                .collect::<Vec<_>>()
+        });
+        result.0 = ui.is_item_active();
+        let _id = ui.push_id("command");
+        result.1 = ui.is_popup_open(POPUP_ID);
+    });
+    ctx.render();
+    result
+}
+
+#[cfg(test)]
+fn press(ctx: &mut crate::Context, text: &mut String, key: Key) -> (bool, bool) {
+    crate::test::press_keys(ctx, &[key], |ctx| frame(ctx, text, false))
+}
+
+#[cfg(test)]
+fn type_char(ctx: &mut crate::Context, text: &mut String, c: char) -> (bool, bool) {
+    ctx.io_mut().add_input_character(c);
+    frame(ctx, text, false);
+    frame(ctx, text, false)
+}
+
+#[test]
+fn test_autocomplete_keyboard() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut text = String::new();
+
+    frame(&mut ctx, &mut text, false);
+    frame(&mut ctx, &mut text, true);
+    assert_eq!(frame(&mut ctx, &mut text, false), (true, false));
+    assert_eq!(type_char(&mut ctx, &mut text, 'c'), (true, true));
+
+    // Down highlights the first candidate, Up wraps around to the last one
+    for key in [Key::DownArrow, Key::DownArrow, Key::UpArrow, Key::UpArrow] {
+        press(&mut ctx, &mut text, key);
+    }
+    press(&mut ctx, &mut text, Key::DownArrow);
+    press(&mut ctx, &mut text, Key::DownArrow);
+    assert_eq!(text, "c");
+    // Enter accepts "clean", the input stays active
+    assert_eq!(press(&mut ctx, &mut text, Key::Enter), (true, false));
+    assert_eq!(text, "clean");
+    assert!(!ctx.io().config_input_text_enter_keep_active);
+
+    // Editing shows the dropdown again, Escape closes it without reverting the text
+    for _ in 0..5 {
+        press(&mut ctx, &mut text, Key::Backspace);
+    }
+    assert_eq!(type_char(&mut ctx, &mut text, 'd'), (true, true));
+    assert_eq!(press(&mut ctx, &mut text, Key::Escape), (true, false));
+    assert_eq!(text, "d");
+    // Without a highlighted candidate, Enter validates the text as usual
+    assert_eq!(type_char(&mut ctx, &mut text, 'o'), (true, true));
+    assert_eq!(press(&mut ctx, &mut text, Key::Enter), (false, false));
+    assert_eq!(text, "do");
+}
+
+#[test]
+fn test_match_parts() {
+    assert_eq!(
+        match_parts("clippy", "cli"),
+        [("cli", true), ("ppy", false)]
+    );
+    assert_eq!(
+        match_parts("Documents", "dcm"),
+        [
+            ("D", true),
+            ("o", false),
+            ("c", true),
+            ("u", false),
+            ("m", true),
+            ("ents", false)
+        ]
+    );
+    assert_eq!(match_parts("test", "x"), [("test", false)]);
+    assert_eq!(
+        match_parts("été", "t"),
+        [("é", false), ("t", true), ("é", false)]
+    );
+    assert_eq!(match_parts("", "abc"), []);
+}
@@ -0,0 +1,299 @@
+//! Line buffer and edit history of the code editor.
+
+use std::ops::Range;
+
+/// A position in the text of a [`CodeEditor`](crate::CodeEditor).
+///
+/// Lines and columns start at zero, columns are counted in characters.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl TextPosition {
+    /// Creates a position from a zero-based line and column
+    pub const fn new(line: usize, column: usize) -> Self {
+        TextPosition { line, column }
+    }
+}
+
+/// Text stored as lines, without their line breaks.
+///
+/// There's always at least one line.
+#[derive(Clone, Debug)]
+pub(crate) struct Lines(Vec<String>);
+
+impl Lines {
+    pub fn new(text: &str) -> Self {
+        Lines(
+            text.split('\n')
+                .map(|line| line.trim_end_matches('\r').to_owned())
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn get(&self, line: usize) -> &str {
+        &self.0[line]
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    pub fn text(&self) -> String {
+        self.0.join("\n")
+    }
+
+    /// Returns the number of characters of a line
+    pub fn line_chars(&self, line: usize) -> usize {
+        self.0[line].chars().count()
+    }
+
+    /// Returns the byte index of a column in a line, clamped to the length of the line
+    pub fn byte_index(&self, line: usize, column: usize) -> usize {
+        byte_index(&self.0[line], column)
+    }
+
+    /// Returns the last position of the text
+    pub fn end(&self) -> TextPosition {
+        let line = self.0.len() - 1;
+        TextPosition::new(line, self.line_chars(line))
+    }
+
+    /// Clamps a position to the text
+    pub fn clamp(&self, pos: TextPosition) -> TextPosition {
+        if pos.line >= self.0.len() {
+            return self.end();
+        }
+        TextPosition::new(pos.line, pos.column.min(self.line_chars(pos.line)))
+    }
+
+    /// Returns the text between two positions, which must be ordered and within the text
+    pub fn slice(&self, start: TextPosition, end: TextPosition) -> String {
+        let start_index = self.byte_index(start.line, start.column);
+        let end_index = self.byte_index(end.line, end.column);
+        if start.line == end.line {
+            return self.0[start.line][start_index..end_index].to_owned();
+        }
+        let mut text = self.0[start.line][start_index..].to_owned();
+        for line in &self.0[start.line + 1..end.line] {
+            text.push('\n');
+            text.push_str(line);
+        }
+        text.push('\n');
+        text.push_str(&self.0[end.line][..end_index]);
+        text
+    }
+
+    /// Replaces the text between two positions, which must be ordered and within the text,
+    /// returning the end of the inserted text
+    pub fn replace(&mut self, start: TextPosition, end: TextPosition, text: &str) -> TextPosition {
+        let start_index = self.byte_index(start.line, start.column);
+        let end_index = self.byte_index(end.line, end.column);
+        let tail = self.0[end.line][end_index..].to_owned();
+        let head = &mut self.0[start.line];
+        head.truncate(start_index);
+
+        let mut inserted = text.split('\n');
+        head.push_str(inserted.next().unwrap_or_default());
+        let new_lines: Vec<String> = inserted.map(str::to_owned).collect();
+        let end_pos = end_of(start, text);
+        self.0
+            .splice(start.line + 1..end.line + 1, new_lines)
+            .for_each(drop);
+        self.0[end_pos.line].push_str(&tail);
+        end_pos
+    }
+}
+
+/// Returns the byte index of a column in a line, clamped to the length of the line
+pub(crate) fn byte_index(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column)
+        .map_or(line.len(), |(index, _)| index)
+}
+
+/// Returns the position after `text` inserted at `start`
+pub(crate) fn end_of(start: TextPosition, text: &str) -> TextPosition {
+    match text.rfind('\n') {
+        Some(index) => TextPosition::new(
+            start.line + text.matches('\n').count(),
+            text[index + 1..].chars().count(),
+        ),
+        None => TextPosition::new(start.line, start.column + text.chars().count()),
+    }
+}
+
+/// The cursor and the other end of the selection
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Selection {
+    pub cursor: TextPosition,
+    pub anchor: TextPosition,
+}
+
+impl Selection {
+    pub fn range(&self) -> Range<TextPosition> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+}
+
+/// A reversible change of the text
+#[derive(Clone, Debug)]
+pub(crate) struct Edit {
+    pub start: TextPosition,
+    pub removed: String,
+    pub inserted: String,
+    pub before: Selection,
+    pub after: Selection,
+}
+
+/// Lines `start..=removed_end` replaced with lines `start..=inserted_end`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LineChange {
+    pub start: usize,
+    pub removed_end: usize,
+    pub inserted_end: usize,
+}
+
+/// Undo and redo stacks
+#[derive(Clone, Debug, Default)]
+pub(crate) struct History {
+    pub undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    /// The last edit was typing which can be extended by the next one
+    typing: bool,
+}
+
+impl History {
+    /// Records an edit. Consecutive typed characters are merged into a single edit.
+    pub fn push(&mut self, edit: Edit, typing: bool) {
+        self.redo.clear();
+        if typing && self.typing && !edit.inserted.contains('\n') && edit.removed.is_empty() {
+            if let Some(last) = self.undo.last_mut() {
+                if end_of(last.start, &last.inserted) == edit.start {
+                    last.inserted.push_str(&edit.inserted);
+                    last.after = edit.after;
+                    return;
+                }
+            }
+        }
+        self.typing = typing && !edit.inserted.contains('\n');
+        self.undo.push(edit);
+    }
+
+    /// Prevents the next typed characters from being merged with the previous ones
+    pub fn break_typing(&mut self) {
+        self.typing = false;
+    }
+
+    pub fn clear(&mut self) {
+        *self = History::default();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the last edit, returning the changed lines
+    pub fn undo(&mut self, lines: &mut Lines, selection: &mut Selection) -> Option<LineChange> {
+        let edit = self.undo.pop()?;
+        self.typing = false;
+        let change = replace(lines, edit.start, &edit.inserted, &edit.removed);
+        *selection = edit.before;
+        self.redo.push(edit);
+        Some(change)
+    }
+
+    /// Applies the last reverted edit again, returning the changed lines
+    pub fn redo(&mut self, lines: &mut Lines, selection: &mut Selection) -> Option<LineChange> {
+        let edit = self.redo.pop()?;
+        self.typing = false;
+        let change = replace(lines, edit.start, &edit.removed, &edit.inserted);
+        *selection = edit.after;
+        self.undo.push(edit);
+        Some(change)
+    }
+}
+
+/// Replaces the text `removed` at `start` with `inserted`
+fn replace(lines: &mut Lines, start: TextPosition, removed: &str, inserted: &str) -> LineChange {
+    let removed_end = end_of(start, removed);
+    let inserted_end = lines.replace(start, removed_end, inserted);
+    LineChange {
+        start: start.line,
+        removed_end: removed_end.line,
+        inserted_end: inserted_end.line,
+    }
+}
+
+#[cfg(test)]
+fn pos(line: usize, column: usize) -> TextPosition {
+    TextPosition::new(line, column)
+}
+
+#[test]
+fn test_lines_replace() {
+    let mut lines = Lines::new("fn main() {\r\n    héllo();\n}");
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines.get(0), "fn main() {");
+    assert_eq!(lines.slice(pos(0, 10), pos(1, 6)), "{\n    hé");
+    assert_eq!(lines.byte_index(1, 7), 8);
+
+    let end = lines.replace(pos(1, 4), pos(1, 9), "let a = 1;\n    world");
+    assert_eq!(end, pos(2, 9));
+    assert_eq!(lines.text(), "fn main() {\n    let a = 1;\n    world();\n}");
+    let end = lines.replace(pos(0, 11), pos(3, 0), "");
+    assert_eq!(end, pos(0, 11));
+    assert_eq!(lines.text(), "fn main() {}");
+    assert_eq!(lines.clamp(pos(4, 2)), pos(0, 12));
+    assert_eq!(lines.clamp(pos(0, 20)), pos(0, 12));
+}
+
+#[test]
+fn test_history() {
+    let mut lines = Lines::new("");
+    let mut selection = Selection::default();
+    let mut history = History::default();
+    for c in "ab\nc".chars() {
+        let start = selection.cursor;
+        let end = lines.replace(start, start, &c.to_string());
+        let after = Selection {
+            cursor: end,
+            anchor: end,
+        };
+        let edit = Edit {
+            start,
+            removed: String::new(),
+            inserted: c.to_string(),
+            before: selection,
+            after,
+        };
+        selection = after;
+        history.push(edit, true);
+    }
+    assert_eq!(lines.text(), "ab\nc");
+    // Typed characters are merged until a line break
+    history.undo(&mut lines, &mut selection);
+    assert_eq!(lines.text(), "ab\n");
+    history.undo(&mut lines, &mut selection);
+    assert_eq!(lines.text(), "ab");
+    history.undo(&mut lines, &mut selection);
+    assert_eq!(lines.text(), "");
+    assert_eq!(selection.cursor, pos(0, 0));
+    assert!(!history.can_undo());
+    for _ in 0..3 {
+        history.redo(&mut lines, &mut selection);
+    }
+    assert_eq!(lines.text(), "ab\nc");
+    assert_eq!(selection.cursor, pos(1, 1));
+    assert!(!history.can_redo());
+}
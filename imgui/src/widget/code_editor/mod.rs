@@ -0,0 +1,1281 @@
+//! A text editor for source code, with line numbers, syntax highlighting, undo/redo and
+//! find/replace.
+
+mod buffer;
+mod tokenizer;
+
+pub use self::buffer::TextPosition;
+pub use self::tokenizer::{SimpleTokenizer, Token, TokenKind, Tokenizer};
+
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+
+use self::buffer::{end_of, Edit, History, LineChange, Lines, Selection};
+use crate::fonts::font::Font;
+use crate::math::MintVec2;
+use crate::sys;
+use crate::{ButtonFlags, ChildFlags, ImColor32, Key, MouseButton, StyleColor, StyleVar, Ui};
+
+/// Options of the searches of a [`CodeEditor`]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct FindOptions {
+    /// Matches the case of ASCII letters
+    pub case_sensitive: bool,
+    /// Only matches whole words, made of alphanumeric characters and `_`
+    pub whole_word: bool,
+}
+
+/// A text editor for source code.
+///
+/// Unlike [`InputTextMultiline`](crate::InputTextMultiline), the editor keeps the text as lines,
+/// highlights it with a [`Tokenizer`], shows line numbers and reports the position of the
+/// cursor. It only draws and tokenizes the visible lines, so it can edit large files.
+///
+/// The editor is persistent state: create it once and call [`CodeEditor::build`] every frame.
+///
+/// Besides the usual navigation keys (with CTRL to move by words and SHIFT to select), it
+/// supports CTRL+A/C/X/V to select all and use the clipboard, CTRL+Z and CTRL+Y (or
+/// CTRL+SHIFT+Z) to undo and redo, and TAB/SHIFT+TAB to indent or unindent the selected lines.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// let mut editor = CodeEditor::new("print('hello')");
+/// editor.set_tokenizer(
+///     SimpleTokenizer::new()
+///         .keywords(["def", "if", "else", "for", "in", "return"])
+///         .line_comment("#"),
+/// );
+/// # let ui = ctx.frame();
+/// ui.window("Script").build(|| {
+///     if ui.button("Find TODO") {
+///         editor.find_next("TODO", FindOptions::default());
+///     }
+///     editor.build(ui, "##script", [0.0, -ui.frame_height_with_spacing()]);
+///     let cursor = editor.cursor();
+///     ui.text(format!("Ln {}, Col {}", cursor.line + 1, cursor.column + 1));
+/// });
+/// ```
+pub struct CodeEditor {
+    lines: Lines,
+    selection: Selection,
+    history: History,
+    tokenizer: Option<Box<dyn Tokenizer>>,
+    colors: [Option<ImColor32>; TokenKind::COUNT],
+    /// Tokenizer state at the start of the first lines
+    line_states: Vec<u32>,
+    tokens: Vec<Token>,
+    /// Index and number of characters of the line with the most characters, if known
+    longest_line: Option<(usize, usize)>,
+    search: Option<(String, FindOptions)>,
+    read_only: bool,
+    tab_size: usize,
+    show_line_numbers: bool,
+    /// Column kept when moving the cursor up and down
+    preferred_column: Option<usize>,
+    scroll_to_cursor: bool,
+    changed: bool,
+    /// Time of the last cursor move, to restart the blinking, or `None` if the cursor moved
+    /// since the last frame
+    cursor_moved_time: Option<f64>,
+}
+
+impl CodeEditor {
+    /// Creates an editor for the given text.
+    ///
+    /// Lines can end with `\n` or `\r\n`, the text is always returned with `\n`.
+    pub fn new(text: &str) -> Self {
+        CodeEditor {
+            lines: Lines::new(text),
+            selection: Selection::default(),
+            history: History::default(),
+            tokenizer: None,
+            colors: [
+                None,
+                Some(ImColor32::from_rgb(86, 156, 214)),
+                Some(ImColor32::from_rgb(78, 201, 176)),
+                None,
+                Some(ImColor32::from_rgb(181, 206, 168)),
+                Some(ImColor32::from_rgb(206, 145, 120)),
+                Some(ImColor32::from_rgb(106, 153, 85)),
+                None,
+                Some(ImColor32::from_rgb(197, 134, 192)),
+            ],
+            line_states: vec![0],
+            tokens: Vec::new(),
+            longest_line: None,
+            search: None,
+            read_only: false,
+            tab_size: 4,
+            show_line_numbers: true,
+            preferred_column: None,
+            scroll_to_cursor: false,
+            changed: false,
+            cursor_moved_time: None,
+        }
+    }
+
+    /// Returns the text, with lines separated by `\n`
+    pub fn text(&self) -> String {
+        self.lines.text()
+    }
+    /// Replaces the text, moving the cursor to the start and clearing the undo history
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = Lines::new(text);
+        self.selection = Selection::default();
+        self.history.clear();
+        self.line_states.truncate(1);
+        self.longest_line = None;
+        self.preferred_column = None;
+        self.scroll_to_cursor = true;
+        self.changed = true;
+    }
+    /// Returns the number of lines, at least 1
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+    /// Returns a line, without its line break
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn line(&self, index: usize) -> &str {
+        self.lines.get(index)
+    }
+    /// Returns an iterator over the lines
+    pub fn lines(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.lines.iter()
+    }
+
+    /// Returns the position of the cursor
+    pub fn cursor(&self) -> TextPosition {
+        self.selection.cursor
+    }
+    /// Moves the cursor, clamped to the text, and clears the selection
+    pub fn set_cursor(&mut self, pos: TextPosition) {
+        self.move_cursor(self.lines.clamp(pos), false);
+    }
+    /// Returns the selected range, if any
+    pub fn selection(&self) -> Option<Range<TextPosition>> {
+        let range = self.selection.range();
+        (range.start != range.end).then_some(range)
+    }
+    /// Selects a range, clamped to the text. The cursor is moved to the end of the range.
+    pub fn set_selection(&mut self, range: Range<TextPosition>) {
+        self.move_cursor(self.lines.clamp(range.start), false);
+        self.move_cursor(self.lines.clamp(range.end), true);
+    }
+    /// Selects the whole text
+    pub fn select_all(&mut self) {
+        self.set_selection(TextPosition::default()..self.lines.end());
+    }
+    /// Returns the selected text
+    pub fn selected_text(&self) -> String {
+        let range = self.selection.range();
+        self.lines.slice(range.start, range.end)
+    }
+    /// Replaces the selection with `text`, as if it was pasted
+    pub fn insert_text(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n");
+        self.replace_range(self.selection.range(), &text, false);
+    }
+    /// Deletes the selected text
+    pub fn delete_selection(&mut self) {
+        self.insert_text("");
+    }
+
+    /// Reverts the last edit, returning false if there's nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let change = self.history.undo(&mut self.lines, &mut self.selection);
+        self.after_history_change(change)
+    }
+    /// Applies the last reverted edit again, returning false if there's nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let change = self.history.redo(&mut self.lines, &mut self.selection);
+        self.after_history_change(change)
+    }
+    /// Returns true if there's an edit to undo
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+    /// Returns true if there's an edit to redo
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Selects the next match of `query` after the cursor, wrapping around at the end of the
+    /// text. The matches are highlighted until [`clear_search`](Self::clear_search) is called.
+    ///
+    /// Returns false if there's no match. Queries can't contain line breaks.
+    pub fn find_next(&mut self, query: &str, options: FindOptions) -> bool {
+        self.find(query, options, true)
+    }
+    /// Selects the previous match of `query` before the cursor, wrapping around at the start of
+    /// the text. See [`find_next`](Self::find_next).
+    pub fn find_previous(&mut self, query: &str, options: FindOptions) -> bool {
+        self.find(query, options, false)
+    }
+    /// Replaces the selected text with `replacement` if it matches `query`, then selects the
+    /// next match.
+    ///
+    /// Returns false if there's no match left.
+    pub fn replace(&mut self, query: &str, replacement: &str, options: FindOptions) -> bool {
+        if self.read_only {
+            return false;
+        }
+        let range = self.selection.range();
+        if range.start.line == range.end.line {
+            let line = self.lines.get(range.start.line);
+            let start = self.lines.byte_index(range.start.line, range.start.column);
+            let end = self.lines.byte_index(range.end.line, range.end.column);
+            if find_in_line(line, query, options, start..end).next() == Some(start..end) {
+                self.replace_range(range, replacement, false);
+            }
+        }
+        self.find_next(query, options)
+    }
+    /// Replaces all the matches of `query` with `replacement`, as a single edit of the lines
+    /// from the first match to the last one.
+    ///
+    /// Returns the number of replaced matches.
+    pub fn replace_all(&mut self, query: &str, replacement: &str, options: FindOptions) -> usize {
+        if self.read_only || query.is_empty() || query.contains('\n') {
+            return 0;
+        }
+        let mut count = 0;
+        let mut replaced = Vec::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            let mut new_line = String::new();
+            let mut copied = 0;
+            for found in find_in_line(line, query, options, 0..line.len()) {
+                new_line.push_str(&line[copied..found.start]);
+                new_line.push_str(replacement);
+                copied = found.end;
+                count += 1;
+            }
+            if copied > 0 {
+                new_line.push_str(&line[copied..]);
+                replaced.push((index, new_line));
+            }
+        }
+        if let (Some(&(first, _)), Some(&(last, _))) = (replaced.first(), replaced.last()) {
+            let mut replaced = replaced.into_iter().peekable();
+            let text = (first..=last)
+                .map(|index| match replaced.next_if(|(line, _)| *line == index) {
+                    Some((_, new_line)) => new_line,
+                    None => self.lines.get(index).to_owned(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let cursor = self.selection.cursor;
+            let end = TextPosition::new(last, self.lines.line_chars(last));
+            self.replace_range(TextPosition::new(first, 0)..end, &text, false);
+            self.move_cursor(self.lines.clamp(cursor), false);
+        }
+        self.search = Some((query.to_owned(), options));
+        count
+    }
+    /// Stops highlighting the matches of the last search
+    pub fn clear_search(&mut self) {
+        self.search = None;
+    }
+
+    /// Sets the tokenizer used to highlight the text
+    pub fn set_tokenizer(&mut self, tokenizer: impl Tokenizer + 'static) {
+        self.tokenizer = Some(Box::new(tokenizer));
+        self.line_states.truncate(1);
+    }
+    /// Removes the tokenizer, drawing all the text with [`StyleColor::Text`]
+    pub fn clear_tokenizer(&mut self) {
+        self.tokenizer = None;
+    }
+    /// Sets the color of a kind of tokens.
+    ///
+    /// [`TokenKind::Text`], [`TokenKind::Identifier`] and [`TokenKind::Punctuation`] use
+    /// [`StyleColor::Text`] by default.
+    pub fn set_token_color(&mut self, kind: TokenKind, color: impl Into<ImColor32>) {
+        self.colors[kind as usize] = Some(color.into());
+    }
+    /// Returns true if the text can't be edited by the user
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+    /// Prevents the user from editing the text. It can still be selected and copied.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+    /// Sets the number of columns between tab stops, used to draw tabs and to indent with TAB.
+    /// Defaults to 4.
+    pub fn set_tab_size(&mut self, tab_size: usize) {
+        self.tab_size = tab_size.max(1);
+    }
+    /// Shows or hides the line numbers. Shown by default.
+    pub fn set_show_line_numbers(&mut self, show: bool) {
+        self.show_line_numbers = show;
+    }
+
+    /// Builds the editor in a child window of the given size, see
+    /// [`ChildWindow::size`](crate::ChildWindow::size).
+    ///
+    /// Returns true if the text was changed since the last call, by the user or by the editing
+    /// methods.
+    pub fn build(&mut self, ui: &Ui, label: impl AsRef<str>, size: impl Into<MintVec2>) -> bool {
+        let metrics = Metrics::new(ui, self.tab_size);
+        if self.longest_line.is_none() {
+            self.longest_line = (0..self.lines.len())
+                .map(|line| (line, self.lines.line_chars(line)))
+                .max_by_key(|&(_, chars)| chars);
+        }
+        let (longest, _) = self.longest_line.unwrap_or_default();
+        let text_width = metrics.column_x(self.lines.get(longest), usize::MAX);
+        let gutter_width = if self.show_line_numbers {
+            let digits = self.lines.len().to_string().len() as f32;
+            (digits + 2.0) * metrics.advance('0')
+        } else {
+            metrics.advance(' ')
+        };
+        let content_size = [
+            gutter_width + text_width + metrics.advance(' ') * 2.0,
+            self.lines.len() as f32 * metrics.line_height,
+        ];
+
+        let window = {
+            let _padding = ui.push_style_var(StyleVar::WindowPadding([0.0, 0.0]));
+            ui.child_window(label)
+                .size(size)
+                .content_size(content_size)
+                .child_flags(ChildFlags::BORDERS)
+                .horizontal_scrollbar(true)
+                .nav_inputs(false)
+                .begin()
+        };
+        if let Some(_window) = window {
+            let view = View::new(ui, gutter_width);
+            self.handle_mouse(ui, &view, &metrics);
+            if ui.is_window_focused() {
+                unsafe { sys::igSetNextFrameWantCaptureKeyboard(true) };
+                self.handle_keyboard(ui, &view, &metrics);
+            }
+            if mem::take(&mut self.scroll_to_cursor) {
+                self.scroll_cursor_into_view(ui, &view, &metrics);
+            }
+            self.draw(ui, &view, &metrics);
+        }
+        mem::take(&mut self.changed)
+    }
+
+    /// Replaces a range of text, recording the edit for undo
+    fn replace_range(&mut self, range: Range<TextPosition>, text: &str, typing: bool) {
+        if self.read_only {
+            return;
+        }
+        let removed = self.lines.slice(range.start, range.end);
+        if removed.is_empty() && text.is_empty() {
+            return;
+        }
+        let before = self.selection;
+        let end = self.lines.replace(range.start, range.end, text);
+        self.after_edit(range.start.line, range.end.line, end.line);
+        self.selection = Selection {
+            cursor: end,
+            anchor: end,
+        };
+        let edit = Edit {
+            start: range.start,
+            removed,
+            inserted: text.to_owned(),
+            before,
+            after: self.selection,
+        };
+        self.history.push(edit, typing);
+        self.preferred_column = None;
+        self.cursor_moved();
+    }
+
+    /// Updates the caches after replacing lines `start..=removed_end` with lines
+    /// `start..=inserted_end`
+    fn after_edit(&mut self, start: usize, removed_end: usize, inserted_end: usize) {
+        self.line_states.truncate(start + 1);
+        if let Some((longest, longest_chars)) = self.longest_line {
+            let edited = (start..=inserted_end)
+                .map(|line| (line, self.lines.line_chars(line)))
+                .max_by_key(|&(_, chars)| chars)
+                .filter(|&(_, chars)| chars >= longest_chars);
+            self.longest_line = match edited {
+                Some(edited) => Some(edited),
+                None if longest > removed_end => {
+                    Some((longest + inserted_end - removed_end, longest_chars))
+                }
+                None if longest < start => Some((longest, longest_chars)),
+                // The longest line got shorter, another line may be the longest now
+                None => None,
+            };
+        }
+        self.changed = true;
+    }
+
+    fn after_history_change(&mut self, change: Option<LineChange>) -> bool {
+        let Some(change) = change else {
+            return false;
+        };
+        self.after_edit(change.start, change.removed_end, change.inserted_end);
+        self.preferred_column = None;
+        self.cursor_moved();
+        true
+    }
+
+    fn cursor_moved(&mut self) {
+        self.scroll_to_cursor = true;
+        self.cursor_moved_time = None;
+    }
+
+    /// Moves the cursor, extending the selection if `select` is true
+    fn move_cursor(&mut self, pos: TextPosition, select: bool) {
+        self.selection.cursor = pos;
+        if !select {
+            self.selection.anchor = pos;
+        }
+        self.history.break_typing();
+        self.cursor_moved();
+    }
+
+    /// Returns the position after moving left or right by one character, or by one word
+    fn step(&self, pos: TextPosition, forward: bool, word: bool) -> TextPosition {
+        let line = self.lines.get(pos.line);
+        if forward && pos.column >= self.lines.line_chars(pos.line) {
+            return match pos.line + 1 < self.lines.len() {
+                true => TextPosition::new(pos.line + 1, 0),
+                false => pos,
+            };
+        }
+        if !forward && pos.column == 0 {
+            return match pos.line {
+                0 => pos,
+                _ => TextPosition::new(pos.line - 1, self.lines.line_chars(pos.line - 1)),
+            };
+        }
+        if !word {
+            let column = if forward {
+                pos.column + 1
+            } else {
+                pos.column - 1
+            };
+            return TextPosition::new(pos.line, column);
+        }
+        let chars: Vec<char> = line.chars().collect();
+        let mut column = pos.column;
+        if forward {
+            // Skips the current word, then the spaces after it
+            let class = char_class(chars[column]);
+            while column < chars.len() && char_class(chars[column]) == class {
+                column += 1;
+            }
+            while column < chars.len() && chars[column].is_whitespace() {
+                column += 1;
+            }
+        } else {
+            // Skips the spaces before the cursor, then the previous word
+            while column > 0 && chars[column - 1].is_whitespace() {
+                column -= 1;
+            }
+            if column > 0 {
+                let class = char_class(chars[column - 1]);
+                while column > 0 && char_class(chars[column - 1]) == class {
+                    column -= 1;
+                }
+            }
+        }
+        TextPosition::new(pos.line, column)
+    }
+
+    /// Returns the range of the word at a position
+    fn word_at(&self, pos: TextPosition) -> Range<TextPosition> {
+        let chars: Vec<char> = self.lines.get(pos.line).chars().collect();
+        if chars.is_empty() {
+            return pos..pos;
+        }
+        let column = pos.column.min(chars.len() - 1);
+        let class = char_class(chars[column]);
+        let mut start = column;
+        while start > 0 && char_class(chars[start - 1]) == class {
+            start -= 1;
+        }
+        let mut end = column;
+        while end < chars.len() && char_class(chars[end]) == class {
+            end += 1;
+        }
+        TextPosition::new(pos.line, start)..TextPosition::new(pos.line, end)
+    }
+
+    fn find(&mut self, query: &str, options: FindOptions, forward: bool) -> bool {
+        if query.is_empty() || query.contains('\n') {
+            return false;
+        }
+        self.search = Some((query.to_owned(), options));
+        let range = self.selection.range();
+        let count = self.lines.len();
+        // Searches from the cursor line to the end, then from the start back to the cursor line
+        for step in 0..=count {
+            let index = match forward {
+                true => (range.end.line + step) % count,
+                false => (range.start.line + count * 2 - step) % count,
+            };
+            let line = self.lines.get(index);
+            let mut bytes = 0..line.len();
+            if step == 0 && forward {
+                bytes.start = self.lines.byte_index(index, range.end.column);
+            } else if step == 0 {
+                bytes.end = self.lines.byte_index(index, range.start.column);
+            } else if step == count && forward {
+                bytes.end = self.lines.byte_index(index, range.end.column);
+            } else if step == count {
+                bytes.start = self.lines.byte_index(index, range.start.column);
+            }
+            let found = match forward {
+                true => find_in_line(line, query, options, bytes).next(),
+                false => find_in_line(line, query, options, bytes).last(),
+            };
+            let found = found.map(|found| {
+                let start = TextPosition::new(index, line[..found.start].chars().count());
+                start..end_of(start, &line[found])
+            });
+            if let Some(found) = found {
+                self.set_selection(found);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Adds or removes one level of indentation to the selected lines
+    fn indent(&mut self, unindent: bool) {
+        let range = self.selection.range();
+        let last = match range.end.column {
+            0 if range.end.line > range.start.line => range.end.line - 1,
+            _ => range.end.line,
+        };
+        let mut text = String::new();
+        for line in range.start.line..=last {
+            let line_text = self.lines.get(line);
+            if line > range.start.line {
+                text.push('\n');
+            }
+            if unindent {
+                let spaces = line_text.len() - line_text.trim_start_matches(' ').len();
+                let removed = match line_text.starts_with('\t') {
+                    true => 1,
+                    false => spaces.min(self.tab_size),
+                };
+                text.push_str(&line_text[removed..]);
+            } else if line_text.is_empty() {
+                // Blank lines aren't indented
+            } else {
+                text.push_str(&" ".repeat(self.tab_size));
+                text.push_str(line_text);
+            }
+        }
+        let start = TextPosition::new(range.start.line, 0);
+        let end = TextPosition::new(last, self.lines.line_chars(last));
+        if self.lines.slice(start, end) != text {
+            self.replace_range(start..end, &text, false);
+        }
+        self.set_selection(start..TextPosition::new(last, self.lines.line_chars(last)));
+    }
+
+    fn handle_keyboard(&mut self, ui: &Ui, view: &View, metrics: &Metrics) {
+        let io = ui.io();
+        let (ctrl, shift, alt) = (io.key_ctrl, io.key_shift, io.key_alt);
+        let shortcut = ctrl && !alt;
+        let cursor = self.selection.cursor;
+        let range = self.selection.range();
+        let has_selection = range.start != range.end;
+        let editable = !self.read_only;
+
+        if ui.is_key_pressed(Key::LeftArrow) {
+            let pos = match has_selection && !shift {
+                true => range.start,
+                false => self.step(cursor, false, ctrl),
+            };
+            self.move_cursor(pos, shift);
+            self.preferred_column = None;
+        } else if ui.is_key_pressed(Key::RightArrow) {
+            let pos = match has_selection && !shift {
+                true => range.end,
+                false => self.step(cursor, true, ctrl),
+            };
+            self.move_cursor(pos, shift);
+            self.preferred_column = None;
+        } else if ui.is_key_pressed(Key::UpArrow) || ui.is_key_pressed(Key::DownArrow) {
+            let down = ui.is_key_pressed(Key::DownArrow);
+            self.move_vertically(if down { 1 } else { -1 }, shift);
+        } else if ui.is_key_pressed(Key::PageUp) || ui.is_key_pressed(Key::PageDown) {
+            let lines = (view.size[1] / metrics.line_height).floor().max(1.0) as isize;
+            let down = ui.is_key_pressed(Key::PageDown);
+            self.move_vertically(if down { lines } else { -lines }, shift);
+        } else if ui.is_key_pressed(Key::Home) {
+            let pos = if ctrl {
+                TextPosition::default()
+            } else {
+                // Toggles between the first non-blank character and the start of the line
+                let line = self.lines.get(cursor.line);
+                let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                TextPosition::new(
+                    cursor.line,
+                    if cursor.column == indent { 0 } else { indent },
+                )
+            };
+            self.move_cursor(pos, shift);
+            self.preferred_column = None;
+        } else if ui.is_key_pressed(Key::End) {
+            let pos = match ctrl {
+                true => self.lines.end(),
+                false => TextPosition::new(cursor.line, self.lines.line_chars(cursor.line)),
+            };
+            self.move_cursor(pos, shift);
+            self.preferred_column = None;
+        } else if shortcut && ui.is_key_pressed(Key::A) {
+            self.select_all();
+        } else if shortcut && (ui.is_key_pressed(Key::C) || ui.is_key_pressed(Key::X)) {
+            let cut = ui.is_key_pressed(Key::X) && editable;
+            if has_selection {
+                ui.set_clipboard_text(self.selected_text());
+                if cut {
+                    self.delete_selection();
+                }
+            } else {
+                // Without a selection, copies or cuts the whole line
+                ui.set_clipboard_text(format!("{}\n", self.lines.get(cursor.line)));
+                if cut {
+                    let start = TextPosition::new(cursor.line, 0);
+                    let end = match cursor.line + 1 < self.lines.len() {
+                        true => TextPosition::new(cursor.line + 1, 0),
+                        false => self.lines.end(),
+                    };
+                    self.replace_range(start..end, "", false);
+                }
+            }
+        } else if shortcut && ui.is_key_pressed(Key::V) && editable {
+            if let Some(text) = ui.clipboard_text() {
+                self.insert_text(&text);
+            }
+        } else if shortcut && ui.is_key_pressed(Key::Z) && editable {
+            match shift {
+                true => self.redo(),
+                false => self.undo(),
+            };
+        } else if shortcut && ui.is_key_pressed(Key::Y) && editable {
+            self.redo();
+        } else if ui.is_key_pressed(Key::Backspace) && editable {
+            if !has_selection {
+                let start = self.step(cursor, false, ctrl);
+                self.selection.anchor = start;
+            }
+            self.delete_selection();
+        } else if ui.is_key_pressed(Key::Delete) && editable {
+            if !has_selection {
+                let end = self.step(cursor, true, ctrl);
+                self.selection.anchor = end;
+            }
+            self.delete_selection();
+        } else if (ui.is_key_pressed(Key::Enter) || ui.is_key_pressed(Key::KeypadEnter)) && editable
+        {
+            // Keeps the indentation of the current line
+            let line = self.lines.get(range.start.line);
+            let indent: String = line
+                .chars()
+                .take(range.start.column)
+                .take_while(|c| c.is_whitespace())
+                .collect();
+            self.replace_range(range, &format!("\n{indent}"), true);
+        } else if ui.is_key_pressed(Key::Tab) && !ctrl && !alt && editable {
+            if shift || range.start.line != range.end.line {
+                self.indent(shift);
+            } else {
+                let x = metrics.column_x(self.lines.get(cursor.line), range.start.column);
+                let column = (x / metrics.advance(' ')).round() as usize;
+                let spaces = self.tab_size - column % self.tab_size;
+                self.replace_range(range, &" ".repeat(spaces), true);
+            }
+        } else if ui.is_key_pressed(Key::Escape) && has_selection {
+            self.move_cursor(cursor, false);
+        }
+
+        // Ignores the characters typed with CTRL, which are shortcuts
+        if editable && !shortcut {
+            let text: String = io
+                .input_queue_characters()
+                .filter(|c| !c.is_control())
+                .collect();
+            if !text.is_empty() {
+                self.replace_range(self.selection.range(), &text, true);
+            }
+        }
+    }
+
+    /// Moves the cursor up or down by a number of lines, keeping its column
+    fn move_vertically(&mut self, lines: isize, select: bool) {
+        let cursor = self.selection.cursor;
+        let column = *self.preferred_column.get_or_insert(cursor.column);
+        let line = cursor.line.saturating_add_signed(lines);
+        let pos = if line >= self.lines.len() {
+            self.lines.end()
+        } else if lines < 0 && cursor.line == 0 {
+            TextPosition::default()
+        } else {
+            self.lines.clamp(TextPosition::new(line, column))
+        };
+        self.move_cursor(pos, select);
+        self.preferred_column = Some(column);
+    }
+
+    fn handle_mouse(&mut self, ui: &Ui, view: &View, metrics: &Metrics) {
+        ui.set_cursor_pos([ui.scroll_x(), ui.scroll_y()]);
+        ui.invisible_button_flags(
+            "##text",
+            [view.size[0].max(1.0), view.size[1].max(1.0)],
+            ButtonFlags::MOUSE_BUTTON_LEFT,
+        );
+        if ui.is_item_hovered() {
+            ui.set_mouse_cursor(Some(crate::MouseCursor::TextInput));
+        }
+        let mouse = ui.io().mouse_pos;
+        if ui.is_item_activated() {
+            let pos = self.position_at(view, metrics, mouse);
+            if ui.is_mouse_double_clicked(MouseButton::Left) {
+                let word = self.word_at(pos);
+                self.set_selection(word);
+            } else {
+                self.move_cursor(pos, ui.io().key_shift);
+            }
+            self.preferred_column = None;
+        } else if ui.is_item_active() && ui.is_mouse_dragging(MouseButton::Left) {
+            let pos = self.position_at(view, metrics, mouse);
+            if pos != self.selection.cursor {
+                self.move_cursor(pos, true);
+                self.preferred_column = None;
+            }
+        }
+    }
+
+    /// Returns the text position at a point on the screen
+    fn position_at(&self, view: &View, metrics: &Metrics, point: [f32; 2]) -> TextPosition {
+        let y = point[1] - view.origin[1];
+        if y < 0.0 {
+            return TextPosition::default();
+        }
+        let line = (y / metrics.line_height) as usize;
+        if line >= self.lines.len() {
+            return self.lines.end();
+        }
+        let target = point[0] - view.origin[0];
+        let mut x = 0.0;
+        for (column, c) in self.lines.get(line).chars().enumerate() {
+            let next = metrics.next_x(x, c);
+            if target < (x + next) / 2.0 {
+                return TextPosition::new(line, column);
+            }
+            x = next;
+        }
+        TextPosition::new(line, self.lines.line_chars(line))
+    }
+
+    fn scroll_cursor_into_view(&self, ui: &Ui, view: &View, metrics: &Metrics) {
+        let cursor = self.selection.cursor;
+        let text_height = view.size[1];
+        let top = cursor.line as f32 * metrics.line_height;
+        if top < ui.scroll_y() {
+            ui.set_scroll_y(top);
+        } else if top + metrics.line_height > ui.scroll_y() + text_height {
+            ui.set_scroll_y(top + metrics.line_height - text_height);
+        }
+        let text_width = view.size[0] - view.gutter_width;
+        let margin = metrics.advance(' ') * 4.0;
+        let x = metrics.column_x(self.lines.get(cursor.line), cursor.column);
+        if x < ui.scroll_x() {
+            ui.set_scroll_x((x - margin).max(0.0));
+        } else if x + margin > ui.scroll_x() + text_width {
+            ui.set_scroll_x(x + margin - text_width);
+        }
+    }
+
+    /// Returns the tokenizer state at the start of a line
+    fn line_state(&mut self, line: usize) -> u32 {
+        let Some(tokenizer) = &self.tokenizer else {
+            return 0;
+        };
+        while self.line_states.len() <= line {
+            let index = self.line_states.len() - 1;
+            let mut state = self.line_states[index];
+            tokenizer.tokenize(self.lines.get(index), &mut state, &mut self.tokens);
+            self.tokens.clear();
+            self.line_states.push(state);
+        }
+        self.line_states[line]
+    }
+
+    fn draw(&mut self, ui: &Ui, view: &View, metrics: &Metrics) {
+        let draw_list = ui.get_window_draw_list();
+        let [window_x, window_y] = ui.window_pos();
+        let text_color = ImColor32::from(ui.style_color(StyleColor::Text));
+        let selection_color = ImColor32::from(ui.style_color(StyleColor::TextSelectedBg));
+        let mut match_color = selection_color;
+        match_color.a /= 2;
+        let focused = ui.is_window_focused();
+
+        let first = (ui.scroll_y() / metrics.line_height) as usize;
+        let visible = (view.size[1] / metrics.line_height).ceil() as usize + 1;
+        let lines = first.min(self.lines.len())..(first + visible).min(self.lines.len());
+        let selection = self.selection.range();
+        let cursor = self.selection.cursor;
+        let moved_time = *self.cursor_moved_time.get_or_insert_with(|| ui.time());
+        // Caches the states of the lines before the visible ones
+        self.line_state(lines.end.saturating_sub(1));
+        let mut tokens = mem::take(&mut self.tokens);
+
+        let text_min = [window_x + view.gutter_width, window_y];
+        let text_max = [window_x + view.size[0], window_y + view.size[1]];
+        draw_list.with_clip_rect_intersect(text_min, text_max, || {
+            for index in lines.clone() {
+                let line = self.lines.get(index);
+                let x0 = view.origin[0];
+                let y = view.origin[1] + index as f32 * metrics.line_height;
+                let y1 = y + metrics.line_height;
+
+                if focused && selection.start == selection.end && index == cursor.line {
+                    let color = ui.style_color(StyleColor::FrameBg);
+                    draw_list
+                        .add_rect([text_min[0], y], [text_max[0], y1], color)
+                        .filled(true)
+                        .build();
+                }
+                if selection.start.line <= index && index <= selection.end.line {
+                    let start = match index == selection.start.line {
+                        true => metrics.column_x(line, selection.start.column),
+                        false => 0.0,
+                    };
+                    let end = match index == selection.end.line {
+                        true => metrics.column_x(line, selection.end.column),
+                        // The line break is selected too
+                        false => metrics.column_x(line, usize::MAX) + metrics.advance(' '),
+                    };
+                    if end > start {
+                        draw_list
+                            .add_rect([x0 + start, y], [x0 + end, y1], selection_color)
+                            .filled(true)
+                            .build();
+                    }
+                }
+                for [start, end] in self.search_matches_x(line, metrics) {
+                    draw_list
+                        .add_rect([x0 + start, y], [x0 + end, y1], match_color)
+                        .filled(true)
+                        .build();
+                }
+
+                tokens.clear();
+                if let Some(tokenizer) = &self.tokenizer {
+                    let mut state = self.line_states[index];
+                    tokenizer.tokenize(line, &mut state, &mut tokens);
+                }
+                let mut x = 0.0;
+                let mut drawn = 0;
+                for token in tokens
+                    .iter()
+                    .chain([&Token::new(line.len()..line.len(), TokenKind::Text)])
+                {
+                    if token.range.start > drawn {
+                        x = self.draw_text(
+                            &draw_list,
+                            metrics,
+                            [x0, y],
+                            x,
+                            &line[drawn..token.range.start],
+                            TokenKind::Text,
+                            text_color,
+                        );
+                    }
+                    if token.range.end > token.range.start {
+                        x = self.draw_text(
+                            &draw_list,
+                            metrics,
+                            [x0, y],
+                            x,
+                            &line[token.range.clone()],
+                            token.kind,
+                            text_color,
+                        );
+                    }
+                    drawn = token.range.end;
+                }
+
+                let blink = ui.io().config_input_text_cursor_blink;
+                let elapsed = ui.time() - moved_time;
+                if focused && index == cursor.line && (!blink || elapsed % 1.2 < 0.8) {
+                    let x = x0 + metrics.column_x(line, cursor.column);
+                    draw_list.add_line([x, y], [x, y1], text_color).build();
+                }
+            }
+        });
+        self.tokens = tokens;
+
+        if self.show_line_numbers {
+            let disabled_color = ui.style_color(StyleColor::TextDisabled);
+            let right = window_x + view.gutter_width - metrics.advance('0');
+            for index in lines {
+                let number = (index + 1).to_string();
+                let y = view.origin[1] + index as f32 * metrics.line_height;
+                let x = right - metrics.str_width(&number);
+                let color = match index == cursor.line {
+                    true => text_color,
+                    false => disabled_color.into(),
+                };
+                draw_list.add_text([x, y], color, number);
+            }
+        }
+    }
+
+    /// Draws a part of a line, starting at `x` from the start of the line, and returns the
+    /// position after it
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text(
+        &self,
+        draw_list: &crate::DrawListMut<'_>,
+        metrics: &Metrics,
+        origin: [f32; 2],
+        mut x: f32,
+        text: &str,
+        kind: TokenKind,
+        text_color: ImColor32,
+    ) -> f32 {
+        let color = self.colors[kind as usize].unwrap_or(text_color);
+        // Tabs are drawn as spaces up to the next tab stop
+        for (index, part) in text.split('\t').enumerate() {
+            if index > 0 {
+                x = metrics.next_x(x, '\t');
+            }
+            if !part.is_empty() {
+                draw_list.add_text([origin[0] + x, origin[1]], color, part);
+                x += metrics.str_width(part);
+            }
+        }
+        x
+    }
+
+    /// Returns the horizontal extents of the matches of the last search in a line, from the
+    /// start of the line
+    fn search_matches_x(&self, line: &str, metrics: &Metrics) -> Vec<[f32; 2]> {
+        let Some((query, options)) = &self.search else {
+            return Vec::new();
+        };
+        find_in_line(line, query, *options, 0..line.len())
+            .map(|found| {
+                let start = line[..found.start].chars().count();
+                let end = start + line[found].chars().count();
+                [metrics.column_x(line, start), metrics.column_x(line, end)]
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for CodeEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeEditor")
+            .field("line_count", &self.lines.len())
+            .field("cursor", &self.selection.cursor)
+            .field("selection", &self.selection())
+            .field("read_only", &self.read_only)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The visible area of the editor, in the child window
+struct View {
+    /// Screen position of the start of the first line, scrolled
+    origin: [f32; 2],
+    /// Size of the visible area, without the scrollbars
+    size: [f32; 2],
+    gutter_width: f32,
+}
+
+impl View {
+    fn new(ui: &Ui, gutter_width: f32) -> Self {
+        let [window_x, window_y] = ui.window_pos();
+        let [width, height] = ui.window_size();
+        let scrollbar_size = unsafe { (*sys::igGetStyle()).ScrollbarSize };
+        View {
+            origin: [
+                window_x + gutter_width - ui.scroll_x(),
+                window_y - ui.scroll_y(),
+            ],
+            size: [
+                width
+                    - if ui.scroll_max_y() > 0.0 {
+                        scrollbar_size
+                    } else {
+                        0.0
+                    },
+                height
+                    - if ui.scroll_max_x() > 0.0 {
+                        scrollbar_size
+                    } else {
+                        0.0
+                    },
+            ],
+            gutter_width,
+        }
+    }
+}
+
+/// Font metrics of the editor
+struct Metrics {
+    font: *const Font,
+    scale: f32,
+    line_height: f32,
+    tab_width: f32,
+}
+
+impl Metrics {
+    fn new(ui: &Ui, tab_size: usize) -> Self {
+        let font = ui.current_font();
+        let line_height = ui.current_font_size();
+        let scale = line_height / font.font_size;
+        Metrics {
+            font,
+            scale,
+            line_height,
+            tab_width: font.char_advance(' ') * scale * tab_size as f32,
+        }
+    }
+
+    /// Returns the advance of a character, tabs excluded
+    fn advance(&self, c: char) -> f32 {
+        unsafe { (*self.font).char_advance(c) * self.scale }
+    }
+
+    /// Returns the position after drawing `c` at `x`
+    fn next_x(&self, x: f32, c: char) -> f32 {
+        match c {
+            '\t' => ((x / self.tab_width).floor() + 1.0) * self.tab_width,
+            _ => x + self.advance(c),
+        }
+    }
+
+    /// Returns the width of a text without tabs
+    fn str_width(&self, text: &str) -> f32 {
+        text.chars().map(|c| self.advance(c)).sum()
+    }
+
+    /// Returns the position of a column from the start of a line
+    fn column_x(&self, line: &str, column: usize) -> f32 {
+        line.chars()
+            .take(column)
+            .fold(0.0, |x, c| self.next_x(x, c))
+    }
+}
+
+/// Classes of characters for word boundaries
+fn char_class(c: char) -> u8 {
+    if c.is_alphanumeric() || c == '_' {
+        0
+    } else if c.is_whitespace() {
+        1
+    } else {
+        2
+    }
+}
+
+/// Returns the byte ranges of the matches of `query` within `bytes` of `line`
+fn find_in_line<'a>(
+    line: &'a str,
+    query: &'a str,
+    options: FindOptions,
+    bytes: Range<usize>,
+) -> impl Iterator<Item = Range<usize>> + 'a {
+    let mut start = bytes.start;
+    std::iter::from_fn(move || {
+        if query.is_empty() {
+            return None;
+        }
+        while start + query.len() <= bytes.end {
+            let candidate = start..start + query.len();
+            start += line[start..].chars().next().map_or(1, char::len_utf8);
+            if !line.is_char_boundary(candidate.end) {
+                continue;
+            }
+            let text = &line[candidate.clone()];
+            let matched = match options.case_sensitive {
+                true => text == query,
+                false => text.eq_ignore_ascii_case(query),
+            };
+            let is_word = |c: Option<char>| c.is_some_and(|c| char_class(c) == 0);
+            if matched
+                && (!options.whole_word
+                    || !(is_word(line[..candidate.start].chars().next_back())
+                        || is_word(line[candidate.end..].chars().next())))
+            {
+                start = candidate.end;
+                return Some(candidate);
+            }
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+fn pos(line: usize, column: usize) -> TextPosition {
+    TextPosition::new(line, column)
+}
+
+#[test]
+fn test_find_replace() {
+    let mut editor = CodeEditor::new("let total = Total + 1;\nlet subtotal = total;");
+    let options = FindOptions::default();
+    assert!(editor.find_next("total", options));
+    assert_eq!(editor.selection(), Some(pos(0, 4)..pos(0, 9)));
+    assert!(editor.find_next("total", options));
+    assert_eq!(editor.selection(), Some(pos(0, 12)..pos(0, 17)));
+    let whole_word = FindOptions {
+        case_sensitive: true,
+        whole_word: true,
+    };
+    assert!(editor.find_next("total", whole_word));
+    assert_eq!(editor.selection(), Some(pos(1, 15)..pos(1, 20)));
+    // Wraps around
+    assert!(editor.find_next("total", whole_word));
+    assert_eq!(editor.selection(), Some(pos(0, 4)..pos(0, 9)));
+    assert!(editor.find_previous("total", whole_word));
+    assert_eq!(editor.selection(), Some(pos(1, 15)..pos(1, 20)));
+    assert!(!editor.find_next("missing", options));
+
+    editor.set_cursor(pos(0, 0));
+    assert!(editor.replace("total", "sum", whole_word));
+    assert!(editor.replace("total", "sum", whole_word));
+    assert_eq!(editor.text(), "let sum = Total + 1;\nlet subtotal = total;");
+    assert_eq!(editor.replace_all("TOTAL", "count", options), 3);
+    assert_eq!(editor.text(), "let sum = count + 1;\nlet subcount = count;");
+    assert!(editor.undo());
+    assert_eq!(editor.text(), "let sum = Total + 1;\nlet subtotal = total;");
+
+    // Only the lines from the first match to the last one are edited
+    let mut editor = CodeEditor::new("a\nx = 10\nb\nx = 2\nc");
+    editor.longest_line = Some((1, 6));
+    assert_eq!(editor.replace_all("x", "long_name", options), 2);
+    assert_eq!(editor.text(), "a\nlong_name = 10\nb\nlong_name = 2\nc");
+    assert_eq!(editor.longest_line, Some((1, 14)));
+    assert_eq!(editor.history.undo[0].removed, "x = 10\nb\nx = 2");
+    assert!(editor.undo());
+    assert_eq!(editor.text(), "a\nx = 10\nb\nx = 2\nc");
+}
+
+#[test]
+fn test_longest_line() {
+    let mut editor = CodeEditor::new("ab\nabcd\nabc");
+    editor.longest_line = Some((1, 4));
+    // Lines after the edit are moved, longer edited lines become the longest
+    editor.set_cursor(pos(0, 2));
+    editor.insert_text("\n");
+    assert_eq!(editor.longest_line, Some((2, 4)));
+    editor.set_cursor(pos(3, 3));
+    editor.insert_text("de");
+    assert_eq!(editor.longest_line, Some((3, 5)));
+    // Undoing and redoing only updates the edited lines
+    assert!(editor.undo());
+    assert_eq!(editor.longest_line, None);
+    editor.longest_line = Some((2, 4));
+    assert!(editor.undo());
+    assert_eq!(editor.longest_line, Some((1, 4)));
+    assert!(editor.redo());
+    assert_eq!(editor.longest_line, Some((2, 4)));
+}
+
+#[test]
+fn test_editing() {
+    let mut editor = CodeEditor::new("fn main() {\n}");
+    editor.set_cursor(pos(0, 11));
+    editor.insert_text("\n    hello();");
+    assert_eq!(editor.cursor(), pos(1, 12));
+    editor.set_selection(pos(1, 4)..pos(1, 9));
+    assert_eq!(editor.selected_text(), "hello");
+    editor.insert_text("world");
+    assert_eq!(editor.line(1), "    world();");
+    assert_eq!(editor.step(pos(1, 4), true, true), pos(1, 9));
+    assert_eq!(editor.step(pos(1, 9), false, true), pos(1, 4));
+    assert_eq!(editor.word_at(pos(1, 6)), pos(1, 4)..pos(1, 9));
+
+    editor.set_selection(pos(0, 0)..pos(2, 0));
+    editor.indent(false);
+    assert_eq!(editor.text(), "    fn main() {\n        world();\n}");
+    editor.indent(true);
+    editor.indent(true);
+    assert_eq!(editor.text(), "fn main() {\nworld();\n}");
+    for _ in 0..3 {
+        assert!(editor.undo());
+    }
+    assert_eq!(editor.text(), "fn main() {\n    world();\n}");
+    assert!(editor.undo());
+    assert_eq!(editor.text(), "fn main() {\n    hello();\n}");
+    assert!(editor.redo());
+    assert_eq!(editor.text(), "fn main() {\n    world();\n}");
+
+    editor.set_read_only(true);
+    editor.insert_text("x");
+    assert_eq!(editor.text(), "fn main() {\n    world();\n}");
+}
+
+#[test]
+fn test_code_editor_typing() {
+    use crate::test::{click, press_keys, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut editor = CodeEditor::new("ab\ncd");
+    let frame = |ctx: &mut crate::Context, editor: &mut CodeEditor| {
+        test_frame(ctx, [300.0, 200.0], |ui| {
+            editor.build(ui, "code", [0.0, 0.0])
+        })
+    };
+
+    frame(&mut ctx, &mut editor);
+    // Clicks at the end of the second line
+    click(&mut ctx, [200.0, 45.0], |ctx| frame(ctx, &mut editor));
+    assert_eq!(editor.cursor(), pos(1, 2));
+
+    for c in "ef".chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    assert!(frame(&mut ctx, &mut editor));
+    press_keys(&mut ctx, &[Key::Enter, Key::Home], |ctx| {
+        frame(ctx, &mut editor)
+    });
+    assert!(!frame(&mut ctx, &mut editor));
+    assert_eq!(editor.text(), "ab\ncdef\n");
+    assert_eq!(editor.cursor(), pos(2, 0));
+
+    ctx.io_mut().add_key_event(Key::ModCtrl, true);
+    ctx.io_mut().add_key_event(Key::Z, true);
+    assert!(frame(&mut ctx, &mut editor));
+    ctx.io_mut().add_key_event(Key::Z, false);
+    ctx.io_mut().add_key_event(Key::ModCtrl, false);
+    frame(&mut ctx, &mut editor);
+    assert_eq!(editor.text(), "ab\ncdef");
+}
+
+#[test]
+fn test_search_matches_after_tabs() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut editor = CodeEditor::new("\tab\tab");
+    assert!(editor.find_next("ab", FindOptions::default()));
+    let ui = ctx.new_frame();
+    let metrics = Metrics::new(ui, 4);
+    let tab = metrics.advance(' ') * 4.0;
+    let ab = metrics.str_width("ab");
+    // The matches start at the tab stops
+    let xs = editor.search_matches_x(editor.line(0), &metrics);
+    let expected = [[tab, tab + ab], [tab * 2.0, tab * 2.0 + ab]];
+    assert_eq!(xs.len(), expected.len());
+    for ([start, end], [expected_start, expected_end]) in xs.into_iter().zip(expected) {
+        assert!((start - expected_start).abs() < 0.01 && (end - expected_end).abs() < 0.01);
+    }
+}
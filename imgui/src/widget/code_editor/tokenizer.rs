@@ -0,0 +1,317 @@
+//! Syntax highlighting of the code editor.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Kind of a token, which determines its color in a [`CodeEditor`](crate::CodeEditor)
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TokenKind {
+    /// Plain text, also used for the parts of lines without tokens
+    Text,
+    /// Reserved word of the language
+    Keyword,
+    /// Name of a built-in or well-known type
+    Type,
+    /// Other names
+    Identifier,
+    /// Numeric literal
+    Number,
+    /// String or character literal
+    String,
+    /// Line or block comment
+    Comment,
+    /// Operators and delimiters
+    Punctuation,
+    /// Preprocessor directive
+    Preprocessor,
+}
+
+impl TokenKind {
+    /// All possible `TokenKind` variants
+    pub const VARIANTS: [TokenKind; TokenKind::COUNT] = [
+        TokenKind::Text,
+        TokenKind::Keyword,
+        TokenKind::Type,
+        TokenKind::Identifier,
+        TokenKind::Number,
+        TokenKind::String,
+        TokenKind::Comment,
+        TokenKind::Punctuation,
+        TokenKind::Preprocessor,
+    ];
+    /// Total count of `TokenKind` variants
+    pub const COUNT: usize = 9;
+}
+
+/// A highlighted part of a line
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Token {
+    /// Byte range of the token in the line
+    pub range: Range<usize>,
+    /// Kind of the token, which determines its color
+    pub kind: TokenKind,
+}
+
+impl Token {
+    /// Creates a token from a byte range and a kind
+    pub fn new(range: Range<usize>, kind: TokenKind) -> Self {
+        Token { range, kind }
+    }
+}
+
+/// Splits the lines of a [`CodeEditor`](crate::CodeEditor) into highlighted tokens.
+///
+/// Lines are tokenized when they're drawn, so only the visible lines of large texts are
+/// tokenized each frame. To highlight constructs spanning several lines, such as block comments,
+/// a tokenizer gets the state at the end of the previous line, which the editor caches.
+///
+/// Also implemented for closures with the same signature as [`Tokenizer::tokenize`].
+pub trait Tokenizer {
+    /// Appends the tokens of `line` to `tokens`, in order and without overlaps. The parts of the
+    /// line without tokens are drawn as [`TokenKind::Text`].
+    ///
+    /// `state` is the state at the start of the line, 0 for the first line, and must be set to
+    /// the state at the end of the line.
+    fn tokenize(&self, line: &str, state: &mut u32, tokens: &mut Vec<Token>);
+}
+
+impl<F: Fn(&str, &mut u32, &mut Vec<Token>)> Tokenizer for F {
+    fn tokenize(&self, line: &str, state: &mut u32, tokens: &mut Vec<Token>) {
+        self(line, state, tokens)
+    }
+}
+
+/// A configurable tokenizer for C-like languages, highlighting keywords, type names, numbers,
+/// strings, comments and preprocessor directives.
+///
+/// ```
+/// # use imgui::*;
+/// let rust = SimpleTokenizer::new()
+///     .keywords(["fn", "let", "mut", "if", "else", "for", "in", "return"])
+///     .types(["bool", "f32", "i32", "str", "String", "Vec", "Self"])
+///     .line_comment("//")
+///     .block_comment("/*", "*/");
+/// let mut editor = CodeEditor::new("fn main() {}");
+/// editor.set_tokenizer(rust);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SimpleTokenizer {
+    keywords: HashSet<String>,
+    types: HashSet<String>,
+    line_comment: Option<String>,
+    block_comment: Option<(String, String)>,
+    preprocessor: Option<char>,
+    quotes: Vec<char>,
+}
+
+impl Default for SimpleTokenizer {
+    fn default() -> Self {
+        SimpleTokenizer {
+            keywords: HashSet::new(),
+            types: HashSet::new(),
+            line_comment: None,
+            block_comment: None,
+            preprocessor: None,
+            quotes: vec!['"', '\''],
+        }
+    }
+}
+
+impl SimpleTokenizer {
+    /// State of lines starting inside a block comment
+    const IN_BLOCK_COMMENT: u32 = 1;
+
+    /// Creates a tokenizer without keywords or comments, highlighting numbers and strings
+    /// quoted with `"` or `'`
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds keywords
+    pub fn keywords<S: Into<String>>(mut self, keywords: impl IntoIterator<Item = S>) -> Self {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+    /// Adds type names
+    pub fn types<S: Into<String>>(mut self, types: impl IntoIterator<Item = S>) -> Self {
+        self.types.extend(types.into_iter().map(Into::into));
+        self
+    }
+    /// Sets the prefix of line comments, e.g. `//`
+    pub fn line_comment(mut self, prefix: impl Into<String>) -> Self {
+        self.line_comment = Some(prefix.into());
+        self
+    }
+    /// Sets the delimiters of block comments, e.g. `/*` and `*/`
+    pub fn block_comment(mut self, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.block_comment = Some((start.into(), end.into()));
+        self
+    }
+    /// Highlights lines starting with `prefix`, e.g. `#`, as preprocessor directives
+    pub fn preprocessor(mut self, prefix: char) -> Self {
+        self.preprocessor = Some(prefix);
+        self
+    }
+    /// Replaces the string quotes. Defaults to `"` and `'`.
+    pub fn quotes(mut self, quotes: impl IntoIterator<Item = char>) -> Self {
+        self.quotes = quotes.into_iter().collect();
+        self
+    }
+}
+
+impl Tokenizer for SimpleTokenizer {
+    fn tokenize(&self, line: &str, state: &mut u32, tokens: &mut Vec<Token>) {
+        let mut pos = 0;
+        // A state left by another tokenizer, e.g. before the block comments were removed
+        if *state == Self::IN_BLOCK_COMMENT && self.block_comment.is_none() {
+            *state = 0;
+        }
+        if let (Self::IN_BLOCK_COMMENT, Some((_, end))) = (*state, &self.block_comment) {
+            match line.find(end.as_str()) {
+                Some(index) => {
+                    pos = index + end.len();
+                    *state = 0;
+                }
+                None => pos = line.len(),
+            }
+            tokens.push(Token::new(0..pos, TokenKind::Comment));
+        } else if let Some(prefix) = self.preprocessor {
+            if line.trim_start().starts_with(prefix) {
+                tokens.push(Token::new(0..line.len(), TokenKind::Preprocessor));
+                return;
+            }
+        }
+
+        while let Some(c) = line[pos..].chars().next() {
+            let rest = &line[pos..];
+            let start = pos;
+            if c.is_whitespace() {
+                pos += c.len_utf8();
+                continue;
+            }
+            if let Some(prefix) = self.line_comment.as_deref() {
+                if rest.starts_with(prefix) {
+                    tokens.push(Token::new(start..line.len(), TokenKind::Comment));
+                    return;
+                }
+            }
+            if let Some((block_start, block_end)) = &self.block_comment {
+                if rest.starts_with(block_start.as_str()) {
+                    let body = start + block_start.len();
+                    match line[body..].find(block_end.as_str()) {
+                        Some(index) => pos = body + index + block_end.len(),
+                        None => {
+                            pos = line.len();
+                            *state = Self::IN_BLOCK_COMMENT;
+                        }
+                    }
+                    tokens.push(Token::new(start..pos, TokenKind::Comment));
+                    continue;
+                }
+            }
+            let kind = if self.quotes.contains(&c) {
+                pos += c.len_utf8();
+                let mut escaped = false;
+                for d in line[pos..].chars() {
+                    pos += d.len_utf8();
+                    match d {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        _ if d == c => break,
+                        _ => {}
+                    }
+                }
+                TokenKind::String
+            } else if c.is_ascii_digit() {
+                pos += word_len(rest, |d| d.is_alphanumeric() || d == '_' || d == '.');
+                TokenKind::Number
+            } else if c.is_alphabetic() || c == '_' {
+                pos += word_len(rest, |d| d.is_alphanumeric() || d == '_');
+                let word = &line[start..pos];
+                if self.keywords.contains(word) {
+                    TokenKind::Keyword
+                } else if self.types.contains(word) {
+                    TokenKind::Type
+                } else {
+                    TokenKind::Identifier
+                }
+            } else {
+                pos += c.len_utf8();
+                TokenKind::Punctuation
+            };
+            tokens.push(Token::new(start..pos, kind));
+        }
+    }
+}
+
+/// Returns the length in bytes of the prefix of `text` matching `f`
+fn word_len(text: &str, f: impl Fn(char) -> bool) -> usize {
+    text.find(|c| !f(c)).unwrap_or(text.len())
+}
+
+#[test]
+fn test_simple_tokenizer() {
+    let tokenizer = SimpleTokenizer::new()
+        .keywords(["let"])
+        .types(["f32"])
+        .line_comment("//")
+        .block_comment("/*", "*/")
+        .preprocessor('#');
+    fn tokenize<'a>(
+        tokenizer: &SimpleTokenizer,
+        line: &'a str,
+        state: &mut u32,
+    ) -> Vec<(&'a str, TokenKind)> {
+        let mut tokens = Vec::new();
+        tokenizer.tokenize(line, state, &mut tokens);
+        tokens
+            .into_iter()
+            .map(|token| (&line[token.range], token.kind))
+            .collect()
+    }
+    let tokenize = |line, state: &mut u32| tokenize(&tokenizer, line, state);
+
+    let mut state = 0;
+    assert_eq!(
+        tokenize("let x: f32 = 1.5; // \"one\"", &mut state),
+        [
+            ("let", TokenKind::Keyword),
+            ("x", TokenKind::Identifier),
+            (":", TokenKind::Punctuation),
+            ("f32", TokenKind::Type),
+            ("=", TokenKind::Punctuation),
+            ("1.5", TokenKind::Number),
+            (";", TokenKind::Punctuation),
+            ("// \"one\"", TokenKind::Comment),
+        ]
+    );
+    assert_eq!(
+        tokenize(r#"s = "a \" b" /* c"#, &mut state),
+        [
+            ("s", TokenKind::Identifier),
+            ("=", TokenKind::Punctuation),
+            (r#""a \" b""#, TokenKind::String),
+            ("/* c", TokenKind::Comment),
+        ]
+    );
+    assert_eq!(state, SimpleTokenizer::IN_BLOCK_COMMENT);
+    assert_eq!(
+        tokenize("#define", &mut state),
+        [("#define", TokenKind::Comment)]
+    );
+    assert_eq!(
+        tokenize("d */ é", &mut state),
+        [("d */", TokenKind::Comment), ("é", TokenKind::Identifier)]
+    );
+    assert_eq!(state, 0);
+    assert_eq!(
+        tokenize("  #include", &mut state),
+        [("  #include", TokenKind::Preprocessor)]
+    );
+
+    // Without block comments, the block comment state is treated as the initial state
+    let mut state = SimpleTokenizer::IN_BLOCK_COMMENT;
+    let mut tokens = Vec::new();
+    SimpleTokenizer::new().tokenize("a */", &mut state, &mut tokens);
+    assert_eq!(state, 0);
+    assert_eq!(tokens[0], Token::new(0..1, TokenKind::Identifier));
+}
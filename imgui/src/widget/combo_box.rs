@@ -48,6 +48,8 @@ pub struct ComboBoxFlags: u32 {
     const NO_ARROW_BUTTON = sys::ImGuiComboFlags_NoArrowButton;
     /// Display only a square arrow button
     const NO_PREVIEW = sys::ImGuiComboFlags_NoPreview;
+    /// Draw the preview with [`Ui::begin_combo_preview`] instead of the preview value
+    const CUSTOM_PREVIEW = sys::ImGuiComboFlags_CustomPreview;
 }
 );
 
@@ -175,6 +177,15 @@ create_token!(
     drop { sys::igEndCombo() }
 );
 
+create_token!(
+    /// Tracks the custom preview of a combo box that can be ended by calling `.end()`
+    /// or by dropping.
+    pub struct ComboPreviewToken<'ui>;
+
+    /// Ends the custom preview of a combo box
+    drop { sys::igEndComboPreview() }
+);
+
 /// # Convenience functions
 impl Ui {
     /// Begins flexibly creating a combo box.
@@ -306,6 +317,49 @@ impl Ui {
         .begin()
     }
 
+    /// Starts drawing the preview of the last combo box, which must have been created with
+    /// [`ComboBoxFlags::CUSTOM_PREVIEW`], in place of its preview value. Items are clipped to
+    /// the preview area.
+    ///
+    /// Must be called after the combo box is ended, i.e. after its [`ComboBoxToken`] is dropped
+    /// or ended, whether it was open or not.
+    ///
+    /// Returns `None` if the preview is not visible and no content should be rendered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # {let ui = ctx.frame();
+    /// let colors = [("Red", [1.0, 0.0, 0.0, 1.0]), ("Green", [0.0, 1.0, 0.0, 1.0])];
+    /// let mut selected = 0;
+    /// if let Some(_combo) = ui.begin_combo_with_flags("Color", "", ComboBoxFlags::CUSTOM_PREVIEW)
+    /// {
+    ///     for (index, (name, _)) in colors.iter().enumerate() {
+    ///         if ui.selectable_config(name).selected(index == selected).build() {
+    ///             selected = index;
+    ///         }
+    ///     }
+    /// }
+    /// if let Some(_preview) = ui.begin_combo_preview() {
+    ///     let (name, color) = colors[selected];
+    ///     ui.color_button("##swatch", color);
+    ///     ui.same_line();
+    ///     ui.text(name);
+    /// }
+    /// # };
+    /// ```
+    #[must_use]
+    #[doc(alias = "BeginComboPreview")]
+    pub fn begin_combo_preview(&self) -> Option<ComboPreviewToken<'_>> {
+        if unsafe { sys::igBeginComboPreview() } {
+            Some(ComboPreviewToken::new(self))
+        } else {
+            None
+        }
+    }
+
     /// Builds a simple combo box for choosing from a slice of values.
     ///
     /// See [`Ui::begin_combo`] for a more "immediate mode" style API
@@ -366,3 +420,32 @@ impl Ui {
         self.combo(label, current_item, items, |s| Cow::Borrowed(s.as_ref()))
     }
 }
+
+#[test]
+fn test_combo_custom_preview() {
+    use crate::test::{click, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    // Returns whether the combo box is open and whether its preview was drawn
+    let frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [300.0, 300.0], |ui| {
+            let combo = ui.begin_combo_with_flags("color", "", ComboBoxFlags::CUSTOM_PREVIEW);
+            let open = combo.is_some();
+            if let Some(combo) = combo {
+                ui.text("Red");
+                combo.end();
+            }
+            let preview = ui.begin_combo_preview().map(|preview| {
+                ui.text("Green");
+                preview.end();
+            });
+            (open, preview.is_some())
+        })
+    };
+
+    frame(&mut ctx);
+    assert_eq!(frame(&mut ctx), (false, true));
+    // The preview is still drawn while the popup is open
+    click(&mut ctx, [20.0, 30.0], frame);
+    assert_eq!(frame(&mut ctx), (true, true));
+}
@@ -0,0 +1,430 @@
+//! Building blocks for custom widgets.
+//!
+//! Widgets drawn with a [`DrawListMut`](crate::DrawListMut) only take part in the layout, in
+//! hovering, in keyboard navigation and in the `is_item_*` queries when they are registered as
+//! items, like Dear ImGui's own widgets. [`Ui::custom_widget`] lays out and registers an item, and
+//! the returned [`CustomWidget`] gives it the behavior of a button, a drag or a slider.
+//!
+//! # Example
+//!
+//! A color swatch which toggles a value when clicked:
+//!
+//! ```no_run
+//! # use imgui::*;
+//! # let mut ctx = Context::create();
+//! # let ui = ctx.frame();
+//! # let mut enabled = false;
+//! if let Some(widget) = ui.custom_widget("##swatch", [40.0, 20.0]) {
+//!     let state = widget.button_behavior(ButtonBehaviorFlags::empty());
+//!     if state.pressed {
+//!         enabled = !enabled;
+//!         widget.mark_edited();
+//!     }
+//!     let color = match (enabled, state.hovered) {
+//!         (true, _) => ui.style_color(StyleColor::CheckMark),
+//!         (false, true) => ui.style_color(StyleColor::FrameBgHovered),
+//!         (false, false) => ui.style_color(StyleColor::FrameBg),
+//!     };
+//!     let rect = widget.rect();
+//!     ui.get_window_draw_list()
+//!         .add_rect(rect.min, rect.max, color)
+//!         .filled(true)
+//!         .build();
+//! }
+//! if ui.is_item_hovered() {
+//!     ui.tooltip_text("Click to toggle");
+//! }
+//! ```
+
+use bitflags::bitflags;
+use std::os::raw::c_void;
+
+use crate::internal::DataTypeKind;
+use crate::math::MintVec2;
+use crate::sys;
+use crate::widget::format::default_format;
+use crate::{Id, ItemFlag, SliderFlags, Ui};
+
+/// A rectangle in screen coordinates
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rect {
+    /// The top left corner
+    pub min: [f32; 2],
+    /// The bottom right corner
+    pub max: [f32; 2],
+}
+
+impl Rect {
+    /// Creates a rectangle from its top left and bottom right corners
+    pub fn new(min: impl Into<MintVec2>, max: impl Into<MintVec2>) -> Self {
+        Rect {
+            min: min.into().into(),
+            max: max.into().into(),
+        }
+    }
+    /// Creates a rectangle from its top left corner and its size
+    pub fn from_pos_size(pos: impl Into<MintVec2>, size: impl Into<MintVec2>) -> Self {
+        let pos: [f32; 2] = pos.into().into();
+        let size: [f32; 2] = size.into().into();
+        Rect {
+            min: pos,
+            max: [pos[0] + size[0], pos[1] + size[1]],
+        }
+    }
+    /// Returns the width and height of the rectangle
+    pub fn size(&self) -> [f32; 2] {
+        [self.width(), self.height()]
+    }
+    pub fn width(&self) -> f32 {
+        self.max[0] - self.min[0]
+    }
+    pub fn height(&self) -> f32 {
+        self.max[1] - self.min[1]
+    }
+    pub fn center(&self) -> [f32; 2] {
+        [
+            (self.min[0] + self.max[0]) * 0.5,
+            (self.min[1] + self.max[1]) * 0.5,
+        ]
+    }
+    /// Returns true if `point` is inside the rectangle, including its top and left edges
+    pub fn contains(&self, point: impl Into<MintVec2>) -> bool {
+        let point: [f32; 2] = point.into().into();
+        (self.min[0]..self.max[0]).contains(&point[0])
+            && (self.min[1]..self.max[1]).contains(&point[1])
+    }
+    /// Returns the rectangle grown by `amount` on each side, or shrunk if it's negative
+    pub fn expand(&self, amount: f32) -> Self {
+        Rect {
+            min: [self.min[0] - amount, self.min[1] - amount],
+            max: [self.max[0] + amount, self.max[1] + amount],
+        }
+    }
+}
+
+impl From<Rect> for sys::ImRect {
+    fn from(rect: Rect) -> Self {
+        sys::ImRect {
+            Min: rect.min.into(),
+            Max: rect.max.into(),
+        }
+    }
+}
+
+impl From<sys::ImRect> for Rect {
+    fn from(rect: sys::ImRect) -> Self {
+        Rect {
+            min: rect.Min.into(),
+            max: rect.Max.into(),
+        }
+    }
+}
+
+bitflags!(
+    /// Flags for [`CustomWidget::button_behavior`]
+    #[repr(transparent)]
+    pub struct ButtonBehaviorFlags: u32 {
+        /// React on left mouse button (the default when no mouse button is set)
+        const MOUSE_BUTTON_LEFT = sys::ImGuiButtonFlags_MouseButtonLeft;
+        /// React on right mouse button
+        const MOUSE_BUTTON_RIGHT = sys::ImGuiButtonFlags_MouseButtonRight;
+        /// React on middle mouse button
+        const MOUSE_BUTTON_MIDDLE = sys::ImGuiButtonFlags_MouseButtonMiddle;
+        /// Pressed on click (mouse down)
+        const PRESSED_ON_CLICK = sys::ImGuiButtonFlags_PressedOnClick;
+        /// Pressed on click and release over the widget (the default)
+        const PRESSED_ON_CLICK_RELEASE = sys::ImGuiButtonFlags_PressedOnClickRelease;
+        /// Pressed on click over the widget and release anywhere
+        const PRESSED_ON_CLICK_RELEASE_ANYWHERE = sys::ImGuiButtonFlags_PressedOnClickReleaseAnywhere;
+        /// Pressed on release, without a prior click over the widget
+        const PRESSED_ON_RELEASE = sys::ImGuiButtonFlags_PressedOnRelease;
+        /// Pressed on double-click
+        const PRESSED_ON_DOUBLE_CLICK = sys::ImGuiButtonFlags_PressedOnDoubleClick;
+        /// Pressed when something is dragged and dropped over the widget
+        const PRESSED_ON_DRAG_DROP_HOLD = sys::ImGuiButtonFlags_PressedOnDragDropHold;
+        /// Pressed repeatedly while held
+        const REPEAT = sys::ImGuiButtonFlags_Repeat;
+        /// Hovered even when the mouse is over a child window
+        const FLATTEN_CHILDREN = sys::ImGuiButtonFlags_FlattenChildren;
+        /// Items submitted later over the widget can be hovered
+        const ALLOW_OVERLAP = sys::ImGuiButtonFlags_AllowOverlap;
+        /// Not pressed when a key modifier is held
+        const NO_KEY_MODIFIERS = sys::ImGuiButtonFlags_NoKeyModifiers;
+        /// Doesn't become active while held, e.g. to let a parent widget be dragged
+        const NO_HOLDING_ACTIVE_ID = sys::ImGuiButtonFlags_NoHoldingActiveId;
+        /// Doesn't take keyboard navigation focus when clicked
+        const NO_NAV_FOCUS = sys::ImGuiButtonFlags_NoNavFocus;
+        /// Not reported as hovered when focused by keyboard navigation
+        const NO_HOVERED_ON_FOCUS = sys::ImGuiButtonFlags_NoHoveredOnFocus;
+    }
+);
+
+/// The state of a [`CustomWidget`] after [`CustomWidget::button_behavior`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ItemState {
+    /// The mouse cursor is over the widget, or it's focused by keyboard navigation
+    pub hovered: bool,
+    /// The widget is held with the mouse, or activated with keyboard navigation
+    pub held: bool,
+    /// The widget was pressed this frame, see [`ButtonBehaviorFlags`]
+    pub pressed: bool,
+}
+
+/// An item registered with [`Ui::custom_widget`] or [`Ui::item_add`], whose behavior is
+/// implemented with Dear ImGui's widget behaviors.
+#[derive(Copy, Clone, Debug)]
+pub struct CustomWidget<'ui> {
+    id: Id,
+    rect: Rect,
+    ui: &'ui Ui,
+}
+
+impl<'ui> CustomWidget<'ui> {
+    /// Wraps an item registered with [`Ui::item_add`] in the current frame
+    pub fn new(ui: &'ui Ui, id: Id, rect: Rect) -> Self {
+        CustomWidget { id, rect, ui }
+    }
+    pub fn id(&self) -> Id {
+        self.id
+    }
+    /// The rectangle of the widget, in screen coordinates
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Handles the mouse and keyboard navigation like a button, returning its state.
+    ///
+    /// The widget becomes active while held, which [`drag_behavior`](Self::drag_behavior) and
+    /// [`slider_behavior`](Self::slider_behavior) need.
+    #[doc(alias = "ButtonBehavior")]
+    pub fn button_behavior(&self, flags: ButtonBehaviorFlags) -> ItemState {
+        let mut state = ItemState::default();
+        state.pressed = unsafe {
+            sys::igButtonBehavior(
+                self.rect.into(),
+                self.id.0,
+                &mut state.hovered,
+                &mut state.held,
+                flags.bits() as sys::ImGuiButtonFlags,
+            )
+        };
+        state
+    }
+
+    /// Returns true if the mouse cursor is over the widget and it can be hovered, i.e. no other
+    /// item is active and no popup is in the way.
+    #[doc(alias = "ItemHoverable")]
+    pub fn hoverable(&self) -> bool {
+        unsafe { sys::igItemHoverable(self.rect.into(), self.id.0, 0) }
+    }
+
+    /// Changes `value` by `speed` per pixel that the mouse moves horizontally while the widget is
+    /// active, e.g. held after [`button_behavior`](Self::button_behavior), or with the arrow keys
+    /// when it's activated by keyboard navigation. Returns true if the value changed.
+    ///
+    /// The value is kept between `min` and `max` when both are set and `min < max`, and rounded
+    /// to the precision of Dear ImGui's default display format unless
+    /// [`SliderFlags::NO_ROUND_TO_FORMAT`] is set.
+    #[doc(alias = "DragBehavior")]
+    pub fn drag_behavior<T: DataTypeKind>(
+        &self,
+        value: &mut T,
+        speed: f32,
+        min: Option<T>,
+        max: Option<T>,
+        flags: SliderFlags,
+    ) -> bool {
+        let option_ptr = |value: &Option<T>| match value {
+            Some(value) => value as *const T as *const c_void,
+            None => std::ptr::null(),
+        };
+        let changed = unsafe {
+            sys::igDragBehavior(
+                self.id.0,
+                T::KIND as i32,
+                value as *mut T as *mut c_void,
+                speed,
+                option_ptr(&min),
+                option_ptr(&max),
+                default_format(T::KIND).as_ptr(),
+                flags.bits() as sys::ImGuiSliderFlags,
+            )
+        };
+        if changed {
+            self.mark_edited();
+        }
+        changed
+    }
+
+    /// Sets `value` between `min` and `max` from the position of the mouse cursor along the
+    /// widget while it's active, e.g. held after [`button_behavior`](Self::button_behavior), or
+    /// with the arrow keys when it's activated by keyboard navigation.
+    ///
+    /// Returns true if the value changed, and the rectangle of the grab to draw. The grab is
+    /// vertical if the widget is taller than it's wide.
+    #[doc(alias = "SliderBehavior")]
+    pub fn slider_behavior<T: DataTypeKind>(
+        &self,
+        value: &mut T,
+        min: T,
+        max: T,
+        flags: SliderFlags,
+    ) -> (bool, Rect) {
+        let mut flags = flags.bits() as sys::ImGuiSliderFlags;
+        if self.rect.height() > self.rect.width() {
+            flags |= sys::ImGuiSliderFlags_Vertical as sys::ImGuiSliderFlags;
+        }
+        let mut grab = sys::ImRect::default();
+        let changed = unsafe {
+            sys::igSliderBehavior(
+                self.rect.into(),
+                self.id.0,
+                T::KIND as i32,
+                value as *mut T as *mut c_void,
+                &min as *const T as *const c_void,
+                &max as *const T as *const c_void,
+                default_format(T::KIND).as_ptr(),
+                flags,
+                &mut grab,
+            )
+        };
+        if changed {
+            self.mark_edited();
+        }
+        (changed, grab.into())
+    }
+
+    /// Draws the highlight around the widget when it's focused by keyboard or gamepad
+    /// navigation, like Dear ImGui's own widgets.
+    #[doc(alias = "RenderNavHighlight")]
+    pub fn render_nav_highlight(&self) {
+        unsafe {
+            sys::igRenderNavHighlight(
+                self.rect.into(),
+                self.id.0,
+                sys::ImGuiNavHighlightFlags_None as sys::ImGuiNavHighlightFlags,
+            )
+        }
+    }
+
+    /// Reports that the value of the widget changed, for [`Ui::is_item_edited`] and
+    /// [`Ui::is_item_deactivated_after_edit`]. Called by the drag and slider behaviors.
+    pub fn mark_edited(&self) {
+        self.ui.mark_item_edited(self.id);
+    }
+}
+
+impl Ui {
+    /// Lays out a custom widget of `size` at the cursor position and registers it as an item,
+    /// with `label` as its id.
+    ///
+    /// Returns `None` if the widget is clipped, in which case it doesn't need to be drawn. See
+    /// the [module documentation](crate::widget::custom_widget) for an example.
+    pub fn custom_widget(
+        &self,
+        label: impl AsRef<str>,
+        size: impl Into<MintVec2>,
+    ) -> Option<CustomWidget<'_>> {
+        let rect = Rect::from_pos_size(self.cursor_screen_pos(), size);
+        let id = self.new_id_str(label);
+        self.item_size(rect, -1.0);
+        self.item_add(rect, id, None, ItemFlag::empty())
+            .then(|| CustomWidget::new(self, id, rect))
+    }
+
+    /// Reserves the space of `rect` in the layout and moves the cursor after it.
+    ///
+    /// `text_baseline_y` is the offset of the text inside the item, e.g. the vertical frame
+    /// padding, to align text on the same line, or -1 for none.
+    #[doc(alias = "ItemSize")]
+    pub fn item_size(&self, rect: Rect, text_baseline_y: f32) {
+        unsafe { sys::igItemSize_Rect(rect.into(), text_baseline_y) }
+    }
+
+    /// Registers an item with `id` and `rect`, after [`item_size`](Self::item_size), so that it
+    /// can be hovered and navigated to, and is the target of the `is_item_*` queries.
+    ///
+    /// `nav_rect` is the rectangle used by keyboard navigation, which defaults to `rect`. Returns
+    /// false if the item is clipped.
+    #[doc(alias = "ItemAdd")]
+    pub fn item_add(&self, rect: Rect, id: Id, nav_rect: Option<Rect>, flags: ItemFlag) -> bool {
+        let nav_rect = nav_rect.map(sys::ImRect::from);
+        let nav_rect_ptr = nav_rect
+            .as_ref()
+            .map_or(std::ptr::null(), |rect| rect as *const _);
+        unsafe {
+            sys::igItemAdd(
+                rect.into(),
+                id.0,
+                nav_rect_ptr,
+                flags.bits() as sys::ImGuiItemFlags,
+            )
+        }
+    }
+
+    /// Keeps the state of `id` alive when it's not submitted as an item this frame, e.g. the
+    /// active id of a widget made of several items.
+    #[doc(alias = "KeepAliveID")]
+    pub fn keep_alive_id(&self, id: Id) {
+        unsafe { sys::igKeepAliveID(id.0) }
+    }
+
+    /// Reports that the value of the item with `id` changed, for [`Ui::is_item_edited`].
+    #[doc(alias = "MarkItemEdited")]
+    pub fn mark_item_edited(&self, id: Id) {
+        unsafe { sys::igMarkItemEdited(id.0) }
+    }
+}
+
+/// Returns the part of `label` which Dear ImGui displays, i.e. the text before any `##`
+pub(crate) fn visible_label(label: &str) -> &str {
+    label.find("##").map_or(label, |end| &label[..end])
+}
+
+#[test]
+fn test_custom_widget() {
+    use crate::test::{press, release, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut value = 10.0f32;
+    let mut frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [300.0, 200.0], |ui| {
+            ui.set_cursor_screen_pos([20.0, 50.0]);
+            let widget = ui.custom_widget("##drag", [100.0, 20.0]).unwrap();
+            let state = widget.button_behavior(ButtonBehaviorFlags::empty());
+            widget.drag_behavior(
+                &mut value,
+                0.5,
+                Some(0.0),
+                Some(100.0),
+                SliderFlags::empty(),
+            );
+            (
+                widget.rect(),
+                state,
+                ui.is_item_hovered(),
+                ui.is_item_edited(),
+                ui.cursor_screen_pos(),
+            )
+        })
+    };
+
+    let (rect, state, hovered, _, cursor) = frame(&mut ctx);
+    assert_eq!(rect, Rect::new([20.0, 50.0], [120.0, 70.0]));
+    assert_eq!(state, ItemState::default());
+    assert!(!hovered);
+    // The item is laid out like built-in widgets
+    assert!(cursor[1] >= 70.0);
+
+    ctx.io_mut().mouse_pos = [50.0, 60.0];
+    let (_, state, hovered, _, _) = frame(&mut ctx);
+    assert!(state.hovered && hovered);
+    let (_, state, _, _, _) = press(&mut ctx, [50.0, 60.0], &mut frame);
+    assert!(state.held);
+    ctx.io_mut().mouse_pos = [70.0, 60.0];
+    let (_, _, _, edited, _) = frame(&mut ctx);
+    assert!(edited);
+    let (_, state, _, _, _) = release(&mut ctx, &mut frame);
+    assert!(state.pressed);
+    assert_eq!(value, 20.0);
+}
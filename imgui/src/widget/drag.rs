@@ -3,6 +3,8 @@ use std::ptr;
 
 use crate::internal::DataTypeKind;
 use crate::sys;
+use crate::widget::expression::Expressions;
+use crate::widget::format::{self, DisplayFormat, InputClamp};
 use crate::widget::slider::SliderFlags;
 use crate::Ui;
 
@@ -33,7 +35,7 @@ impl<L: AsRef<str>, T: DataTypeKind> Drag<T, L> {
     }
 }
 
-impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
+impl<L: AsRef<str>, T: DataTypeKind, F: DisplayFormat<T>> Drag<T, L, F> {
     /// Sets the range (inclusive)
     pub fn range(mut self, min: T, max: T) -> Self {
         self.min = Some(min);
@@ -47,8 +49,11 @@ impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
         self.speed = speed;
         self
     }
-    /// Sets the display format using *a C-style printf string*
-    pub fn display_format<F2: AsRef<str>>(self, display_format: F2) -> Drag<T, L, F2> {
+    /// Sets the display format, using either *a C-style printf string* or a [`FormatWith`]
+    /// closure
+    ///
+    /// [`FormatWith`]: crate::FormatWith
+    pub fn display_format<F2: DisplayFormat<T>>(self, display_format: F2) -> Drag<T, L, F2> {
         Drag {
             label: self.label,
             speed: self.speed,
@@ -58,6 +63,14 @@ impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
             flags: self.flags,
         }
     }
+    /// Evaluates arithmetic expressions such as `1920/2` or `+=15` typed in the text input
+    /// (CTRL+Click or double-click), clamping the result to the range.
+    ///
+    /// This replaces the display format, which can be set with
+    /// [`Expressions::display_format`].
+    pub fn expressions(self, expressions: Expressions<'_>) -> Drag<T, L, Expressions<'_>> {
+        self.display_format(expressions)
+    }
     /// Replaces all current settings with the given flags
     pub fn flags(mut self, flags: SliderFlags) -> Self {
         self.flags = flags;
@@ -67,33 +80,25 @@ impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
     ///
     /// Returns true if the slider value was changed.
     pub fn build(self, ui: &Ui, value: &mut T) -> bool {
-        unsafe {
-            let (one, two) = ui.scratch_txt_with_opt(self.label, self.display_format);
-
-            sys::igDragScalar(
-                one,
-                T::KIND as i32,
-                value as *mut T as *mut c_void,
-                self.speed,
-                self.min
-                    .as_ref()
-                    .map(|min| min as *const T)
-                    .unwrap_or(ptr::null()) as *const c_void,
-                self.max
-                    .as_ref()
-                    .map(|max| max as *const T)
-                    .unwrap_or(ptr::null()) as *const c_void,
-                two,
-                self.flags.bits() as i32,
-            )
-        }
+        self.build_one(ui, self.label.as_ref(), value)
     }
     /// Builds a horizontal array of multiple drag sliders attached to the given slice.
     ///
     /// Returns true if any slider value was changed.
     pub fn build_array(self, ui: &Ui, values: &mut [T]) -> bool {
+        let display_format =
+            match format::native_format(self.display_format.as_ref().map(|f| f.kind())) {
+                Ok(display_format) => display_format,
+                Err(_) => {
+                    // Each value gets its own format string and text input
+                    let label = self.label.as_ref();
+                    return format::build_array(ui, label, values, |value| {
+                        self.build_one(ui, "", value)
+                    });
+                }
+            };
         unsafe {
-            let (one, two) = ui.scratch_txt_with_opt(self.label, self.display_format);
+            let (one, two) = ui.scratch_txt_with_opt(&self.label, display_format);
 
             sys::igDragScalarN(
                 one,
@@ -114,6 +119,38 @@ impl<L: AsRef<str>, T: DataTypeKind, F: AsRef<str>> Drag<T, L, F> {
             )
         }
     }
+    fn build_one(&self, ui: &Ui, label: &str, value: &mut T) -> bool {
+        let input_clamp = if self.flags.contains(SliderFlags::NO_INPUT) {
+            None
+        } else {
+            Some(InputClamp::drag(self.flags.bits(), self.min, self.max))
+        };
+        format::build_scalar(
+            ui,
+            label,
+            value,
+            self.display_format.as_ref().map(|f| f.kind()),
+            input_clamp,
+            |one, two, value| unsafe {
+                sys::igDragScalar(
+                    one,
+                    T::KIND as i32,
+                    value as *mut T as *mut c_void,
+                    self.speed,
+                    self.min
+                        .as_ref()
+                        .map(|min| min as *const T)
+                        .unwrap_or(ptr::null()) as *const c_void,
+                    self.max
+                        .as_ref()
+                        .map(|max| max as *const T)
+                        .unwrap_or(ptr::null()) as *const c_void,
+                    two,
+                    self.flags.bits() as i32,
+                )
+            },
+        )
+    }
 }
 
 /// Builder for a drag slider widget.
@@ -209,14 +246,22 @@ where
     /// Returns true if the slider value was changed.
     #[doc(alias = "DragFloatRange2")]
     pub fn build(self, ui: &Ui, min: &mut f32, max: &mut f32) -> bool {
+        let display_format = self
+            .display_format
+            .as_ref()
+            .map(|v| format::valid_format(v.as_ref(), f32::KIND));
+        let max_display_format = self
+            .max_display_format
+            .as_ref()
+            .map(|v| format::valid_format(v.as_ref(), f32::KIND));
         // we do this ourselves the long way...
         unsafe {
             let buffer = &mut *ui.scratch_buffer().get();
             buffer.refresh_buffer();
 
             let label_start = buffer.push(self.label);
-            let display_format = self.display_format.as_ref().map(|v| buffer.push(v));
-            let max_display_format = self.max_display_format.as_ref().map(|v| buffer.push(v));
+            let display_format = display_format.map(|v| buffer.push(v));
+            let max_display_format = max_display_format.map(|v| buffer.push(v));
 
             let label = buffer.offset(label_start);
             let display_format = display_format
@@ -252,14 +297,22 @@ where
     /// Returns true if the slider value was changed.
     #[doc(alias = "DragIntRange2")]
     pub fn build(self, ui: &Ui, min: &mut i32, max: &mut i32) -> bool {
+        let display_format = self
+            .display_format
+            .as_ref()
+            .map(|v| format::valid_format(v.as_ref(), i32::KIND));
+        let max_display_format = self
+            .max_display_format
+            .as_ref()
+            .map(|v| format::valid_format(v.as_ref(), i32::KIND));
         unsafe {
             // we do this ourselves the long way...
             let buffer = &mut *ui.scratch_buffer().get();
             buffer.refresh_buffer();
 
             let label_start = buffer.push(self.label);
-            let display_format = self.display_format.as_ref().map(|v| buffer.push(v));
-            let max_display_format = self.max_display_format.as_ref().map(|v| buffer.push(v));
+            let display_format = display_format.map(|v| buffer.push(v));
+            let max_display_format = max_display_format.map(|v| buffer.push(v));
 
             let label = buffer.offset(label_start);
             let display_format = display_format
@@ -0,0 +1,283 @@
+//! Combo boxes, radio groups and segmented controls over the variants of an enum.
+//!
+//! An enum implementing [`EnumVariants`] lists its variants with their labels and optional
+//! descriptions, which are shown as tooltips. With the `derive` feature, the trait can be derived
+//! for enums whose variants have no fields:
+//!
+//! ```ignore
+//! use imgui::EnumVariants;
+//!
+//! #[derive(Copy, Clone, PartialEq, EnumVariants)]
+//! enum Filter {
+//!     Nearest,
+//!     #[imgui(label = "Bilinear", description = "Blends the four nearest texels")]
+//!     Linear,
+//!     #[imgui(skip)]
+//!     Unsupported,
+//! }
+//! ```
+//!
+//! Otherwise it's implemented by hand:
+//!
+//! ```no_run
+//! # use imgui::*;
+//! #[derive(Copy, Clone, PartialEq)]
+//! enum Filter {
+//!     Nearest,
+//!     Linear,
+//! }
+//!
+//! impl EnumVariants for Filter {
+//!     const VARIANTS: &'static [Self] = &[Filter::Nearest, Filter::Linear];
+//!
+//!     fn label(&self) -> &'static str {
+//!         match self {
+//!             Filter::Nearest => "Nearest",
+//!             Filter::Linear => "Bilinear",
+//!         }
+//!     }
+//!
+//!     fn description(&self) -> Option<&'static str> {
+//!         match self {
+//!             Filter::Nearest => None,
+//!             Filter::Linear => Some("Blends the four nearest texels"),
+//!         }
+//!     }
+//! }
+//!
+//! # let mut ctx = Context::create();
+//! # let ui = ctx.frame();
+//! let mut filter = Filter::Nearest;
+//! ui.enum_combo("Filter", &mut filter);
+//! ui.enum_radio_group("Filter##radio", &mut filter);
+//! ui.enum_segmented_control("Filter##segments", &mut filter);
+//! ```
+//!
+//! For more options, e.g. a horizontal radio group, the variants can be passed to the builders
+//! directly:
+//!
+//! ```no_run
+//! # use imgui::*;
+//! # use std::borrow::Cow;
+//! # #[derive(Copy, Clone, PartialEq)]
+//! # enum Filter { Nearest, Linear }
+//! # impl EnumVariants for Filter {
+//! #     const VARIANTS: &'static [Self] = &[Filter::Nearest, Filter::Linear];
+//! #     fn label(&self) -> &'static str { "" }
+//! # }
+//! # let mut ctx = Context::create();
+//! # let ui = ctx.frame();
+//! # let mut index = 0;
+//! ui.radio_group_config("Filter", Filter::VARIANTS, |filter| Cow::from(filter.label()))
+//!     .tooltip_fn(|filter| filter.description().map(Cow::from))
+//!     .horizontal(true)
+//!     .build(&mut index);
+//! ```
+
+use std::borrow::Cow;
+
+use crate::{sys, HoveredFlags, Id, Ui};
+
+/// An enum whose variants can be selected with [`Ui::enum_combo`], [`Ui::enum_radio_group`] and
+/// [`Ui::enum_segmented_control`].
+///
+/// See the [module documentation](crate::widget::enum_variants) for examples.
+pub trait EnumVariants: Copy + PartialEq + 'static {
+    /// The variants to choose from, in display order
+    const VARIANTS: &'static [Self];
+
+    /// Returns the text shown for the variant
+    fn label(&self) -> &'static str;
+
+    /// Returns the description of the variant, shown as a tooltip
+    fn description(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Returns the index of `value` in the variants, or `usize::MAX` to select none
+fn variant_index<E: EnumVariants>(value: &E) -> usize {
+    E::VARIANTS
+        .iter()
+        .position(|variant| variant == value)
+        .unwrap_or(usize::MAX)
+}
+
+fn variant_label<E: EnumVariants>(variant: &E) -> Cow<'_, str> {
+    Cow::Borrowed(variant.label())
+}
+
+fn variant_description<E: EnumVariants>(variant: &E) -> Option<Cow<'_, str>> {
+    variant.description().map(Cow::Borrowed)
+}
+
+impl Ui {
+    /// Builds a combo box for choosing a variant of an enum. Returns true if another variant was
+    /// selected.
+    #[doc(alias = "Combo")]
+    pub fn enum_combo<E: EnumVariants>(&self, label: impl AsRef<str>, value: &mut E) -> bool {
+        let mut changed = false;
+        let preview = if E::VARIANTS.contains(value) {
+            value.label()
+        } else {
+            ""
+        };
+        if let Some(_combo) = self.begin_combo(label, preview) {
+            for variant in E::VARIANTS {
+                let selected = variant == value;
+                if self
+                    .selectable_config(variant.label())
+                    .selected(selected)
+                    .build()
+                    && !selected
+                {
+                    *value = *variant;
+                    changed = true;
+                }
+                if let Some(description) = variant.description() {
+                    if self.is_item_hovered_with_flags(HoveredFlags::FOR_TOOLTIP) {
+                        self.tooltip_text(description);
+                    }
+                }
+                if selected {
+                    self.set_item_default_focus();
+                }
+            }
+        }
+        // Like Dear ImGui's own combo, after the popup the last item is the combo box again
+        if changed {
+            self.mark_item_edited(Id(unsafe { sys::igGetItemID() }));
+        }
+        changed
+    }
+
+    /// Builds a group of radio buttons, one per variant of an enum. Returns true if another
+    /// variant was selected.
+    pub fn enum_radio_group<E: EnumVariants>(&self, label: impl AsRef<str>, value: &mut E) -> bool {
+        let mut index = variant_index(value);
+        let changed = self
+            .radio_group_config(label, E::VARIANTS, variant_label)
+            .tooltip_fn(variant_description)
+            .build(&mut index);
+        if changed {
+            *value = E::VARIANTS[index];
+        }
+        changed
+    }
+
+    /// Builds a segmented control with a segment per variant of an enum. Returns true if another
+    /// variant was selected.
+    pub fn enum_segmented_control<E: EnumVariants>(
+        &self,
+        label: impl AsRef<str>,
+        value: &mut E,
+    ) -> bool {
+        let mut index = variant_index(value);
+        let changed = self
+            .segmented_control_config(label, E::VARIANTS, variant_label)
+            .tooltip_fn(variant_description)
+            .build(&mut index);
+        if changed {
+            *value = E::VARIANTS[index];
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Mode {
+    Move,
+    Rotate,
+    Scale,
+    Hidden,
+}
+
+#[cfg(test)]
+impl EnumVariants for Mode {
+    const VARIANTS: &'static [Self] = &[Mode::Move, Mode::Rotate, Mode::Scale];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Mode::Move => "Move",
+            Mode::Rotate => "Rotate",
+            Mode::Scale => "Scale",
+            Mode::Hidden => "Hidden",
+        }
+    }
+}
+
+#[test]
+fn test_enum_radio_group() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let frame = |ctx: &mut crate::Context, mode: &mut Mode| {
+        crate::test::test_frame(ctx, [300.0, 200.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            let changed = ui.enum_radio_group("##mode", mode);
+            (changed, ui.is_item_edited(), ui.item_rect_size())
+        })
+    };
+
+    // A value which isn't listed selects no radio button
+    let mut mode = Mode::Hidden;
+    let (_, _, size) = frame(&mut ctx, &mut mode);
+    let row_height = size[1] / 3.0;
+    let (changed, edited, _) =
+        crate::test::click(&mut ctx, [20.0, 40.0 + row_height * 1.5], |ctx| {
+            frame(ctx, &mut mode)
+        });
+    assert!(changed && edited);
+    assert_eq!(mode, Mode::Rotate);
+    let (changed, edited, _) = frame(&mut ctx, &mut mode);
+    assert!(!changed && !edited);
+}
+
+#[test]
+fn test_enum_combo() {
+    use crate::test::{click, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let frame = |ctx: &mut crate::Context, mode: &mut Mode| {
+        test_frame(ctx, [300.0, 200.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            let changed = ui.enum_combo("##mode", mode);
+            (changed, ui.is_item_edited())
+        })
+    };
+
+    let mut mode = Mode::Move;
+    frame(&mut ctx, &mut mode);
+    assert_eq!(
+        click(&mut ctx, [20.0, 50.0], |ctx| frame(ctx, &mut mode)),
+        (false, false)
+    );
+    frame(&mut ctx, &mut mode);
+    // The popup lists the variants below the combo box, Scale is the third one
+    let clicked = click(&mut ctx, [30.0, 100.0], |ctx| frame(ctx, &mut mode));
+    assert_eq!(clicked, (true, true));
+    assert_eq!(mode, Mode::Scale);
+    assert_eq!(frame(&mut ctx, &mut mode), (false, false));
+}
+
+#[test]
+fn test_enum_segmented_control() {
+    use crate::test::{click, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let frame = |ctx: &mut crate::Context, mode: &mut Mode| {
+        test_frame(ctx, [400.0, 100.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            let changed = ui.enum_segmented_control("##mode", mode);
+            // The segments fit their text, this point is in the second one, after Move
+            let rotate_x = 10.0 + ui.calc_text_size("Move")[0] + ui.frame_height();
+            (changed, ui.is_item_edited(), rotate_x)
+        })
+    };
+
+    let mut mode = Mode::Scale;
+    let (_, _, rotate_x) = frame(&mut ctx, &mut mode);
+    let clicked = click(&mut ctx, [rotate_x, 45.0], |ctx| frame(ctx, &mut mode));
+    assert_eq!(clicked, (true, true, rotate_x));
+    assert_eq!(mode, Mode::Rotate);
+    assert_eq!(frame(&mut ctx, &mut mode), (false, false, rotate_x));
+}
@@ -0,0 +1,592 @@
+//! Arithmetic expressions typed in numeric widgets.
+//!
+//! With [`Expressions`], the text input of a drag, slider or scalar input accepts expressions
+//! such as `1920/2`, `3.5*2` or `(10 + 4) / 2` instead of plain numbers, as well as relative
+//! edits such as `+=15` or `*=2`, applied to the value the widget had when the text input was
+//! activated.
+
+use std::error::Error;
+use std::fmt;
+use std::mem::MaybeUninit;
+
+use crate::internal::{DataType, DataTypeKind};
+use crate::widget::format::{self, private, DisplayFormat};
+
+/// Evaluates arithmetic expressions typed in a numeric widget.
+///
+/// Supported syntax:
+///
+/// - decimal numbers, with an optional exponent: `12`, `0.5`, `.5`, `1e3`;
+/// - the `+`, `-`, `*` and `/` operators with the usual precedence, unary minus and
+///   parentheses;
+/// - unit suffixes from a user-provided table, e.g. `10cm + 5mm`, each unit being a factor
+///   applied to the number or parenthesized expression it follows;
+/// - a leading `+=`, `-=`, `*=` or `/=`, applying the rest of the expression to the value the
+///   widget had when the text input was activated.
+///
+/// The result is rounded to the nearest value for integer widgets, and clamped to the range of
+/// drags and sliders. Expressions are evaluated with `f64`s, so for 64-bit integer widgets,
+/// plain integers are parsed exactly and results beyond 2<sup>53</sup> are rejected. Text that
+/// doesn't evaluate leaves the value unchanged, the error being shown in a tooltip while the text
+/// input is active.
+///
+/// Use it with [`Drag::expressions`](crate::Drag::expressions),
+/// [`InputScalar::expressions`](crate::InputScalar::expressions), or as the display format of
+/// any other numeric widget:
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// const LENGTH_UNITS: &[(&str, f64)] = &[("m", 1.0), ("cm", 0.01), ("mm", 0.001)];
+/// let mut width = 1.5f64;
+/// Drag::new("Width (m)")
+///     .range(0.0, 100.0)
+///     .speed(0.01)
+///     .expressions(Expressions::new().units(LENGTH_UNITS).display_format("%.3f m"))
+///     .build(ui, &mut width);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Expressions<'a> {
+    units: &'a [(&'a str, f64)],
+    display_format: Option<&'a str>,
+}
+
+impl<'a> Expressions<'a> {
+    /// Creates an expression evaluator without units, displaying values with Dear ImGui's
+    /// default format
+    pub const fn new() -> Self {
+        Expressions {
+            units: &[],
+            display_format: None,
+        }
+    }
+    /// Sets the unit suffixes accepted after numbers, as pairs of a suffix and the factor it
+    /// applies, e.g. `("cm", 0.01)` for a value in meters.
+    ///
+    /// Suffixes are case-sensitive and can't contain digits, whitespace, operators,
+    /// parentheses, dots or commas.
+    pub const fn units(mut self, units: &'a [(&'a str, f64)]) -> Self {
+        self.units = units;
+        self
+    }
+    /// Sets the *C-style printf string* used to display the value
+    pub const fn display_format(mut self, display_format: &'a str) -> Self {
+        self.display_format = Some(display_format);
+        self
+    }
+    /// Evaluates `text`, applying relative edits such as `+=15` to `initial`
+    pub fn evaluate(&self, text: &str, initial: f64) -> Result<f64, ExpressionError> {
+        let mut parser = Parser {
+            text,
+            pos: 0,
+            units: self.units,
+        };
+        parser.skip_whitespace();
+        let relative = relative_op(text);
+        if let Some(op) = relative {
+            parser.pos += op.len();
+        }
+        let operand = parser.expression()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected `{c}`")));
+        }
+        let result = match relative {
+            None => operand,
+            Some("+=") => initial + operand,
+            Some("-=") => initial - operand,
+            Some("*=") => initial * operand,
+            Some(_) if operand == 0.0 => {
+                return Err(ExpressionError::new("division by zero", 0));
+            }
+            Some(_) => initial / operand,
+        };
+        if result.is_finite() {
+            Ok(result)
+        } else {
+            Err(ExpressionError::new("the result is too large", 0))
+        }
+    }
+}
+
+impl<T: DataTypeKind> DisplayFormat<T> for Expressions<'_> {}
+impl<T: DataTypeKind> private::Sealed<T> for Expressions<'_> {
+    fn kind(&self) -> private::FormatKind<'_, T> {
+        let display_format = match self.display_format {
+            Some(display_format) => display_format,
+            None => format::default_format(T::KIND).to_str().unwrap(),
+        };
+        private::FormatKind {
+            display: private::Display::Printf(display_format),
+            parse: Some(self),
+        }
+    }
+}
+impl<T: DataTypeKind> private::TextParser<T> for Expressions<'_> {
+    fn parse_text(&self, text: &str, initial: T) -> Result<T, Option<String>> {
+        if let Some(value) = parse_integer(text) {
+            return Ok(value);
+        }
+        let value = self
+            .evaluate(text, to_f64(initial))
+            .map_err(|error| Some(error.to_string()))?;
+        // Not all 64-bit integers fit in an f64, so results may have been rounded
+        if matches!(T::KIND, DataType::I64 | DataType::U64) {
+            let relative = relative_op(text).is_some();
+            if value.abs() >= MAX_EXACT_INTEGER
+                || (relative && to_f64(initial).abs() >= MAX_EXACT_INTEGER)
+            {
+                return Err(Some("the result can't be represented exactly".to_owned()));
+            }
+        }
+        Ok(from_f64(value))
+    }
+    fn clamps_to_range(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the relative edit operator at the start of `text`, if any
+fn relative_op(text: &str) -> Option<&'static str> {
+    let text = text.trim_start();
+    ["+=", "-=", "*=", "/="]
+        .into_iter()
+        .find(|op| text.starts_with(op))
+}
+
+/// Integers below this magnitude are exactly representable in an f64, larger ones may have
+/// been rounded
+const MAX_EXACT_INTEGER: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+
+/// Parses a plain integer literal exactly for 64-bit integer widgets
+fn parse_integer<T: DataTypeKind>(text: &str) -> Option<T> {
+    let text = text.trim();
+    let mut result = MaybeUninit::<T>::uninit();
+    let ptr = result.as_mut_ptr();
+    // Safety: `DataTypeKind` guarantees that `T` has the layout of its `KIND`
+    unsafe {
+        match T::KIND {
+            DataType::I64 => (ptr as *mut i64).write(text.parse().ok()?),
+            DataType::U64 => (ptr as *mut u64).write(text.parse().ok()?),
+            _ => return None,
+        }
+        Some(result.assume_init())
+    }
+}
+
+/// Error returned when an expression can't be evaluated
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpressionError {
+    message: String,
+    position: usize,
+}
+
+impl ExpressionError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        ExpressionError {
+            message: message.into(),
+            position,
+        }
+    }
+    /// Returns the description of the error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    /// Returns the byte offset in the expression where the error was found
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ExpressionError {}
+
+/// Recursive descent parser evaluating the expression as it goes
+struct Parser<'t, 'u> {
+    text: &'t str,
+    pos: usize,
+    units: &'u [(&'u str, f64)],
+}
+
+impl Parser<'_, '_> {
+    fn rest(&self) -> &str {
+        &self.text[self.pos..]
+    }
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+    fn error(&self, message: impl Into<String>) -> ExpressionError {
+        ExpressionError::new(message, self.pos)
+    }
+    /// Skips whitespace and consumes `c` if it's the next character
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value *= self.unary()?;
+            } else if self.eat('/') {
+                self.skip_whitespace();
+                let divisor_pos = self.pos;
+                let divisor = self.unary()?;
+                if divisor == 0.0 {
+                    return Err(ExpressionError::new("division by zero", divisor_pos));
+                }
+                value /= divisor;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// unary := ('-' | '+') unary | atom
+    fn unary(&mut self) -> Result<f64, ExpressionError> {
+        if self.eat('-') {
+            Ok(-self.unary()?)
+        } else if self.eat('+') {
+            self.unary()
+        } else {
+            self.atom()
+        }
+    }
+
+    /// atom := (number | '(' expression ')') unit?
+    fn atom(&mut self) -> Result<f64, ExpressionError> {
+        self.skip_whitespace();
+        let value = if self.eat('(') {
+            let value = self.expression()?;
+            if !self.eat(')') {
+                return Err(self.error("expected `)`"));
+            }
+            value
+        } else {
+            self.number()?
+        };
+        self.unit(value)
+    }
+
+    fn number(&mut self) -> Result<f64, ExpressionError> {
+        let bytes = self.rest().as_bytes();
+        let digits = |from: usize| {
+            bytes[from..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count()
+        };
+        let mut len = digits(0);
+        let mut has_digits = len > 0;
+        if bytes.get(len) == Some(&b'.') {
+            let fraction = digits(len + 1);
+            has_digits |= fraction > 0;
+            len += 1 + fraction;
+        }
+        if !has_digits {
+            return Err(match self.peek() {
+                Some(c) => self.error(format!("expected a number, found `{c}`")),
+                None => self.error("expected a number"),
+            });
+        }
+        if let Some(b'e' | b'E') = bytes.get(len) {
+            // Only an exponent if digits follow, otherwise it may be a unit
+            let sign = usize::from(matches!(bytes.get(len + 1), Some(b'+' | b'-')));
+            let exponent = digits(len + 1 + sign);
+            if exponent > 0 {
+                len += 1 + sign + exponent;
+            }
+        }
+        let number = &self.rest()[..len];
+        let value = number
+            .parse()
+            .map_err(|_| self.error(format!("invalid number `{number}`")))?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    fn unit(&mut self, value: f64) -> Result<f64, ExpressionError> {
+        let start = self.pos;
+        self.skip_whitespace();
+        let len = self
+            .rest()
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace() || "+-*/(),.=".contains(c))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            self.pos = start;
+            return Ok(value);
+        }
+        let unit = &self.rest()[..len];
+        match self.units.iter().find(|(name, _)| *name == unit) {
+            Some((_, factor)) => {
+                self.pos += len;
+                Ok(value * factor)
+            }
+            None => Err(self.error(format!("unknown unit `{unit}`"))),
+        }
+    }
+}
+
+/// Converts a widget value to f64
+pub(crate) fn to_f64<T: DataTypeKind>(value: T) -> f64 {
+    let ptr = &value as *const T;
+    // Safety: `DataTypeKind` guarantees that `T` has the layout of its `KIND`
+    unsafe {
+        match T::KIND {
+            DataType::I8 => *(ptr as *const i8) as f64,
+            DataType::U8 => *(ptr as *const u8) as f64,
+            DataType::I16 => *(ptr as *const i16) as f64,
+            DataType::U16 => *(ptr as *const u16) as f64,
+            DataType::I32 => *(ptr as *const i32) as f64,
+            DataType::U32 => *(ptr as *const u32) as f64,
+            DataType::I64 => *(ptr as *const i64) as f64,
+            DataType::U64 => *(ptr as *const u64) as f64,
+            DataType::F32 => *(ptr as *const f32) as f64,
+            DataType::F64 => *(ptr as *const f64),
+        }
+    }
+}
+
+/// Converts an f64 to a widget value, rounding and saturating for integers
+pub(crate) fn from_f64<T: DataTypeKind>(value: f64) -> T {
+    let mut result = MaybeUninit::<T>::uninit();
+    let ptr = result.as_mut_ptr();
+    // Safety: `DataTypeKind` guarantees that `T` has the layout of its `KIND`
+    unsafe {
+        match T::KIND {
+            DataType::I8 => (ptr as *mut i8).write(value.round() as i8),
+            DataType::U8 => (ptr as *mut u8).write(value.round() as u8),
+            DataType::I16 => (ptr as *mut i16).write(value.round() as i16),
+            DataType::U16 => (ptr as *mut u16).write(value.round() as u16),
+            DataType::I32 => (ptr as *mut i32).write(value.round() as i32),
+            DataType::U32 => (ptr as *mut u32).write(value.round() as u32),
+            DataType::I64 => (ptr as *mut i64).write(value.round() as i64),
+            DataType::U64 => (ptr as *mut u64).write(value.round() as u64),
+            DataType::F32 => (ptr as *mut f32).write(value as f32),
+            DataType::F64 => (ptr as *mut f64).write(value),
+        }
+        result.assume_init()
+    }
+}
+
+#[cfg(test)]
+const UNITS: &[(&str, f64)] = &[("m", 1.0), ("cm", 0.01), ("mm", 0.001), ("%", 0.01)];
+
+#[cfg(test)]
+fn eval(text: &str) -> Result<f64, ExpressionError> {
+    Expressions::new().units(UNITS).evaluate(text, 10.0)
+}
+
+#[test]
+fn test_evaluate() {
+    let cases = [
+        ("42", 42.0),
+        (" 1920/2 ", 960.0),
+        ("3.5*2", 7.0),
+        (".5", 0.5),
+        ("1e3", 1000.0),
+        ("2.5E-1", 0.25),
+        ("1 + 2 * 3", 7.0),
+        ("(1 + 2) * 3", 9.0),
+        ("10 - 4 - 3", 3.0),
+        ("8 / 4 / 2", 1.0),
+        ("-3 * -(2 + 1)", 9.0),
+        ("+=15", 25.0),
+        ("-= 2 * 2", 6.0),
+        ("*=2", 20.0),
+        ("/=4", 2.5),
+        ("-5", -5.0),
+        ("150cm", 1.5),
+        ("1m + 25 cm", 1.25),
+        ("(1 + 1)mm", 0.002),
+        ("50%", 0.5),
+        ("+=50cm", 10.5),
+    ];
+    for (text, expected) in cases {
+        let value = eval(text).unwrap_or_else(|e| panic!("{text}: {e}"));
+        assert!((value - expected).abs() < 1e-9, "{text}: {value}");
+    }
+}
+
+#[test]
+fn test_evaluate_errors() {
+    let cases = [
+        ("", "expected a number", 0),
+        ("  ", "expected a number", 2),
+        ("1 +", "expected a number", 3),
+        ("2 * (3 + 1", "expected `)`", 10),
+        ("1 / (2 - 2)", "division by zero", 4),
+        ("/=0", "division by zero", 0),
+        ("12 px", "unknown unit `px`", 3),
+        ("1.2.3", "unexpected `.`", 3),
+        ("2 3", "unexpected `3`", 2),
+        ("abc", "expected a number, found `a`", 0),
+        ("1e308 * 10", "the result is too large", 0),
+    ];
+    for (text, message, position) in cases {
+        let error = eval(text).expect_err(text);
+        assert_eq!(
+            (error.message(), error.position()),
+            (message, position),
+            "{text}"
+        );
+    }
+}
+
+/// Builds a drag with expressions at (10, 40)
+#[cfg(test)]
+fn drag_frame(ctx: &mut crate::Context, value: &mut f32) {
+    crate::test::test_frame(ctx, [300.0, 100.0], |ui| {
+        ui.set_cursor_screen_pos([10.0, 40.0]);
+        crate::Drag::new("drag")
+            .range(0.0, 100.0)
+            .expressions(Expressions::new())
+            .build(ui, value);
+    })
+}
+
+/// Replaces the text of the active text input with `text`
+#[cfg(test)]
+fn type_text<R>(
+    ctx: &mut crate::Context,
+    text: &str,
+    mut frame: impl FnMut(&mut crate::Context) -> R,
+) {
+    use crate::Key;
+
+    ctx.io_mut().add_key_event(Key::ModCtrl, true);
+    crate::test::press_keys(ctx, &[Key::A], &mut frame);
+    ctx.io_mut().add_key_event(Key::ModCtrl, false);
+    for c in text.chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    // Relative edits are applied once, not every frame
+    for _ in 0..3 {
+        frame(ctx);
+    }
+}
+
+#[test]
+fn test_drag_expressions() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+
+    // CTRL+Click the drag to start typing
+    let mut value = 25.0f32;
+    drag_frame(&mut ctx, &mut value);
+    ctx.io_mut().add_key_event(crate::Key::ModCtrl, true);
+    crate::test::press(&mut ctx, [20.0, 45.0], |ctx| drag_frame(ctx, &mut value));
+    ctx.io_mut().add_key_event(crate::Key::ModCtrl, false);
+    crate::test::release(&mut ctx, |ctx| drag_frame(ctx, &mut value));
+
+    type_text(&mut ctx, "+=15", |ctx| drag_frame(ctx, &mut value));
+    assert_eq!(value, 40.0);
+    // Errors keep the last value
+    type_text(&mut ctx, "(1 + 2) *", |ctx| drag_frame(ctx, &mut value));
+    assert_eq!(value, 40.0);
+    // Relative to the value before editing, clamped to the range
+    type_text(&mut ctx, "*=10", |ctx| drag_frame(ctx, &mut value));
+    assert_eq!(value, 100.0);
+    type_text(&mut ctx, "-= 5 / 2", |ctx| drag_frame(ctx, &mut value));
+    assert_eq!(value, 22.5);
+}
+
+#[test]
+fn test_input_expression_error_tooltip() {
+    use crate::test::{click, press_keys};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut value = 25i32;
+    // Returns the number of windows drawn, including tooltips
+    let mut frame = |ctx: &mut crate::Context| {
+        let ui = ctx.new_frame();
+        ui.window("test")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([300.0, 100.0], crate::Condition::Always)
+            .build(|| {
+                ui.set_cursor_screen_pos([10.0, 40.0]);
+                ui.input_scalar("value", &mut value)
+                    .expressions(Expressions::new())
+                    .build();
+            });
+        ctx.render().draw_lists_count()
+    };
+
+    frame(&mut ctx);
+    let windows = click(&mut ctx, [20.0, 45.0], &mut frame);
+    // The error stays in a tooltip while the text isn't changed
+    type_text(&mut ctx, "(1 + 2) *", &mut frame);
+    assert_eq!(frame(&mut ctx), windows + 1);
+    type_text(&mut ctx, "3", &mut frame);
+    assert_eq!(frame(&mut ctx), windows);
+    // And is forgotten when the text input is deactivated
+    type_text(&mut ctx, "3 *", &mut frame);
+    assert_eq!(frame(&mut ctx), windows + 1);
+    press_keys(&mut ctx, &[crate::Key::Enter], &mut frame);
+    assert_eq!(frame(&mut ctx), windows);
+    assert!(ctx.ui_mut().text_input_error.borrow().is_none());
+    assert_eq!(value, 3);
+}
+
+#[test]
+fn test_conversions() {
+    assert_eq!(from_f64::<i32>(2.5), 3);
+    assert_eq!(from_f64::<i8>(-1000.0), i8::MIN);
+    assert_eq!(from_f64::<u16>(-3.0), 0);
+    assert_eq!(from_f64::<f32>(0.25), 0.25);
+    assert_eq!(to_f64(-7i64), -7.0);
+    assert_eq!(to_f64(200u8), 200.0);
+    assert_eq!(to_f64(0.5f32), 0.5);
+}
+
+#[test]
+fn test_64_bit_integer_expressions() {
+    use private::TextParser;
+
+    let expressions = Expressions::new();
+    let big = (1i64 << 53) + 1;
+    assert_eq!(expressions.parse_text(&big.to_string(), 0i64), Ok(big));
+    assert_eq!(expressions.parse_text(" -42 ", 0i64), Ok(-42));
+    assert_eq!(
+        expressions.parse_text("18446744073709551615", 0),
+        Ok(u64::MAX)
+    );
+    assert_eq!(expressions.parse_text("2 * 3", big), Ok(6));
+    let inexact = Some(Some("the result can't be represented exactly".to_owned()));
+    assert_eq!(expressions.parse_text("+=1", big).err(), inexact);
+    assert_eq!(expressions.parse_text("1e19", 0i64).err(), inexact);
+    assert_eq!(
+        expressions.parse_text("2 * 9007199254740993", 0u64).err(),
+        inexact
+    );
+}
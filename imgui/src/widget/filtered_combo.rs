@@ -0,0 +1,546 @@
+//! Combo box with a filter text input.
+
+use std::borrow::Cow;
+
+use crate::sys;
+use crate::{
+    ComboBox, ComboBoxFlags, HistoryDirection, InputText, InputTextCallback,
+    InputTextCallbackHandler, Key, ListClipper, SelectableFlags, TextCallbackData, Ui,
+};
+
+/// How the items of a [`FilteredCombo`] are matched with the filter text
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ComboFilterMode {
+    /// Items containing the characters of the filter in order, ignoring case, sorted from the
+    /// closest match. Consecutive characters and characters starting words rank higher.
+    #[default]
+    Fuzzy,
+    /// Dear ImGui's [`TextFilter`](crate::TextFilter) syntax, keeping the original order: items
+    /// containing one of the comma-separated words of the filter, ignoring case. Words prefixed
+    /// by `-` hide the items containing them, e.g. `-test,src`.
+    TextFilter,
+}
+
+/// Builder for a combo box whose popup starts with a text input filtering its items.
+///
+/// The filter text input is focused when the popup opens. While typing, the up and down arrows
+/// move the highlight through the matching items and Enter selects the highlighted one. Only the
+/// visible items are submitted, so the combo box can hold large lists.
+///
+/// Created with [`Ui::filtered_combo`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # use std::borrow::Cow;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let fonts = ["Arial", "Consolas", "DejaVu Sans Mono", "Fira Code", "Helvetica"];
+/// let mut font = 0;
+/// ui.filtered_combo("Font", &mut font, &fonts, |font| Cow::Borrowed(*font))
+///     .hint("Search fonts")
+///     .build();
+/// ```
+#[must_use]
+pub struct FilteredCombo<'ui, 'a, Label, V, L, H = &'static str, P = fn(&Ui, &V)> {
+    label: Label,
+    current_item: &'a mut usize,
+    items: &'a [V],
+    label_fn: L,
+    hint: Option<H>,
+    preview: Option<P>,
+    mode: ComboFilterMode,
+    flags: ComboBoxFlags,
+    max_visible_items: usize,
+    ui: &'ui Ui,
+}
+
+impl<'ui, 'a, Label, V, L, H, P> FilteredCombo<'ui, 'a, Label, V, L, H, P>
+where
+    Label: AsRef<str>,
+    H: AsRef<str>,
+    for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    P: FnOnce(&Ui, &V),
+{
+    /// Sets the hint displayed in the filter text input when it's empty.
+    pub fn hint<H2: AsRef<str>>(self, hint: H2) -> FilteredCombo<'ui, 'a, Label, V, L, H2, P> {
+        FilteredCombo {
+            label: self.label,
+            current_item: self.current_item,
+            items: self.items,
+            label_fn: self.label_fn,
+            hint: Some(hint),
+            preview: self.preview,
+            mode: self.mode,
+            flags: self.flags,
+            max_visible_items: self.max_visible_items,
+            ui: self.ui,
+        }
+    }
+    /// Draws the preview of the current item with `preview`, e.g. an icon next to its label,
+    /// instead of its label. This sets [`ComboBoxFlags::CUSTOM_PREVIEW`].
+    ///
+    /// The closure is called with the current item, if any, in the area of the preview, see
+    /// [`Ui::begin_combo_preview`].
+    pub fn preview<P2: FnOnce(&Ui, &V)>(
+        self,
+        preview: P2,
+    ) -> FilteredCombo<'ui, 'a, Label, V, L, H, P2> {
+        FilteredCombo {
+            label: self.label,
+            current_item: self.current_item,
+            items: self.items,
+            label_fn: self.label_fn,
+            hint: self.hint,
+            preview: Some(preview),
+            mode: self.mode,
+            flags: self.flags,
+            max_visible_items: self.max_visible_items,
+            ui: self.ui,
+        }
+    }
+    /// Sets how items are matched with the filter text.
+    ///
+    /// Default: `ComboFilterMode::Fuzzy`
+    pub fn mode(mut self, mode: ComboFilterMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Replaces the combo box flags.
+    ///
+    /// With [`ComboBoxFlags::CUSTOM_PREVIEW`], the preview is only drawn by the closure set
+    /// with [`preview`](Self::preview).
+    pub fn flags(mut self, flags: ComboBoxFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    /// Sets the number of items visible without scrolling. Defaults to 8.
+    pub fn max_visible_items(mut self, max_visible_items: usize) -> Self {
+        self.max_visible_items = max_visible_items.max(1);
+        self
+    }
+    /// Builds the combo box.
+    ///
+    /// Returns true if an item was selected.
+    pub fn build(self) -> bool {
+        let FilteredCombo {
+            label,
+            current_item,
+            items,
+            label_fn,
+            hint,
+            preview,
+            mode,
+            mut flags,
+            max_visible_items,
+            ui,
+        } = self;
+        if preview.is_some() {
+            flags |= ComboBoxFlags::CUSTOM_PREVIEW;
+        }
+        // Dear ImGui asserts that custom previews have no preview value
+        let custom_preview = flags.contains(ComboBoxFlags::CUSTOM_PREVIEW);
+        let preview_value = match custom_preview {
+            true => None,
+            false => items.get(*current_item).map(&label_fn),
+        };
+        let combo = ComboBox {
+            label,
+            preview_value,
+            flags,
+            ui,
+        }
+        .begin();
+        let changed = match combo {
+            Some(combo) => {
+                let list = FilteredList {
+                    items,
+                    label_fn: &label_fn,
+                    mode,
+                    max_visible_items,
+                };
+                let changed = list.build(ui, current_item, hint.as_ref().map(AsRef::as_ref));
+                combo.end();
+                changed
+            }
+            None => false,
+        };
+        // The preview is drawn after the combo box is ended
+        if let (Some(preview), Some(item)) = (preview, items.get(*current_item)) {
+            if let Some(_preview) = ui.begin_combo_preview() {
+                preview(ui, item);
+            }
+        }
+        changed
+    }
+}
+
+/// The contents of the popup of a [`FilteredCombo`]
+struct FilteredList<'a, V, L> {
+    items: &'a [V],
+    label_fn: &'a L,
+    mode: ComboFilterMode,
+    max_visible_items: usize,
+}
+
+impl<V, L> FilteredList<'_, V, L>
+where
+    for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+{
+    /// Builds the filter text input and the matching items, returning true if an item was
+    /// selected
+    fn build(self, ui: &Ui, current_item: &mut usize, hint: Option<&str>) -> bool {
+        let FilteredList {
+            items,
+            label_fn,
+            mode,
+            max_visible_items,
+        } = self;
+        let highlight_id = ui.new_id_str("##highlight");
+        let storage = unsafe { sys::igGetStateStorage() };
+        let appearing = ui.is_window_appearing();
+        let filter_id = ui.new_id_str("##filter");
+        let mut filter = ui
+            .widget_states
+            .take::<String>(filter_id)
+            .unwrap_or_default();
+        if appearing {
+            filter.clear();
+        }
+        let mut matches = matching_items(items, label_fn, mode, &filter);
+        let mut highlight = if appearing {
+            matches.iter().position(|&index| index == *current_item)
+        } else {
+            let highlight = unsafe { sys::ImGuiStorage_GetInt(storage, highlight_id.0, -1) };
+            usize::try_from(highlight).ok()
+        };
+        let mut scroll_to_highlight = appearing;
+
+        if appearing {
+            ui.set_keyboard_focus_here();
+        }
+        ui.set_next_item_width(-f32::MIN_POSITIVE);
+        let mut handler = Handler { direction: None };
+        let edited = {
+            let input = InputText::new(ui, "##filter", &mut filter)
+                .callback(InputTextCallback::HISTORY, &mut handler);
+            match hint {
+                Some(hint) => input.hint(hint).build(),
+                None => input.build(),
+            }
+        };
+        if edited {
+            matches = matching_items(items, label_fn, mode, &filter);
+            highlight = Some(0);
+            scroll_to_highlight = true;
+        }
+        if let Some(direction) = handler.direction {
+            let count = matches.len().max(1);
+            highlight = Some(match (direction, highlight) {
+                (HistoryDirection::Down, Some(index)) => (index + 1) % count,
+                (HistoryDirection::Down, None) => 0,
+                (HistoryDirection::Up, Some(index)) => (index + count - 1) % count,
+                (HistoryDirection::Up, None) => count - 1,
+            });
+            scroll_to_highlight = true;
+        }
+        let highlight = highlight.filter(|&index| index < matches.len());
+
+        let mut changed = false;
+        if let (Some(row), true) = (
+            highlight,
+            ui.is_key_pressed(Key::Enter) || ui.is_key_pressed(Key::KeypadEnter),
+        ) {
+            *current_item = matches[row];
+            changed = true;
+            ui.close_current_popup();
+        } else if matches.is_empty() {
+            ui.text_disabled("No matches");
+        } else {
+            let visible = matches.len().min(max_visible_items);
+            let height = visible as f32 * ui.text_line_height_with_spacing();
+            let list = ui
+                .child_window("##items")
+                .size([0.0, height])
+                .nav_inputs(false)
+                .begin();
+            if let Some(_list) = list {
+                let mut clipper = ListClipper::new(matches.len() as i32).begin(ui);
+                if let (Some(row), true) = (highlight, scroll_to_highlight) {
+                    clipper.include_item_by_index(row as i32);
+                }
+                for row in clipper.iter() {
+                    let row = row as usize;
+                    let index = matches[row];
+                    let _id = ui.push_id_usize(index);
+                    let highlighted = highlight == Some(row);
+                    let mut flags = SelectableFlags::empty();
+                    flags.set(SelectableFlags::HIGHLIGHT, highlighted);
+                    if ui
+                        .selectable_config(label_fn(&items[index]))
+                        .selected(index == *current_item)
+                        .flags(flags)
+                        .build()
+                    {
+                        *current_item = index;
+                        changed = true;
+                    }
+                    if highlighted && scroll_to_highlight {
+                        ui.set_scroll_here_y();
+                    }
+                }
+            }
+        }
+
+        let highlight = highlight.map_or(-1, |index| index as i32);
+        unsafe { sys::ImGuiStorage_SetInt(storage, highlight_id.0, highlight) };
+        ui.widget_states.insert(filter_id, filter);
+        changed
+    }
+}
+
+/// Returns the indices of the items matching `filter`, in display order
+fn matching_items<V, L>(
+    items: &[V],
+    label_fn: &L,
+    mode: ComboFilterMode,
+    filter: &str,
+) -> Vec<usize>
+where
+    for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+{
+    let labels = items.iter().map(label_fn).enumerate();
+    match mode {
+        ComboFilterMode::Fuzzy => {
+            let mut scored: Vec<(i32, usize)> = labels
+                .filter_map(|(index, label)| Some((fuzzy_score(&label, filter)?, index)))
+                .collect();
+            scored.sort_by_key(|&(score, _)| -score);
+            scored.into_iter().map(|(_, index)| index).collect()
+        }
+        ComboFilterMode::TextFilter => {
+            let mut filter = filter.to_owned();
+            filter.push('\0');
+            unsafe {
+                let text_filter = sys::ImGuiTextFilter_ImGuiTextFilter(filter.as_ptr().cast());
+                let matches = labels
+                    .filter(|(_, label)| {
+                        let range = label.as_bytes().as_ptr_range();
+                        sys::ImGuiTextFilter_PassFilter(
+                            text_filter,
+                            range.start.cast(),
+                            range.end.cast(),
+                        )
+                    })
+                    .map(|(index, _)| index)
+                    .collect();
+                sys::ImGuiTextFilter_destroy(text_filter);
+                matches
+            }
+        }
+    }
+}
+
+/// Records the arrow keys pressed in the filter text input
+struct Handler {
+    direction: Option<HistoryDirection>,
+}
+
+impl InputTextCallbackHandler for &mut Handler {
+    fn on_history(&mut self, direction: HistoryDirection, _: TextCallbackData) {
+        self.direction = Some(direction);
+    }
+}
+
+/// Returns how closely `text` matches `pattern`, or `None` if it doesn't contain the
+/// characters of `pattern` in order, ignoring case and whitespace in `pattern`
+fn fuzzy_score(text: &str, pattern: &str) -> Option<i32> {
+    let mut pattern = pattern.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+    for c in text.chars() {
+        let Some(&p) = pattern.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(p.to_lowercase()) {
+            pattern.next();
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            let word_start = previous.is_none_or(|previous| {
+                !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase())
+            });
+            if word_start {
+                score += 10;
+            }
+            previous_matched = true;
+        } else {
+            // Skipped characters lower the score
+            score -= 1;
+            previous_matched = false;
+        }
+        previous = Some(c);
+    }
+    pattern.peek().is_none().then_some(score)
+}
+
+impl Ui {
+    /// Creates a builder for a combo box choosing from a slice of values, with a text input
+    /// filtering the items at the top of its popup.
+    ///
+    /// See [`Ui::combo`] for the version without a filter.
+    #[doc(alias = "Combo")]
+    pub fn filtered_combo<'ui, 'a, Label, V, L>(
+        &'ui self,
+        label: Label,
+        current_item: &'a mut usize,
+        items: &'a [V],
+        label_fn: L,
+    ) -> FilteredCombo<'ui, 'a, Label, V, L>
+    where
+        Label: AsRef<str>,
+        for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    {
+        FilteredCombo {
+            label,
+            current_item,
+            items,
+            label_fn,
+            hint: None,
+            preview: None,
+            mode: ComboFilterMode::Fuzzy,
+            flags: ComboBoxFlags::empty(),
+            max_visible_items: 8,
+            ui: self,
+        }
+    }
+}
+
+#[cfg(test)]
+const COLORS: [&str; 5] = ["Red", "Green", "Blue", "Dark green", "Grey"];
+
+#[test]
+fn test_fuzzy_score() {
+    assert_eq!(fuzzy_score("Dark green", ""), Some(0));
+    assert_eq!(fuzzy_score("Dark green", "xyz"), None);
+    assert_eq!(fuzzy_score("Green", "rg"), None);
+    // Consecutive characters starting a word rank higher than scattered ones
+    let green = fuzzy_score("Green", "gre").unwrap();
+    let grey = fuzzy_score("Grey", "gre").unwrap();
+    let dark_green = fuzzy_score("Dark green", "gre").unwrap();
+    let darker = fuzzy_score("Dark red", "dre").unwrap();
+    assert_eq!(green, grey);
+    assert!(green > dark_green);
+    assert!(dark_green > darker);
+    assert!(fuzzy_score("DarkGreen", "dg") > fuzzy_score("Dodge", "dg"));
+}
+
+#[test]
+fn test_filtered_combo() {
+    use crate::test::{click, press_keys, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut current = 0;
+    let frame = |ctx: &mut crate::Context, current: &mut usize, mode| {
+        test_frame(ctx, [300.0, 300.0], |ui| {
+            ui.filtered_combo("color", current, &COLORS, |c| Cow::Borrowed(*c))
+                .mode(mode)
+                .build()
+        })
+    };
+    let open = |ctx: &mut crate::Context, current: &mut usize, mode| {
+        click(ctx, [20.0, 30.0], |ctx| frame(ctx, current, mode));
+        frame(ctx, current, mode);
+    };
+    let press = |ctx: &mut crate::Context, current: &mut usize, mode, key| {
+        let mut changed = false;
+        press_keys(ctx, &[key], |ctx| changed |= frame(ctx, current, mode));
+        changed
+    };
+
+    frame(&mut ctx, &mut current, ComboFilterMode::Fuzzy);
+    open(&mut ctx, &mut current, ComboFilterMode::Fuzzy);
+    // The filter input is focused: "gre" matches Green, Grey then Dark green
+    for c in "gre".chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    assert!(!frame(&mut ctx, &mut current, ComboFilterMode::Fuzzy));
+    assert!(!press(
+        &mut ctx,
+        &mut current,
+        ComboFilterMode::Fuzzy,
+        Key::UpArrow
+    ));
+    assert!(press(
+        &mut ctx,
+        &mut current,
+        ComboFilterMode::Fuzzy,
+        Key::Enter
+    ));
+    assert_eq!(COLORS[current], "Dark green");
+
+    // The filter is cleared when the popup opens again
+    open(&mut ctx, &mut current, ComboFilterMode::TextFilter);
+    for c in "-d,e".chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    frame(&mut ctx, &mut current, ComboFilterMode::TextFilter);
+    assert!(!press(
+        &mut ctx,
+        &mut current,
+        ComboFilterMode::TextFilter,
+        Key::DownArrow
+    ));
+    assert!(press(
+        &mut ctx,
+        &mut current,
+        ComboFilterMode::TextFilter,
+        Key::Enter
+    ));
+    assert_eq!(COLORS[current], "Blue");
+}
+
+#[test]
+fn test_filtered_combo_custom_preview() {
+    use crate::test::{click, press_keys, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut current = 0;
+    // Returns whether the selection changed and the previewed item
+    let frame = |ctx: &mut crate::Context, current: &mut usize| {
+        test_frame(ctx, [300.0, 300.0], |ui| {
+            let mut previewed = None;
+            let changed = ui
+                .filtered_combo("color", current, &COLORS, |c| Cow::Borrowed(*c))
+                .preview(|ui, color| {
+                    ui.text(color);
+                    previewed = Some(*color);
+                })
+                .build();
+            // Without a preview closure, only the arrow is drawn
+            ui.filtered_combo("other", &mut 1, &COLORS, |c| Cow::Borrowed(*c))
+                .flags(ComboBoxFlags::CUSTOM_PREVIEW)
+                .build();
+            (changed, previewed)
+        })
+    };
+
+    frame(&mut ctx, &mut current);
+    assert_eq!(frame(&mut ctx, &mut current), (false, Some("Red")));
+    click(&mut ctx, [20.0, 30.0], |ctx| frame(ctx, &mut current));
+    assert_eq!(frame(&mut ctx, &mut current), (false, Some("Red")));
+    for c in "blu".chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    frame(&mut ctx, &mut current);
+    let mut changed = Vec::new();
+    press_keys(&mut ctx, &[Key::Enter], |ctx| {
+        changed.push(frame(ctx, &mut current))
+    });
+    assert!(changed.contains(&(true, Some("Blue"))));
+    assert_eq!(COLORS[current], "Blue");
+    assert_eq!(frame(&mut ctx, &mut current), (false, Some("Blue")));
+}
@@ -0,0 +1,942 @@
+//! Display formats for numeric widgets.
+//!
+//! Sliders, drags and scalar inputs accept either a C printf format string, which is validated
+//! against the widget's [`DataType`], or a [`FormatWith`] closure that formats the value in
+//! Rust, e.g. to add units or use localized separators. [`Expressions`](crate::Expressions)
+//! additionally evaluate arithmetic expressions typed in the widget's text input.
+
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::{c_char, c_void};
+use std::{mem, ptr, slice};
+
+use crate::internal::{DataType, DataTypeKind};
+use crate::sys;
+use crate::Ui;
+
+/// Display format of a numeric widget.
+///
+/// Implemented for C printf format strings (any `AsRef<str>`), for [`FormatWith`] and for
+/// [`Expressions`](crate::Expressions).
+///
+/// Printf format strings must contain at most one conversion specification, matching the data
+/// type of the widget: one of `d`, `i`, `u`, `o`, `x` and `X` for integers, with `ll` (or
+/// `I64`) for 64-bit integers, and one of `f`, `F`, `e`, `E`, `g`, `G`, `a` and `A` for floats.
+/// Widgets with an invalid format string use the default format of their data type instead.
+pub trait DisplayFormat<T>: private::Sealed<T> {}
+
+impl<T, S: AsRef<str>> DisplayFormat<T> for S {}
+impl<T, S: AsRef<str>> private::Sealed<T> for S {
+    fn kind(&self) -> private::FormatKind<'_, T> {
+        private::FormatKind {
+            display: private::Display::Printf(self.as_ref()),
+            parse: None,
+        }
+    }
+}
+
+/// Formats the value of a numeric widget with a Rust closure instead of a printf format
+/// string, optionally parsing the text typed by the user with another closure.
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut weight = 12.5f32;
+/// ui.slider_config("Weight", 0.0, 100.0)
+///     .display_format(
+///         FormatWith::new(|kg| format!("{kg:.1} kg"))
+///             .parse_with(|text| text.trim().trim_end_matches("kg").trim().parse().ok()),
+///     )
+///     .build(&mut weight);
+/// ```
+///
+/// Without a parser, text entry (e.g. CTRL+Click on a slider) uses Dear ImGui's default
+/// number parsing. With one, the text field starts with the formatted value and every edit is
+/// parsed with it; text that doesn't parse leaves the value unchanged.
+#[derive(Copy, Clone)]
+pub struct FormatWith<F, P> {
+    format: F,
+    parse: Option<P>,
+}
+
+impl<T, F> FormatWith<F, fn(&str) -> Option<T>>
+where
+    F: Fn(T) -> String,
+{
+    /// Formats values with `format`
+    pub fn new(format: F) -> Self {
+        FormatWith {
+            format,
+            parse: None,
+        }
+    }
+}
+
+impl<F, P> FormatWith<F, P> {
+    /// Parses the text typed by the user with `parse`, which returns None for invalid text
+    pub fn parse_with<T, P2>(self, parse: P2) -> FormatWith<F, P2>
+    where
+        P2: Fn(&str) -> Option<T>,
+    {
+        FormatWith {
+            format: self.format,
+            parse: Some(parse),
+        }
+    }
+}
+
+impl<F, P> fmt::Debug for FormatWith<F, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormatWith")
+            .field("parse", &self.parse.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F, P> DisplayFormat<T> for FormatWith<F, P>
+where
+    F: Fn(T) -> String,
+    P: Fn(&str) -> Option<T>,
+{
+}
+impl<T, F, P> private::Sealed<T> for FormatWith<F, P>
+where
+    F: Fn(T) -> String,
+    P: Fn(&str) -> Option<T>,
+{
+    fn kind(&self) -> private::FormatKind<'_, T> {
+        private::FormatKind {
+            display: private::Display::With(&self.format),
+            parse: self
+                .parse
+                .as_ref()
+                .map(|parse| parse as &dyn private::TextParser<T>),
+        }
+    }
+}
+
+pub(crate) mod private {
+    pub enum Display<'a, T> {
+        Printf(&'a str),
+        With(&'a dyn Fn(T) -> String),
+    }
+
+    pub struct FormatKind<'a, T> {
+        pub display: Display<'a, T>,
+        pub parse: Option<&'a dyn TextParser<T>>,
+    }
+
+    /// Parses the text typed in a numeric widget
+    pub trait TextParser<T> {
+        /// Parses `text`, `initial` being the value when the text input was activated.
+        ///
+        /// Errors with a message are shown in a tooltip.
+        fn parse_text(&self, text: &str, initial: T) -> Result<T, Option<String>>;
+        /// Whether the parsed values are always clamped to the range of drags and sliders
+        fn clamps_to_range(&self) -> bool {
+            false
+        }
+    }
+
+    impl<T, P: Fn(&str) -> Option<T>> TextParser<T> for P {
+        fn parse_text(&self, text: &str, _initial: T) -> Result<T, Option<String>> {
+            self(text).ok_or(None)
+        }
+    }
+
+    pub trait Sealed<T> {
+        fn kind(&self) -> FormatKind<'_, T>;
+    }
+}
+
+use self::private::{Display, FormatKind, TextParser};
+
+/// Returns the printf format to pass to Dear ImGui's widgets, after validating it, or the
+/// format itself if it needs the Rust implementation of the widget
+pub(crate) fn native_format<T: DataTypeKind>(
+    format: Option<FormatKind<'_, T>>,
+) -> Result<Option<&str>, FormatKind<'_, T>> {
+    match format {
+        None => Ok(None),
+        Some(FormatKind {
+            display: Display::Printf(format),
+            parse: None,
+        }) => Ok(Some(valid_format(format, T::KIND))),
+        Some(format) => Err(format),
+    }
+}
+
+/// Returns `format` if it's a valid printf format string for values of type `data_type`, or
+/// the default format of the type otherwise, as passing an invalid format to printf is undefined
+/// behaviour
+pub(crate) fn valid_format(format: &str, data_type: DataType) -> &str {
+    match validate_format(format, data_type) {
+        Ok(()) => format,
+        Err(_) => default_format(data_type).to_str().unwrap(),
+    }
+}
+
+fn validate_format(format: &str, data_type: DataType) -> Result<(), &'static str> {
+    let bytes = format.as_bytes();
+    let mut conversion_found = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            i += 1;
+            continue;
+        }
+        i += 1;
+        if bytes.get(i) == Some(&b'%') {
+            i += 1;
+            continue;
+        }
+        if conversion_found {
+            return Err("more than one conversion specification");
+        }
+        conversion_found = true;
+
+        while let Some(b'-' | b'+' | b' ' | b'#' | b'0' | b'\'') = bytes.get(i) {
+            i += 1;
+        }
+        while let Some(b'0'..=b'9') = bytes.get(i) {
+            i += 1;
+        }
+        if bytes.get(i) == Some(&b'.') {
+            i += 1;
+            while let Some(b'0'..=b'9') = bytes.get(i) {
+                i += 1;
+            }
+        }
+        if bytes.get(i) == Some(&b'*') {
+            return Err("`*` width or precision is not supported");
+        }
+        let length_start = i;
+        while let Some(b'h' | b'l' | b'L' | b'q' | b'j' | b'z' | b't' | b'w' | b'I') = bytes.get(i)
+        {
+            i += 1;
+            if bytes[i - 1] == b'I' {
+                while let Some(b'0'..=b'9') = bytes.get(i) {
+                    i += 1;
+                }
+            }
+        }
+        let length = &format[length_start..i];
+        let Some(&conversion) = bytes.get(i) else {
+            return Err("incomplete conversion specification");
+        };
+        i += 1;
+
+        match data_type {
+            DataType::F32 | DataType::F64 => {
+                if !b"fFeEgGaA".contains(&conversion) {
+                    return Err("floats need one of the f, F, e, E, g, G, a or A conversions");
+                }
+                if !matches!(length, "" | "l") {
+                    return Err("unsupported length modifier for a float");
+                }
+            }
+            DataType::I64 | DataType::U64 => {
+                if !b"diuoxX".contains(&conversion) {
+                    return Err("integers need one of the d, i, u, o, x or X conversions");
+                }
+                if !matches!(length, "ll" | "I64") {
+                    return Err("64-bit integers need the ll length modifier");
+                }
+            }
+            _ => {
+                if !b"diuoxX".contains(&conversion) {
+                    return Err("integers need one of the d, i, u, o, x or X conversions");
+                }
+                if !matches!(length, "" | "h" | "hh" | "I32") {
+                    return Err("unsupported length modifier for an integer of 32 bits or less");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Dear ImGui's default format for `data_type`
+pub(crate) fn default_format(data_type: DataType) -> &'static CStr {
+    match data_type {
+        DataType::I8 | DataType::I16 | DataType::I32 => c"%d",
+        DataType::U8 | DataType::U16 | DataType::U32 => c"%u",
+        DataType::I64 => c"%lld",
+        DataType::U64 => c"%llu",
+        DataType::F32 => c"%.3f",
+        DataType::F64 => c"%f",
+    }
+}
+
+/// Escapes pre-formatted text so that it's displayed as is when used as a format string
+fn escape(text: &str) -> Cow<'_, str> {
+    if text.contains('%') {
+        Cow::Owned(text.replace('%', "%%"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn same_bytes<T: DataTypeKind>(a: &T, b: &T) -> bool {
+    unsafe {
+        slice::from_raw_parts(a as *const T as *const u8, mem::size_of::<T>())
+            == slice::from_raw_parts(b as *const T as *const u8, mem::size_of::<T>())
+    }
+}
+
+fn opt_ptr<T>(value: &Option<T>) -> *const c_void {
+    value
+        .as_ref()
+        .map_or(ptr::null(), |value| value as *const T as *const c_void)
+}
+
+/// Bounds of the values typed in the CTRL+Click text input of a slider or drag
+pub(crate) struct InputClamp<T> {
+    pub(crate) min: Option<T>,
+    pub(crate) max: Option<T>,
+    /// Whether `ImGuiSliderFlags_ClampOnInput` is set
+    pub(crate) on_input: bool,
+}
+
+impl<T: DataTypeKind> InputClamp<T> {
+    /// The bounds of a slider
+    pub(crate) fn slider(flags: u32, min: T, max: T) -> Self {
+        InputClamp {
+            min: Some(min),
+            max: Some(max),
+            on_input: flags & sys::ImGuiSliderFlags_ClampOnInput != 0,
+        }
+    }
+
+    /// The bounds of Dear ImGui's `DragScalar`, where a `min >= max` range means unbounded
+    /// unless the range is [0, 0] and `ImGuiSliderFlags_ClampZeroRange` is set
+    pub(crate) fn drag(flags: u32, min: Option<T>, max: Option<T>) -> Self {
+        let bounded = match (&min, &max) {
+            (None, None) => false,
+            (Some(min), Some(max)) => match unsafe {
+                sys::igDataTypeCompare(
+                    T::KIND as i32,
+                    min as *const T as *const c_void,
+                    max as *const T as *const c_void,
+                )
+            } {
+                dir if dir < 0 => true,
+                0 => {
+                    let is_zero = unsafe {
+                        slice::from_raw_parts(min as *const T as *const u8, mem::size_of::<T>())
+                            .iter()
+                            .all(|&byte| byte == 0)
+                    };
+                    !is_zero || flags & sys::ImGuiSliderFlags_ClampZeroRange != 0
+                }
+                _ => false,
+            },
+            _ => true,
+        };
+        let on_input = flags & sys::ImGuiSliderFlags_ClampOnInput != 0;
+        if bounded {
+            InputClamp { min, max, on_input }
+        } else {
+            InputClamp {
+                min: None,
+                max: None,
+                on_input,
+            }
+        }
+    }
+}
+
+/// # Text input of numeric widgets
+impl Ui {
+    /// Remembers the value of the numeric widget whose text input was just activated
+    fn set_text_input_initial<T: DataTypeKind>(&self, id: sys::ImGuiID, value: T) {
+        let mut bytes = [0; 8];
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &value as *const T as *const u8,
+                bytes.as_mut_ptr(),
+                mem::size_of::<T>(),
+            );
+        }
+        self.text_input_initial.set(Some((id, bytes)));
+    }
+
+    /// Returns the value the numeric widget had when its text input was activated, or `current`
+    /// if unknown
+    fn text_input_initial<T: DataTypeKind>(&self, id: sys::ImGuiID, current: T) -> T {
+        match self.text_input_initial.get() {
+            Some((initial_id, bytes)) if initial_id == id => unsafe {
+                ptr::read_unaligned(bytes.as_ptr() as *const T)
+            },
+            _ => current,
+        }
+    }
+
+    /// Remembers the error of the text typed in the numeric widget `id`, or forgets it if the
+    /// text is valid
+    fn set_text_input_error(&self, id: sys::ImGuiID, error: Option<String>) {
+        let mut current = self.text_input_error.borrow_mut();
+        match error {
+            Some(error) => *current = Some((id, error)),
+            None if matches!(&*current, Some((error_id, _)) if *error_id == id) => *current = None,
+            None => {}
+        }
+    }
+
+    /// Shows the error of the text typed in the numeric widget `id` in a tooltip while its text
+    /// input is `active`, and forgets it once the text input is deactivated
+    fn text_input_error_tooltip(&self, id: sys::ImGuiID, active: bool) {
+        let mut error = self.text_input_error.borrow_mut();
+        match &*error {
+            Some((error_id, message)) if *error_id == id => {
+                if active {
+                    self.tooltip_text(message);
+                } else {
+                    *error = None;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parses the text typed in the numeric widget `id` into `value`, clamping it to `clamp`.
+///
+/// Returns true if the value changed. Parse errors with a message are kept for
+/// [`Ui::text_input_error_tooltip`].
+fn apply_text<T: DataTypeKind>(
+    ui: &Ui,
+    id: sys::ImGuiID,
+    text: &str,
+    value: &mut T,
+    initial: T,
+    parse: &dyn TextParser<T>,
+    clamp: Option<InputClamp<T>>,
+) -> bool {
+    let parsed = parse.parse_text(text, initial);
+    let mut new_value = match parsed {
+        Ok(new_value) => {
+            ui.set_text_input_error(id, None);
+            new_value
+        }
+        Err(error) => {
+            ui.set_text_input_error(id, error);
+            return false;
+        }
+    };
+    if let Some(clamp) = clamp {
+        if (clamp.on_input || parse.clamps_to_range())
+            && (clamp.min.is_some() || clamp.max.is_some())
+        {
+            unsafe {
+                sys::igDataTypeClamp(
+                    T::KIND as i32,
+                    &mut new_value as *mut T as *mut c_void,
+                    opt_ptr(&clamp.min),
+                    opt_ptr(&clamp.max),
+                );
+            }
+        }
+    }
+    if same_bytes(value, &new_value) {
+        return false;
+    }
+    *value = new_value;
+    true
+}
+
+/// Builds a single value slider or drag with an optional display format.
+///
+/// `raw` builds the widget with the given label and printf format pointers. `input_clamp` is
+/// None if the widget has no text input.
+pub(crate) fn build_scalar<T: DataTypeKind>(
+    ui: &Ui,
+    label: &str,
+    value: &mut T,
+    format: Option<FormatKind<'_, T>>,
+    input_clamp: Option<InputClamp<T>>,
+    raw: impl FnOnce(*const c_char, *const c_char, &mut T) -> bool,
+) -> bool {
+    let FormatKind { display, parse } = match native_format(format) {
+        Ok(format) => {
+            let (label, format) = ui.scratch_txt_with_opt(label, format);
+            return raw(label, format, value);
+        }
+        Err(format) => format,
+    };
+
+    // Text formatted in Rust is displayed as is
+    let (text, display_format) = match display {
+        Display::Printf(format) => (None, Cow::Borrowed(valid_format(format, T::KIND))),
+        Display::With(format) => {
+            let text = format(*value);
+            let display_format = escape(&text).into_owned();
+            (Some(text), Cow::Owned(display_format))
+        }
+    };
+    let (label_ptr, format_ptr) = ui.scratch_txt_two(label, display_format);
+    let (Some(parse), Some(input_clamp)) = (parse, input_clamp) else {
+        return raw(label_ptr, format_ptr, value);
+    };
+
+    unsafe {
+        let id = sys::igGetID_Str(label_ptr);
+        if sys::igTempInputIsActive(id) {
+            let text = text.as_deref().unwrap_or_default();
+            return temp_input(ui, label, label_ptr, id, value, text, parse, input_clamp);
+        }
+        ui.text_input_error_tooltip(id, false);
+        let changed = raw(label_ptr, format_ptr, value);
+        if sys::igTempInputIsActive(id) {
+            ui.set_text_input_initial(id, *value);
+            if text.is_some() {
+                // Dear ImGui just started the text input, with the value formatted by its
+                // default format: replace it with ours on the next frame
+                let state = sys::igGetInputTextState(id);
+                if !state.is_null() {
+                    sys::ImGuiInputTextState_ReloadUserBufAndSelectAll(state);
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// The CTRL+Click text input of a slider or drag, parsing the text with a custom parser.
+///
+/// `text` is the formatted value, or empty if the text input was started with Dear ImGui's
+/// formatting. This replaces the widget while the text input is active, mirroring the layout of Dear
+/// ImGui's `SliderScalar` and `DragScalar`.
+#[allow(clippy::too_many_arguments)]
+unsafe fn temp_input<T: DataTypeKind>(
+    ui: &Ui,
+    label: &str,
+    label_ptr: *const c_char,
+    id: sys::ImGuiID,
+    value: &mut T,
+    text: &str,
+    parse: &dyn TextParser<T>,
+    clamp: InputClamp<T>,
+) -> bool {
+    let style = &*sys::igGetStyle();
+    let width = sys::igCalcItemWidth();
+    let label_size = ui.calc_text_size_with_opts(label, true, -1.0);
+    let pos = ui.cursor_screen_pos();
+    let frame_bb = sys::ImRect {
+        Min: pos.into(),
+        Max: sys::ImVec2::new(
+            pos[0] + width,
+            pos[1] + label_size[1] + style.FramePadding.y * 2.0,
+        ),
+    };
+    let label_width = if label_size[0] > 0.0 {
+        style.ItemInnerSpacing.x + label_size[0]
+    } else {
+        0.0
+    };
+    let total_bb = sys::ImRect {
+        Min: frame_bb.Min,
+        Max: sys::ImVec2::new(frame_bb.Max.x + label_width, frame_bb.Max.y),
+    };
+    sys::igItemSize_Rect(total_bb, style.FramePadding.y);
+    if !sys::igItemAdd(
+        total_bb,
+        id,
+        &frame_bb,
+        sys::ImGuiItemFlags_Inputable as sys::ImGuiItemFlags,
+    ) {
+        return false;
+    }
+
+    let mut buf = text.trim().as_bytes().to_vec();
+    buf.resize(buf.len().max(64) + 64, 0);
+    let flags = sys::ImGuiInputTextFlags_AutoSelectAll | sys::ImGuiInputTextFlags_NoMarkEdited;
+    let edited = sys::igTempInputText(
+        frame_bb,
+        id,
+        label_ptr,
+        buf.as_mut_ptr() as *mut c_char,
+        buf.len() as i32,
+        flags as sys::ImGuiInputTextFlags,
+    );
+    let text = CStr::from_bytes_until_nul(&buf)
+        .ok()
+        .and_then(|text| text.to_str().ok());
+    let changed = match text {
+        Some(text) if edited => {
+            let initial = ui.text_input_initial(id, *value);
+            apply_text(ui, id, text, value, initial, parse, Some(clamp))
+        }
+        _ => false,
+    };
+    ui.text_input_error_tooltip(id, true);
+    if changed {
+        sys::igMarkItemEdited(id);
+    }
+    changed
+}
+
+/// Builds a horizontal array of single value widgets, laid out like Dear ImGui's
+/// `SliderScalarN` and `DragScalarN`
+pub(crate) fn build_array<T>(
+    ui: &Ui,
+    label: &str,
+    values: &mut [T],
+    mut build_one: impl FnMut(&mut T) -> bool,
+) -> bool {
+    let spacing = unsafe { (*sys::igGetStyle()).ItemInnerSpacing.x };
+    let count = values.len().max(1) as f32;
+    let full_width = ui.calc_item_width();
+    let width_one = ((full_width - spacing * (count - 1.0)) / count)
+        .trunc()
+        .max(1.0);
+    let width_last = (full_width - (width_one + spacing) * (count - 1.0))
+        .trunc()
+        .max(1.0);
+
+    let mut changed = false;
+    let _group = ui.begin_group();
+    {
+        let _id = ui.push_id(label);
+        let last = values.len().saturating_sub(1);
+        for (index, value) in values.iter_mut().enumerate() {
+            let _id = ui.push_id_usize(index);
+            if index > 0 {
+                ui.same_line_with_spacing(0.0, spacing);
+            }
+            ui.set_next_item_width(if index == last { width_last } else { width_one });
+            changed |= build_one(value);
+        }
+    }
+    let visible_label = label.split("##").next().unwrap_or_default();
+    if !visible_label.is_empty() {
+        ui.same_line_with_spacing(0.0, spacing);
+        ui.text(visible_label);
+    }
+    changed
+}
+
+/// Parses text with Dear ImGui's default number parsing
+struct DefaultParser;
+
+impl<T: DataTypeKind> TextParser<T> for DefaultParser {
+    fn parse_text(&self, text: &str, initial: T) -> Result<T, Option<String>> {
+        let text = CString::new(text).map_err(|_| None)?;
+        let mut value = initial;
+        unsafe {
+            sys::igDataTypeApplyFromText(
+                text.as_ptr(),
+                T::KIND as i32,
+                &mut value as *mut T as *mut c_void,
+                default_format(T::KIND).as_ptr(),
+                ptr::null_mut(),
+            );
+        }
+        Ok(value)
+    }
+}
+
+/// Formats `value` with a printf format string
+fn format_printf<T: DataTypeKind>(value: &T, format: &str) -> String {
+    let format = CString::new(format).unwrap_or_default();
+    let mut buf = [0u8; 64];
+    unsafe {
+        sys::igDataTypeFormatString(
+            buf.as_mut_ptr() as *mut c_char,
+            buf.len() as i32,
+            T::KIND as i32,
+            value as *const T as *const c_void,
+            format.as_ptr(),
+        );
+    }
+    CStr::from_bytes_until_nul(&buf)
+        .ok()
+        .and_then(|text| text.to_str().ok())
+        .unwrap_or_default()
+        .to_owned()
+}
+
+/// Clamps a float stepped by the buttons of a scalar input to the finite values of its type, as
+/// typed values are. Dear ImGui already saturates integer steps to the range of their type.
+fn clamp_to_finite<T: DataTypeKind>(value: &mut T) {
+    let ptr = value as *mut T;
+    // Safety: `DataTypeKind` guarantees that `T` has the layout of its `KIND`
+    unsafe {
+        match T::KIND {
+            DataType::F32 => *(ptr as *mut f32) = (*(ptr as *mut f32)).clamp(f32::MIN, f32::MAX),
+            DataType::F64 => *(ptr as *mut f64) = (*(ptr as *mut f64)).clamp(f64::MIN, f64::MAX),
+            _ => {}
+        }
+    }
+}
+
+/// Builds a scalar input whose text is formatted or parsed in Rust.
+///
+/// This mirrors Dear ImGui's `InputScalar`, with the step buttons when `step` is set.
+pub(crate) fn build_input_scalar<T: DataTypeKind>(
+    ui: &Ui,
+    label: &str,
+    value: &mut T,
+    step: Option<T>,
+    step_fast: Option<T>,
+    format: FormatKind<'_, T>,
+    flags: crate::InputTextFlags,
+) -> bool {
+    let flags =
+        flags | crate::InputTextFlags::AUTO_SELECT_ALL | crate::InputTextFlags::CALLBACK_RESIZE;
+    let mut text = match format.display {
+        Display::Printf(display_format) => {
+            format_printf(value, valid_format(display_format, T::KIND))
+        }
+        Display::With(format) => format(*value),
+    };
+    let parse = format.parse.unwrap_or(&DefaultParser);
+    let mut input_text = |label: &str, value: &mut T| -> bool {
+        let edited = ui.input_text(label, &mut text).flags(flags).build();
+        let id = unsafe { sys::igGetItemID() };
+        let active = ui.is_item_active();
+        if ui.is_item_activated() {
+            ui.set_text_input_initial(id, *value);
+        }
+        let initial = ui.text_input_initial(id, *value);
+        let changed = edited && apply_text(ui, id, &text, value, initial, parse, None);
+        ui.text_input_error_tooltip(id, active);
+        changed
+    };
+
+    let Some(step) = step else {
+        return input_text(label, value);
+    };
+
+    let style = unsafe { &*sys::igGetStyle() };
+    let button_size = ui.frame_height();
+    let spacing = style.ItemInnerSpacing.x;
+    let mut changed = false;
+    let group = ui.begin_group();
+    {
+        let _id = ui.push_id(label);
+        ui.set_next_item_width((ui.calc_item_width() - (button_size + spacing) * 2.0).max(1.0));
+        changed |= input_text("", value);
+
+        let _padding = ui.push_style_var(crate::StyleVar::FramePadding([
+            style.FramePadding.y,
+            style.FramePadding.y,
+        ]));
+        let _repeat = ui.push_item_flag(crate::ItemFlag::BUTTON_REPEAT, true);
+        let _disabled = ui.begin_disabled(flags.contains(crate::InputTextFlags::READ_ONLY));
+        let step = match step_fast {
+            Some(step_fast) if ui.io().key_ctrl => step_fast,
+            _ => step,
+        };
+        for op in ['-', '+'] {
+            ui.same_line_with_spacing(0.0, spacing);
+            if ui.button_with_size(op.to_string(), [button_size, button_size]) {
+                let mut new_value = *value;
+                unsafe {
+                    sys::igDataTypeApplyOp(
+                        T::KIND as i32,
+                        op as i32,
+                        &mut new_value as *mut T as *mut c_void,
+                        value as *const T as *const c_void,
+                        &step as *const T as *const c_void,
+                    );
+                }
+                clamp_to_finite(&mut new_value);
+                if !same_bytes(value, &new_value) {
+                    *value = new_value;
+                    changed = true;
+                }
+            }
+        }
+    }
+    let visible_label = label.split("##").next().unwrap_or_default();
+    if !visible_label.is_empty() {
+        ui.same_line_with_spacing(0.0, spacing);
+        ui.text(visible_label);
+    }
+    group.end();
+    if changed {
+        unsafe { sys::igMarkItemEdited(sys::igGetItemID()) };
+    }
+    changed
+}
+
+#[test]
+fn test_validate_format() {
+    let valid = [
+        ("%d", DataType::I32),
+        ("%5.2x items", DataType::U8),
+        ("%hhd", DataType::I8),
+        ("value: %lld%%", DataType::I64),
+        ("%I64u", DataType::U64),
+        ("%.3f", DataType::F32),
+        ("%+08.2lf m/s", DataType::F64),
+        ("%g%%", DataType::F32),
+        ("no conversion", DataType::I32),
+        ("", DataType::F64),
+    ];
+    for (format, data_type) in valid {
+        assert_eq!(validate_format(format, data_type), Ok(()), "{format}");
+    }
+    let invalid = [
+        ("%s", DataType::I32),
+        ("%f", DataType::I32),
+        ("%d", DataType::F32),
+        ("%d", DataType::I64),
+        ("%ld", DataType::I32),
+        ("%Lf", DataType::F64),
+        ("%d %d", DataType::I32),
+        ("%*d", DataType::I32),
+        ("%.*f", DataType::F32),
+        ("%n", DataType::I32),
+        ("%p", DataType::U64),
+        ("50%", DataType::I32),
+    ];
+    for (format, data_type) in invalid {
+        assert!(validate_format(format, data_type).is_err(), "{format}");
+    }
+}
+
+#[test]
+fn test_invalid_format_fallback() {
+    assert_eq!(valid_format("%.1f ms", DataType::F32), "%.1f ms");
+    assert_eq!(valid_format("%s", DataType::F32), "%.3f");
+    assert_eq!(valid_format("%.0f", DataType::I32), "%d");
+    assert_eq!(valid_format("%d", DataType::U64), "%llu");
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    let mut value = 0.0f32;
+    ui.slider_config("slider", 0.0, 1.0)
+        .display_format("%s")
+        .build(&mut value);
+    let mut value = 5;
+    ui.slider_config("int slider", 0, 10)
+        .display_format("%.0f")
+        .build(&mut value);
+    crate::Drag::new("int drag")
+        .display_format("%.0f")
+        .build(ui, &mut value);
+    ctx.render();
+}
+
+#[test]
+fn test_input_scalar_long_text() {
+    use crate::test::{click, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let format = FormatWith::new(|m: f64| format!("{m} m"))
+        .parse_with(|text| text.strip_suffix(" m")?.trim().parse().ok());
+    let mut value = 1.0f64;
+    let mut frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [300.0, 100.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            ui.input_scalar("value", &mut value)
+                .display_format(format)
+                .build();
+        })
+    };
+
+    // The text isn't limited to the length of the formatted value
+    frame(&mut ctx);
+    click(&mut ctx, [20.0, 45.0], &mut frame);
+    for c in "1234567.125 m".chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    frame(&mut ctx);
+    assert_eq!(value, 1234567.125);
+}
+
+#[test]
+fn test_input_scalar_step_clamp() {
+    use crate::test::{click, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut value = f32::MAX;
+    let mut changed = false;
+    let mut frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [300.0, 100.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            ui.set_next_item_width(200.0);
+            changed = ui
+                .input_scalar("##value", &mut value)
+                .step(f32::MAX)
+                .expressions(crate::Expressions::new())
+                .build();
+            ui.item_rect_max()
+        })
+    };
+
+    // The "+" button is the last one, at the right of the input
+    let [max_x, max_y] = frame(&mut ctx);
+    let plus = [max_x - 5.0, max_y - 5.0];
+    click(&mut ctx, plus, &mut frame);
+    assert_eq!(value, f32::MAX);
+    assert!(!changed);
+}
+
+#[test]
+fn test_format_with() {
+    use crate::test::{press, press_keys, release, test_frame};
+    use crate::Key;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let format = FormatWith::new(|percent: f32| format!("{percent:.0}% done"))
+        .parse_with(|text| text.strip_suffix("% done")?.trim().parse().ok());
+    let mut value = 25.0f32;
+    let mut frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [300.0, 100.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            ui.slider_config("percent", 0.0, 100.0)
+                .display_format(format)
+                .build(&mut value);
+        })
+    };
+
+    // CTRL+Click the slider to start typing
+    frame(&mut ctx);
+    ctx.io_mut().add_key_event(Key::ModCtrl, true);
+    press(&mut ctx, [20.0, 45.0], &mut frame);
+    ctx.io_mut().add_key_event(Key::ModCtrl, false);
+    release(&mut ctx, &mut frame);
+    // The text field starts with the formatted value: "25% done" becomes "125% done"
+    press_keys(&mut ctx, &[Key::Home], &mut frame);
+    ctx.io_mut().add_input_character('1');
+    frame(&mut ctx);
+    frame(&mut ctx);
+    assert_eq!(value, 125.0);
+}
+
+#[test]
+fn test_format_with_builders() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let meters = FormatWith::new(|m: f64| format!("{m:.2} m"));
+    let mut value = 1.5f64;
+    let mut values = [1u32, 2, 3];
+    let ui = ctx.new_frame();
+    ui.window("builders").build(|| {
+        assert!(!ui
+            .input_scalar("input", &mut value)
+            .display_format(meters)
+            .build());
+        assert!(!ui
+            .input_scalar("input with steps", &mut value)
+            .display_format(meters)
+            .step(0.5)
+            .build());
+        assert!(!ui
+            .input_scalar("expressions", &mut value)
+            .expressions(crate::Expressions::new().display_format("%.2f m"))
+            .build());
+        assert!(!crate::Drag::new("drag")
+            .display_format(FormatWith::new(|v: f64| format!("{v:.1}%")))
+            .build(ui, &mut value));
+        assert!(!ui
+            .slider_config("array", 0, 10)
+            .display_format(FormatWith::new(|v: u32| format!("#{v}")))
+            .build_array(&mut values));
+    });
+    ctx.render();
+}
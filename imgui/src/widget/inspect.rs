@@ -0,0 +1,581 @@
+//! A property inspector, which shows and edits the fields of a value in a table.
+//!
+//! Requires the `tables-api` feature, which the `derive` feature enables.
+//!
+//! Types implementing [`Inspect`] add rows to the two-column table of [`Ui::inspect`], with a
+//! label in the first column and a widget in the second one. Nested values, e.g. structs and
+//! `Vec`s, are collapsible tree nodes.
+//!
+//! With the `derive` feature, the trait can be derived for structs whose fields implement it, the
+//! `#[inspect(...)]` attribute of each field setting its [`InspectOptions`] or a custom widget:
+//!
+//! ```ignore
+//! use imgui::{AngleSlider, Inspect, Ui};
+//!
+//! #[derive(Inspect)]
+//! struct Light {
+//!     #[inspect(tooltip = "Shown in the scene tree")]
+//!     name: String,
+//!     #[inspect(color)]
+//!     color: [f32; 3],
+//!     #[inspect(range = 0.0..=10.0)]
+//!     intensity: f32,
+//!     #[inspect(speed = 0.1)]
+//!     position: mint::Vector3<f32>,
+//!     #[inspect(read_only)]
+//!     id: u64,
+//!     #[inspect(with = "angle_slider")]
+//!     spot_angle: f32,
+//!     #[inspect(skip)]
+//!     dirty: bool,
+//! }
+//!
+//! fn angle_slider(ui: &Ui, id: &str, value: &mut f32) -> bool {
+//!     AngleSlider::new(id).build(ui, value)
+//! }
+//! ```
+//!
+//! Otherwise it's implemented with [`Ui::inspect_row`] for values edited by a single widget, and
+//! [`Ui::inspect_node`] for values made of other ones:
+//!
+//! ```no_run
+//! # use imgui::*;
+//! struct Transform {
+//!     position: [f32; 3],
+//!     scale: f32,
+//! }
+//!
+//! impl Inspect for Transform {
+//!     fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+//!         ui.inspect_node(label, options, || {
+//!             let scale = InspectOptions {
+//!                 range: Some([0.1, 10.0]),
+//!                 ..*options
+//!             };
+//!             self.position.inspect(ui, "position", options) | self.scale.inspect(ui, "scale", &scale)
+//!         })
+//!     }
+//! }
+//!
+//! # let mut ctx = Context::create();
+//! # let ui = ctx.frame();
+//! let mut transform = Transform { position: [0.0; 3], scale: 1.0 };
+//! if ui.inspect("Transform", &mut transform) {
+//!     println!("The transform was edited");
+//! }
+//! ```
+
+use crate::math::{MintVec2, MintVec3, MintVec4};
+use crate::widget::custom_widget::visible_label;
+use crate::{
+    Drag, HoveredFlags, Id, ImColor32, TableColumnFlags, TableColumnSetup, TableFlags,
+    TreeNodeFlags, Ui,
+};
+
+/// How a value is shown in an inspector, set by the `#[inspect(...)]` attributes of the derive
+/// macro.
+///
+/// Containers such as `Vec` and `Option` pass the options of their range, speed, read-only and
+/// color to their items.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct InspectOptions {
+    /// The range of a number, edited with a slider instead of a drag unless a speed is set
+    pub range: Option<[f64; 2]>,
+    /// The change of a dragged number per pixel
+    pub speed: Option<f32>,
+    /// Shows the value without letting it be edited
+    pub read_only: bool,
+    /// The tooltip shown when the label is hovered
+    pub tooltip: Option<&'static str>,
+    /// Edits arrays and vectors of 3 or 4 floats as colors
+    pub color: bool,
+    /// Opens the tree node of a nested value the first time it's shown
+    pub default_open: bool,
+}
+
+impl InspectOptions {
+    /// Returns the options passed on to the items of a container
+    fn for_items(&self) -> Self {
+        InspectOptions {
+            tooltip: None,
+            default_open: false,
+            ..*self
+        }
+    }
+}
+
+/// A value which can be shown and edited in an inspector, see the
+/// [module documentation](crate::widget::inspect).
+///
+/// As `Option` has an inherent `inspect` method, options are inspected with
+/// `Inspect::inspect(&mut value, ...)`.
+pub trait Inspect {
+    /// Adds the rows showing the value to the current inspector table, with `label` in the first
+    /// column. Returns true if the value was edited.
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool;
+}
+
+impl Ui {
+    /// Shows and edits `value` in a two-column table of labels and widgets. Returns true if the
+    /// value was edited.
+    ///
+    /// `label` is the id of the table, and its visible part the label of the value, whose tree
+    /// node is open by default.
+    pub fn inspect<T: Inspect + ?Sized>(&self, label: impl AsRef<str>, value: &mut T) -> bool {
+        let label = label.as_ref();
+        let flags = TableFlags::RESIZABLE | TableFlags::BORDERS_INNER_V | TableFlags::ROW_BG;
+        let Some(_table) = self.begin_table_with_flags(label, 2, flags) else {
+            return false;
+        };
+        for (name, weight) in [("Property", 0.4), ("Value", 0.6)] {
+            self.table_setup_column_with(TableColumnSetup {
+                name,
+                flags: TableColumnFlags::WIDTH_STRETCH,
+                init_width_or_weight: weight,
+                user_id: Id::default(),
+            });
+        }
+        let options = InspectOptions {
+            default_open: true,
+            ..Default::default()
+        };
+        value.inspect(self, visible_label(label), &options)
+    }
+
+    /// Adds a row to the current inspector table, with `label` in the first column and the
+    /// widget built by `build` in the second one. Returns what `build` returns, i.e. true if the
+    /// value was edited.
+    ///
+    /// `build` is passed the label of the widget, which is hidden, and the widget fills the width
+    /// of the column. It's disabled if the value is read-only.
+    pub fn inspect_row(
+        &self,
+        label: &str,
+        options: &InspectOptions,
+        build: impl FnOnce(&str) -> bool,
+    ) -> bool {
+        self.inspect_label_cell(label, options);
+        let _id = self.push_id(label);
+        self.table_set_column_index(1);
+        self.set_next_item_width(-f32::MIN_POSITIVE);
+        let _disabled = self.begin_disabled(options.read_only);
+        build("##value")
+    }
+
+    /// Adds a row to the current inspector table with a tree node labeled `label`, under which
+    /// the rows added by `build` are shown when it's open. Returns what `build` returns, i.e.
+    /// true if the value was edited, or false if the node is closed.
+    pub fn inspect_node(
+        &self,
+        label: &str,
+        options: &InspectOptions,
+        build: impl FnOnce() -> bool,
+    ) -> bool {
+        self.inspect_node_with_text(label, label, options, build)
+    }
+
+    fn inspect_node_with_text(
+        &self,
+        label: &str,
+        text: &str,
+        options: &InspectOptions,
+        build: impl FnOnce() -> bool,
+    ) -> bool {
+        self.table_next_row();
+        self.table_set_column_index(0);
+        self.align_text_to_frame_padding();
+        let mut flags = TreeNodeFlags::SPAN_FULL_WIDTH;
+        flags.set(TreeNodeFlags::DEFAULT_OPEN, options.default_open);
+        let node = self
+            .tree_node_config(label)
+            .label::<&str, _>(text)
+            .flags(flags)
+            .push();
+        self.inspect_tooltip(options);
+        match node {
+            Some(_node) => build(),
+            None => false,
+        }
+    }
+
+    fn inspect_label_cell(&self, label: &str, options: &InspectOptions) {
+        self.table_next_row();
+        self.table_set_column_index(0);
+        self.align_text_to_frame_padding();
+        self.text(label);
+        self.inspect_tooltip(options);
+    }
+
+    fn inspect_tooltip(&self, options: &InspectOptions) {
+        if let Some(tooltip) = options.tooltip {
+            if self.is_item_hovered_with_flags(HoveredFlags::FOR_TOOLTIP) {
+                self.tooltip_text(tooltip);
+            }
+        }
+    }
+}
+
+impl Inspect for bool {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        ui.inspect_row(label, options, |id| ui.checkbox(id, self))
+    }
+}
+
+macro_rules! impl_inspect_number {
+    ($($ty:ty),*) => {$(
+        impl Inspect for $ty {
+            fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+                ui.inspect_row(label, options, |id| match (options.range, options.speed) {
+                    (Some([min, max]), None) => ui.slider(id, min as $ty, max as $ty, self),
+                    (range, speed) => {
+                        let mut drag = Drag::new(id).speed(speed.unwrap_or(1.0));
+                        if let Some([min, max]) = range {
+                            drag = drag.range(min as $ty, max as $ty);
+                        }
+                        drag.build(ui, self)
+                    }
+                })
+            }
+        }
+    )*};
+}
+
+impl_inspect_number!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64);
+
+/// Edits an array of floats with a drag per component, or a color editor
+fn inspect_floats<const N: usize>(
+    ui: &Ui,
+    label: &str,
+    options: &InspectOptions,
+    values: &mut [f32; N],
+) -> bool {
+    ui.inspect_row(label, options, |id| match N {
+        3 if options.color => {
+            let mut color = MintVec3::from_slice(values);
+            let edited = ui.color_edit3(id, &mut color);
+            values.copy_from_slice(&<[f32; 3]>::from(color));
+            edited
+        }
+        4 if options.color => {
+            let mut color = MintVec4::from_slice(values);
+            let edited = ui.color_edit4(id, &mut color);
+            values.copy_from_slice(&<[f32; 4]>::from(color));
+            edited
+        }
+        _ => {
+            let mut drag = Drag::new(id).speed(options.speed.unwrap_or(1.0));
+            if let Some([min, max]) = options.range {
+                drag = drag.range(min as f32, max as f32);
+            }
+            drag.build_array(ui, values)
+        }
+    })
+}
+
+impl<const N: usize> Inspect for [f32; N] {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        inspect_floats(ui, label, options, self)
+    }
+}
+
+macro_rules! impl_inspect_mint {
+    ($($ty:ty => $n:literal),*) => {$(
+        impl Inspect for $ty {
+            fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+                let mut values: [f32; $n] = (*self).into();
+                let edited = inspect_floats(ui, label, options, &mut values);
+                *self = values.into();
+                edited
+            }
+        }
+    )*};
+}
+
+impl_inspect_mint!(MintVec2 => 2, MintVec3 => 3, MintVec4 => 4);
+
+impl Inspect for ImColor32 {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        let mut color = self.to_rgba_f32s();
+        let edited = ui.inspect_row(label, options, |id| ui.color_edit4(id, &mut color));
+        *self = color.into();
+        edited
+    }
+}
+
+impl Inspect for String {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        ui.inspect_row(label, options, |id| ui.input_text(id, self).build())
+    }
+}
+
+impl<T: Inspect> Inspect for [T] {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        let text = format!("{} [{}]", label, self.len());
+        let items = options.for_items();
+        ui.inspect_node_with_text(label, &text, options, || {
+            let mut edited = false;
+            for (index, item) in self.iter_mut().enumerate() {
+                edited |= item.inspect(ui, &format!("[{}]", index), &items);
+            }
+            edited
+        })
+    }
+}
+
+impl<T: Inspect> Inspect for Vec<T> {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        self.as_mut_slice().inspect(ui, label, options)
+    }
+}
+
+/// A checkbox sets the option to `Some` default value or `None`, and the value is shown below it
+impl<T: Inspect + Default> Inspect for Option<T> {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        let mut is_some = self.is_some();
+        let toggled = ui.inspect_row(label, options, |id| ui.checkbox(id, &mut is_some));
+        if toggled {
+            *self = is_some.then(T::default);
+        }
+        let edited = match self {
+            Some(value) => {
+                let _id = ui.push_id(label);
+                ui.indent();
+                let edited = value.inspect(ui, "value", &options.for_items());
+                ui.unindent();
+                edited
+            }
+            None => false,
+        };
+        toggled || edited
+    }
+}
+
+impl<T: Inspect + ?Sized> Inspect for Box<T> {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        (**self).inspect(ui, label, options)
+    }
+}
+
+#[cfg(test)]
+struct Settings {
+    enabled: bool,
+    volume: f32,
+    name: String,
+    offsets: Vec<[f32; 2]>,
+    limit: Option<u32>,
+    /// The center of the checkbox of `enabled` in the last frame
+    checkbox: [f32; 2],
+}
+
+#[cfg(test)]
+impl Inspect for Settings {
+    fn inspect(&mut self, ui: &Ui, label: &str, options: &InspectOptions) -> bool {
+        ui.inspect_node(label, options, || {
+            let volume = InspectOptions {
+                range: Some([0.0, 1.0]),
+                ..*options
+            };
+            let mut edited = self.enabled.inspect(ui, "enabled", options);
+            let [min, max] = [ui.item_rect_min(), ui.item_rect_max()];
+            self.checkbox = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+            edited |= self.volume.inspect(ui, "volume", &volume);
+            edited |= self.name.inspect(ui, "name", options);
+            edited |= self.offsets.inspect(ui, "offsets", options);
+            // Options have an inherent `inspect` method taking precedence over the trait
+            edited |= Inspect::inspect(&mut self.limit, ui, "limit", options);
+            edited
+        })
+    }
+}
+
+#[test]
+fn test_inspect() {
+    use crate::test::{click, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let frame = |ctx: &mut crate::Context, settings: &mut Settings, read_only: bool| {
+        test_frame(ctx, [400.0, 400.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            let _disabled = ui.begin_disabled(read_only);
+            ui.inspect("Settings", settings)
+        })
+    };
+
+    let mut settings = Settings {
+        enabled: false,
+        volume: 0.5,
+        name: "default".into(),
+        offsets: vec![[0.0, 0.0]; 2],
+        limit: None,
+        checkbox: [0.0; 2],
+    };
+    // The root node is open by default, showing the checkbox of the first field
+    assert!(!frame(&mut ctx, &mut settings, false));
+    assert!(settings.checkbox[0] > 150.0 && settings.checkbox[1] > 40.0);
+    let checkbox = settings.checkbox;
+    assert!(click(&mut ctx, checkbox, |ctx| frame(
+        ctx,
+        &mut settings,
+        false
+    )));
+    assert!(settings.enabled);
+    assert!(!frame(&mut ctx, &mut settings, false));
+
+    // A disabled inspector can't be edited
+    assert!(!click(&mut ctx, checkbox, |ctx| frame(
+        ctx,
+        &mut settings,
+        true
+    )));
+    assert!(settings.enabled);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_inspect() {
+    use crate::Inspect;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ROWS: RefCell<Vec<(String, InspectOptions)>> = const { RefCell::new(Vec::new()) };
+    }
+    /// Records the labels and options it's inspected with
+    struct Probe;
+    impl Inspect for Probe {
+        fn inspect(&mut self, _: &Ui, label: &str, options: &InspectOptions) -> bool {
+            ROWS.with(|rows| rows.borrow_mut().push((label.to_owned(), *options)));
+            false
+        }
+    }
+    fn custom_widget(_: &Ui, id: &str, _: &mut Probe) -> bool {
+        let row = (format!("with {id}"), InspectOptions::default());
+        ROWS.with(|rows| rows.borrow_mut().push(row));
+        false
+    }
+
+    #[derive(Inspect)]
+    struct Inner {
+        probe: Probe,
+    }
+    #[derive(Inspect)]
+    struct Outer {
+        first: Probe,
+        #[inspect(label = "Second")]
+        second: Probe,
+        #[inspect(skip)]
+        #[allow(dead_code)]
+        skipped: Probe,
+        #[inspect(with = "custom_widget")]
+        custom: Probe,
+        #[inspect(open, read_only)]
+        inner: Inner,
+        #[inspect(range = 0.5..=2, speed = 0.25, tooltip = "Scale", color)]
+        options: Probe,
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut outer = Outer {
+        first: Probe,
+        second: Probe,
+        skipped: Probe,
+        custom: Probe,
+        inner: Inner { probe: Probe },
+        options: Probe,
+    };
+    crate::test::test_frame(&mut ctx, [400.0, 400.0], |ui| {
+        ui.inspect("Outer", &mut outer)
+    });
+    let rows = ROWS.take();
+    let labels: Vec<(&str, bool)> = rows
+        .iter()
+        .map(|(label, options)| (label.as_str(), options.read_only))
+        .collect();
+    // Skipped fields have no row, and read-only structs have read-only fields
+    assert_eq!(
+        labels,
+        [
+            ("first", false),
+            ("Second", false),
+            ("with ##value", false),
+            ("probe", true),
+            ("options", false)
+        ]
+    );
+    assert_eq!(rows[0].1, InspectOptions::default());
+    assert_eq!(
+        rows[4].1,
+        InspectOptions {
+            range: Some([0.5, 2.0]),
+            speed: Some(0.25),
+            read_only: false,
+            tooltip: Some("Scale"),
+            color: true,
+            default_open: false,
+        }
+    );
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_inspect_widgets() {
+    use crate::test::{click, press, release, test_frame};
+    use crate::Inspect;
+
+    /// Records the rectangle of the widget of the previous field
+    #[derive(Default)]
+    struct ItemRect([[f32; 2]; 2]);
+    impl Inspect for ItemRect {
+        fn inspect(&mut self, ui: &Ui, _: &str, _: &InspectOptions) -> bool {
+            self.0 = [ui.item_rect_min(), ui.item_rect_max()];
+            false
+        }
+    }
+
+    #[derive(Inspect)]
+    struct Numbers {
+        #[inspect(range = 0..=10)]
+        slider: i32,
+        slider_rect: ItemRect,
+        #[inspect(range = 0..=10, speed = 0.5)]
+        drag: i32,
+        drag_rect: ItemRect,
+        limit: Option<u32>,
+        limit_rect: ItemRect,
+    }
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let frame = |ctx: &mut crate::Context, numbers: &mut Numbers| {
+        test_frame(ctx, [400.0, 400.0], |ui| ui.inspect("Numbers", numbers))
+    };
+    // Clicks near the right end of a widget
+    let right_end = |ItemRect([min, max]): &ItemRect| [max[0] - 4.0, (min[1] + max[1]) / 2.0];
+
+    let mut numbers = Numbers {
+        slider: 5,
+        slider_rect: ItemRect::default(),
+        drag: 5,
+        drag_rect: ItemRect::default(),
+        limit: None,
+        limit_rect: ItemRect::default(),
+    };
+    assert!(!frame(&mut ctx, &mut numbers));
+    // A range makes a slider, which jumps to the clicked value
+    let slider = right_end(&numbers.slider_rect);
+    assert!(press(&mut ctx, slider, |ctx| frame(ctx, &mut numbers)));
+    release(&mut ctx, |ctx| frame(ctx, &mut numbers));
+    assert_eq!(numbers.slider, 10);
+    // With a speed, it makes a drag, which doesn't change when clicked
+    let drag = right_end(&numbers.drag_rect);
+    assert!(!press(&mut ctx, drag, |ctx| frame(ctx, &mut numbers)));
+    assert!(!release(&mut ctx, |ctx| frame(ctx, &mut numbers)));
+    assert_eq!(numbers.drag, 5);
+
+    // Checking the checkbox of an option sets it to the default value
+    let [min, max] = numbers.limit_rect.0;
+    let checkbox = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+    assert!(click(&mut ctx, checkbox, |ctx| frame(ctx, &mut numbers)));
+    assert_eq!(numbers.limit, Some(0));
+    assert!(!frame(&mut ctx, &mut numbers));
+    assert!(click(&mut ctx, checkbox, |ctx| frame(ctx, &mut numbers)));
+    assert_eq!(numbers.limit, None);
+}
@@ -0,0 +1,244 @@
+use std::f32::consts::PI;
+use std::os::raw::c_void;
+
+use crate::sys;
+use crate::widget::custom_widget::{visible_label, ButtonBehaviorFlags, CustomWidget, Rect};
+use crate::{ItemFlag, StyleColor, Ui};
+
+/// The angle of the minimum value, measured clockwise from the positive x axis
+const ANGLE_MIN: f32 = PI * 0.75;
+/// The angle of the maximum value
+const ANGLE_MAX: f32 = PI * 2.25;
+
+impl Ui {
+    /// Renders a rotary knob for a value between `min` and `max`, which is changed by dragging
+    /// the mouse up and down. Returns true if the value was changed.
+    pub fn knob(&self, label: impl AsRef<str>, min: f32, max: f32, value: &mut f32) -> bool {
+        self.knob_config(label, min, max).build(value)
+    }
+
+    /// Creates an unbuilt rotary knob
+    pub fn knob_config<Label: AsRef<str>>(
+        &self,
+        label: Label,
+        min: f32,
+        max: f32,
+    ) -> Knob<'_, Label> {
+        Knob {
+            label,
+            min,
+            max,
+            radius: 0.0,
+            speed: 0.0,
+            ui: self,
+        }
+    }
+}
+
+/// Builder for a rotary knob widget.
+///
+/// Unlike [`AngleSlider`](crate::AngleSlider), which edits an angle, the knob maps the range of
+/// any value to three quarters of a turn. The value is changed by dragging the knob vertically,
+/// or with the arrow keys when it's activated by keyboard navigation.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let mut gain = 0.5;
+/// ui.knob_config("Gain", 0.0, 1.0)
+///     .radius(24.0)
+///     .speed(0.002)
+///     .build(&mut gain);
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Knob<'ui, Label> {
+    label: Label,
+    min: f32,
+    max: f32,
+    radius: f32,
+    speed: f32,
+    ui: &'ui Ui,
+}
+
+impl<Label: AsRef<str>> Knob<'_, Label> {
+    /// Sets the range of the value
+    #[inline]
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+    /// Sets the radius of the knob. Zero, the default, uses the frame height.
+    #[inline]
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+    /// Sets how much the value changes per pixel that the mouse moves. Zero, the default,
+    /// covers the range in 200 pixels.
+    #[inline]
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Builds the knob bound to `value`. Returns true if the value was changed.
+    pub fn build(self, value: &mut f32) -> bool {
+        let ui = self.ui;
+        let style = unsafe { ui.style() };
+        let label = self.label.as_ref();
+        let text = visible_label(label);
+        let text_size = ui.calc_text_size(text);
+
+        let radius = if self.radius > 0.0 {
+            self.radius
+        } else {
+            ui.frame_height()
+        };
+        let pos = ui.cursor_screen_pos();
+        let knob = Rect::from_pos_size(pos, [radius * 2.0, radius * 2.0]);
+        let label_width = if text.is_empty() {
+            0.0
+        } else {
+            style.item_inner_spacing[0] + text_size[0]
+        };
+        let rect = Rect::from_pos_size(pos, [knob.width() + label_width, knob.height()]);
+
+        let id = ui.new_id_str(label);
+        ui.item_size(rect, -1.0);
+        if !ui.item_add(rect, id, Some(knob), ItemFlag::empty()) {
+            return false;
+        }
+        let widget = CustomWidget::new(ui, id, knob);
+        let state = widget.button_behavior(ButtonBehaviorFlags::empty());
+        let speed = if self.speed > 0.0 {
+            self.speed
+        } else {
+            (self.max - self.min) / 200.0
+        };
+        // The public drag behavior is horizontal, knobs are dragged vertically
+        let changed = unsafe {
+            sys::igDragBehavior(
+                id.0,
+                sys::ImGuiDataType_Float as i32,
+                value as *mut f32 as *mut c_void,
+                speed,
+                &self.min as *const f32 as *const c_void,
+                &self.max as *const f32 as *const c_void,
+                c"%.3f".as_ptr(),
+                (sys::ImGuiSliderFlags_Vertical | sys::ImGuiSliderFlags_AlwaysClamp)
+                    as sys::ImGuiSliderFlags,
+            )
+        };
+        if changed {
+            widget.mark_edited();
+        }
+
+        let active = ui.is_item_active();
+        let (grab_color, frame_color) = if active {
+            (StyleColor::SliderGrabActive, StyleColor::FrameBgActive)
+        } else if state.hovered {
+            (StyleColor::SliderGrab, StyleColor::FrameBgHovered)
+        } else {
+            (StyleColor::SliderGrab, StyleColor::FrameBg)
+        };
+        let fraction = if self.max > self.min {
+            ((*value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let angle = ANGLE_MIN + (ANGLE_MAX - ANGLE_MIN) * fraction;
+        let center = knob.center();
+        let thickness = (radius * 0.2).max(2.0);
+        let arc_radius = radius - thickness * 0.5;
+        let arc = |from: f32, to: f32| {
+            let segments = ((to - from) / (PI / 24.0)).ceil().max(1.0) as usize;
+            (0..=segments)
+                .map(|i| {
+                    let angle = from + (to - from) * i as f32 / segments as f32;
+                    [
+                        center[0] + angle.cos() * arc_radius,
+                        center[1] + angle.sin() * arc_radius,
+                    ]
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let draw_list = ui.get_window_draw_list();
+        widget.render_nav_highlight();
+        draw_list
+            .add_polyline(arc(ANGLE_MIN, ANGLE_MAX), ui.style_color(frame_color))
+            .thickness(thickness)
+            .build();
+        if fraction > 0.0 {
+            draw_list
+                .add_polyline(arc(ANGLE_MIN, angle), ui.style_color(grab_color))
+                .thickness(thickness)
+                .build();
+        }
+        let inner_radius = radius - thickness * 2.0;
+        draw_list
+            .add_circle(center, inner_radius, ui.style_color(frame_color))
+            .filled(true)
+            .build();
+        draw_list
+            .add_line(
+                [
+                    center[0] + angle.cos() * inner_radius * 0.3,
+                    center[1] + angle.sin() * inner_radius * 0.3,
+                ],
+                [
+                    center[0] + angle.cos() * inner_radius,
+                    center[1] + angle.sin() * inner_radius,
+                ],
+                ui.style_color(StyleColor::Text),
+            )
+            .thickness(thickness * 0.5)
+            .build();
+        if !text.is_empty() {
+            draw_list.add_text(
+                [
+                    knob.max[0] + style.item_inner_spacing[0],
+                    center[1] - text_size[1] * 0.5,
+                ],
+                ui.style_color(StyleColor::Text),
+                text,
+            );
+        }
+        changed
+    }
+}
+
+#[test]
+fn test_knob_vertical_drag() {
+    use crate::test::{press, release, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut value = 0.5f32;
+    let frame = |ctx: &mut crate::Context, value: &mut f32| {
+        test_frame(ctx, [200.0, 100.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            ui.knob_config("Gain", 0.0, 1.0)
+                .radius(20.0)
+                .speed(0.01)
+                .build(value);
+            ui.is_item_edited()
+        })
+    };
+
+    frame(&mut ctx, &mut value);
+    press(&mut ctx, [30.0, 60.0], |ctx| frame(ctx, &mut value));
+    // Dragging up increases the value, horizontal moves are ignored
+    ctx.io_mut().mouse_pos = [50.0, 40.0];
+    assert!(frame(&mut ctx, &mut value));
+    assert!((value - 0.7).abs() < 1e-4, "{value}");
+    // The value is clamped to the range
+    ctx.io_mut().mouse_pos = [50.0, -100.0];
+    frame(&mut ctx, &mut value);
+    assert_eq!(value, 1.0);
+    assert!(!release(&mut ctx, |ctx| frame(ctx, &mut value)));
+}
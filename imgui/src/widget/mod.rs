@@ -1,13 +1,31 @@
+pub mod autocomplete;
+pub mod code_editor;
 pub mod color_editors;
 pub mod combo_box;
+pub mod custom_widget;
 pub mod drag;
+pub mod enum_variants;
+pub mod expression;
+pub mod filtered_combo;
+pub mod format;
 pub mod image;
+#[cfg(feature = "tables-api")]
+pub mod inspect;
+pub mod knob;
 pub mod list_box;
 pub mod menu;
 pub mod misc;
+pub mod plot;
 pub mod progress_bar;
+pub mod radio_group;
+pub mod segmented_control;
 pub mod selectable;
 pub mod slider;
+pub mod spinner;
+pub mod splitter;
+pub(crate) mod state;
 pub mod tab;
 pub mod text;
+pub mod toggle;
 pub mod tree;
+pub mod validation;
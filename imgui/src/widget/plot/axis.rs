@@ -0,0 +1,133 @@
+//! Ranges and tick marks of the plot axes.
+
+/// The visible range of a plot axis
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PlotRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl PlotRange {
+    /// Creates a range. `min` and `max` are swapped if needed.
+    pub fn new(min: f64, max: f64) -> Self {
+        PlotRange {
+            min: min.min(max),
+            max: min.max(max),
+        }
+        .valid()
+    }
+
+    /// Returns the length of the range
+    pub fn size(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// Returns true if `value` is in the range
+    pub fn contains(&self, value: f64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    /// Expands empty or non-finite ranges so that they can be drawn
+    fn valid(self) -> Self {
+        if !self.min.is_finite() || !self.max.is_finite() {
+            return PlotRange { min: 0.0, max: 1.0 };
+        }
+        if self.size() < f64::EPSILON * self.max.abs().max(1.0) {
+            let half = (self.max.abs() * 0.1).max(0.5);
+            return PlotRange {
+                min: self.min - half,
+                max: self.max + half,
+            };
+        }
+        self
+    }
+
+    /// Scales the range by `factor` around `center`
+    pub(crate) fn zoom(self, center: f64, factor: f64) -> Self {
+        let min = center - (center - self.min) * factor;
+        let max = center + (self.max - center) * factor;
+        // Stops zooming in before the precision runs out
+        if max - min < f64::EPSILON * 1000.0 * center.abs().max(1.0) {
+            return self;
+        }
+        PlotRange::new(min, max)
+    }
+
+    /// Moves the range by `offset`
+    pub(crate) fn pan(self, offset: f64) -> Self {
+        PlotRange::new(self.min + offset, self.max + offset)
+    }
+
+    /// Returns the range of the finite values, with a margin, or `None` if there are none
+    pub(crate) fn fit(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let (min, max) = values
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        (min <= max).then(|| {
+            let margin = (max - min) * 0.05;
+            PlotRange::new(min - margin, max + margin)
+        })
+    }
+
+    /// Returns the smallest range containing both ranges
+    pub(crate) fn union(self, other: Self) -> Self {
+        PlotRange::new(self.min.min(other.min), self.max.max(other.max))
+    }
+}
+
+/// Returns the positions of at most about `max_count` tick marks in the range, spaced by 1, 2
+/// or 5 times a power of ten, and that spacing
+pub(crate) fn ticks(range: PlotRange, max_count: usize) -> (Vec<f64>, f64) {
+    let raw_step = range.size() / max_count.max(1) as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|&step| step >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+    let first = (range.min / step).ceil() as i64;
+    let last = (range.max / step).floor() as i64;
+    let ticks = (first..=last).map(|i| i as f64 * step).collect();
+    (ticks, step)
+}
+
+/// Formats a tick mark with as many decimals as its spacing needs
+pub(crate) fn format_tick(value: f64, step: f64) -> String {
+    let value = if value.abs() < step * 1e-6 {
+        0.0
+    } else {
+        value
+    };
+    if value != 0.0 && (value.abs() >= 1e9 || step < 1e-6) {
+        return format!("{value:e}");
+    }
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    format!("{value:.decimals$}")
+}
+
+#[test]
+fn test_ticks() {
+    let (positions, step) = ticks(PlotRange::new(-0.3, 1.05), 5);
+    assert_eq!(step, 0.5);
+    assert_eq!(positions, [0.0, 0.5, 1.0]);
+    let (positions, step) = ticks(PlotRange::new(0.0, 1000.0), 4);
+    assert_eq!(step, 500.0);
+    assert_eq!(positions, [0.0, 500.0, 1000.0]);
+    assert_eq!(format_tick(0.25, 0.05), "0.25");
+    assert_eq!(format_tick(1500.0, 500.0), "1500");
+    assert_eq!(format_tick(-1e-17, 0.1), "0.0");
+    assert_eq!(format_tick(2e12, 1e11), "2e12");
+
+    let range = PlotRange::new(0.0, 10.0);
+    assert_eq!(range.zoom(5.0, 0.5), PlotRange::new(2.5, 7.5));
+    assert_eq!(range.pan(-1.0), PlotRange::new(-1.0, 9.0));
+    assert_eq!(PlotRange::new(3.0, 3.0), PlotRange::new(2.5, 3.5));
+    assert_eq!(
+        PlotRange::fit([1.0, f64::NAN, 3.0]),
+        Some(PlotRange::new(0.9, 3.1))
+    );
+    assert_eq!(PlotRange::fit([f64::NAN]), None);
+}
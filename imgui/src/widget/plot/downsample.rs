@@ -0,0 +1,96 @@
+//! Reduction of large series to the points that can be seen.
+
+use std::ops::Range;
+
+/// Returns the range of indices of the sorted `xs` between `min` and `max`, including one
+/// point on each side so that lines leaving the plot are drawn
+pub(crate) fn visible_range(xs: &[f64], min: f64, max: f64) -> Range<usize> {
+    let start = xs.partition_point(|&x| x < min).saturating_sub(1);
+    let end = (xs.partition_point(|&x| x <= max) + 1).min(xs.len());
+    start..end.max(start)
+}
+
+/// Downsamples the points of a line with sorted x values, keeping the first, lowest, highest
+/// and last points of each of `buckets` ranges of x values. The shape of the line is kept when
+/// each bucket is a pixel wide.
+pub(crate) fn min_max(xs: &[f64], ys: &[f64], indices: Range<usize>, buckets: usize) -> Vec<usize> {
+    if indices.len() <= buckets * 4 {
+        return indices.collect();
+    }
+    let first_x = xs[indices.start];
+    let width = (xs[indices.end - 1] - first_x) / buckets as f64;
+    let mut kept = Vec::with_capacity(buckets * 4);
+    let mut index = indices.start;
+    while index < indices.end {
+        let bucket = ((xs[index] - first_x) / width).floor();
+        // The last point belongs to the last bucket
+        let bucket_end = match bucket + 1.0 >= buckets as f64 {
+            true => f64::INFINITY,
+            false => first_x + (bucket + 1.0) * width,
+        };
+        let start = index;
+        let (mut lowest, mut highest) = (index, index);
+        while index < indices.end && (xs[index] < bucket_end || index == start) {
+            if ys[index] < ys[lowest] {
+                lowest = index;
+            }
+            if ys[index] > ys[highest] {
+                highest = index;
+            }
+            index += 1;
+        }
+        let mut bucket_points = [start, lowest, highest, index - 1];
+        bucket_points.sort_unstable();
+        for point in bucket_points {
+            if kept.last() != Some(&point) {
+                kept.push(point);
+            }
+        }
+    }
+    kept
+}
+
+/// Downsamples the points of a shaded band between `ys` and `ys2`, keeping the points kept by
+/// [`min_max`] for either line.
+pub(crate) fn min_max_band(
+    xs: &[f64],
+    ys: &[f64],
+    ys2: &[f64],
+    indices: Range<usize>,
+    buckets: usize,
+) -> Vec<usize> {
+    let mut kept = min_max(xs, ys, indices.clone(), buckets);
+    kept.extend(min_max(xs, ys2, indices, buckets));
+    kept.sort_unstable();
+    kept.dedup();
+    kept
+}
+
+#[test]
+fn test_downsample() {
+    let xs: Vec<f64> = (0..10).map(f64::from).collect();
+    assert_eq!(visible_range(&xs, 2.5, 5.0), 2..7);
+    assert_eq!(visible_range(&xs, -3.0, 0.0), 0..2);
+    assert_eq!(visible_range(&xs, 20.0, 30.0), 9..10);
+
+    let xs: Vec<f64> = (0..1_000_000).map(|i| i as f64 / 1000.0).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| (x * 10.0).sin()).collect();
+    let kept = min_max(&xs, &ys, 0..xs.len(), 100);
+    assert!(kept.len() <= 400);
+    assert_eq!(kept.first(), Some(&0));
+    assert_eq!(kept.last(), Some(&999_999));
+    assert!(kept.windows(2).all(|pair| pair[0] < pair[1]));
+    // The peaks are kept
+    let max = kept.iter().map(|&i| ys[i]).fold(f64::MIN, f64::max);
+    assert!(max > 0.9999);
+    assert_eq!(min_max(&xs, &ys, 10..20, 100), (10..20).collect::<Vec<_>>());
+
+    // The peaks of both lines of a band are kept
+    let ys2: Vec<f64> = xs.iter().map(|x| 2.0 + (x * 7.0).cos()).collect();
+    let kept = min_max_band(&xs, &ys, &ys2, 0..xs.len(), 100);
+    assert!(kept.len() <= 800);
+    assert!(kept.windows(2).all(|pair| pair[0] < pair[1]));
+    let max = kept.iter().map(|&i| ys2[i]).fold(f64::MIN, f64::max);
+    let min = kept.iter().map(|&i| ys2[i]).fold(f64::MAX, f64::min);
+    assert!(max > 2.9999 && min < 1.0001, "{min} {max}");
+}
@@ -0,0 +1,926 @@
+//! A plot widget with axes, a legend and mouse interaction.
+
+mod axis;
+mod downsample;
+
+pub use self::axis::PlotRange;
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use self::axis::{format_tick, ticks};
+use crate::math::MintVec2;
+use crate::sys;
+use crate::{ButtonFlags, Condition, DrawListMut, ImColor32, Key, MouseButton, StyleColor, Ui};
+
+/// Colors of the series without an explicit color
+const PALETTE: [[u8; 3]; 10] = [
+    [31, 119, 180],
+    [255, 127, 14],
+    [44, 160, 44],
+    [214, 39, 40],
+    [148, 103, 189],
+    [140, 86, 75],
+    [227, 119, 194],
+    [127, 127, 127],
+    [188, 189, 34],
+    [23, 190, 207],
+];
+
+/// Distance in pixels within which the nearest point is hovered
+const HOVER_RADIUS: f32 = 12.0;
+
+/// Builder for a plot of series of `f64` points, with axes, tick labels and a legend.
+///
+/// The plot is interactive: the mouse wheel zooms around the mouse cursor (only along one axis
+/// when the cursor is over its tick labels), dragging pans the view, double-clicking fits the
+/// view to the data and clicking a legend entry hides or shows its series. The nearest point to
+/// the mouse cursor is shown in a tooltip.
+///
+/// The view is kept between frames, keyed by the label. The view is fitted to the data when the
+/// plot is first shown, unless limits are set.
+///
+/// Line, shaded and bar series with sorted x values only draw their visible part, line and
+/// shaded series are downsampled to a few points per pixel, and bars centered on the same pixel
+/// column are drawn as one bar, so series of millions of points can be plotted every frame.
+///
+/// Created with [`Ui::plot`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let time: Vec<f64> = (0..1000).map(|i| i as f64 * 0.01).collect();
+/// let speed: Vec<f64> = time.iter().map(|t| t.sin() * 10.0 + 50.0).collect();
+/// let limit = vec![55.0; time.len()];
+/// let response = ui
+///     .plot("Telemetry")
+///     .x_label("time (s)")
+///     .y_label("speed (km/h)")
+///     .build(|plot| {
+///         plot.line("speed", &time, &speed).thickness(2.0);
+///         plot.line("limit", &time, &limit).color([1.0, 0.0, 0.0, 1.0]);
+///     });
+/// if let Some(point) = response.hovered_point {
+///     println!("hovering sample {} of series {}", point.index, point.series);
+/// }
+/// ```
+#[must_use]
+pub struct Plot<'ui, Label> {
+    label: Label,
+    size: [f32; 2],
+    x_label: Option<String>,
+    y_label: Option<String>,
+    x_limits: Option<(PlotRange, Condition)>,
+    y_limits: Option<(PlotRange, Condition)>,
+    legend: bool,
+    auto_fit: bool,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label: AsRef<str>> Plot<'ui, Label> {
+    /// Creates a plot builder. Text before `##` in the label is shown as the title.
+    pub fn new(ui: &'ui Ui, label: Label) -> Self {
+        Plot {
+            label,
+            size: [0.0, 0.0],
+            x_label: None,
+            y_label: None,
+            x_limits: None,
+            y_limits: None,
+            legend: true,
+            auto_fit: false,
+            ui,
+        }
+    }
+    /// Sets the size of the plot, including its labels.
+    ///
+    /// Zero uses the available width, or a default height of 250 pixels, and negative values
+    /// are relative to the available size.
+    pub fn size(mut self, size: impl Into<MintVec2>) -> Self {
+        self.size = size.into().into();
+        self
+    }
+    /// Sets the label of the x axis, shown below its tick labels
+    pub fn x_label(mut self, label: impl Into<String>) -> Self {
+        self.x_label = Some(label.into());
+        self
+    }
+    /// Sets the label of the y axis, shown above its tick labels
+    pub fn y_label(mut self, label: impl Into<String>) -> Self {
+        self.y_label = Some(label.into());
+        self
+    }
+    /// Sets the visible range of the x axis.
+    ///
+    /// With [`Condition::Always`], the range is set every frame and can't be changed by the
+    /// user. With the other conditions, it's set when the plot is first shown.
+    pub fn x_limits(mut self, min: f64, max: f64, condition: Condition) -> Self {
+        self.x_limits = Some((PlotRange::new(min, max), condition));
+        self
+    }
+    /// Sets the visible range of the y axis, see [`x_limits`](Self::x_limits).
+    pub fn y_limits(mut self, min: f64, max: f64, condition: Condition) -> Self {
+        self.y_limits = Some((PlotRange::new(min, max), condition));
+        self
+    }
+    /// Shows or hides the legend. Shown by default.
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+    /// Fits the view to the data every frame, e.g. for live data. Disabled by default.
+    pub fn auto_fit(mut self, auto_fit: bool) -> Self {
+        self.auto_fit = auto_fit;
+        self
+    }
+
+    /// Builds the plot, adding its series with the closure.
+    pub fn build<'d, F: FnOnce(&mut PlotContent<'d>)>(self, f: F) -> PlotResponse {
+        let ui = self.ui;
+        let mut content = PlotContent { series: Vec::new() };
+        f(&mut content);
+        let series = content.series;
+
+        let label = self.label.as_ref();
+        let title = label.split("##").next().unwrap_or_default();
+        let style = unsafe { &*sys::igGetStyle() };
+        let padding = [style.FramePadding.x, style.FramePadding.y];
+        let line_height = ui.text_line_height();
+
+        let avail = ui.content_region_avail();
+        let width = match self.size[0] {
+            width if width > 0.0 => width,
+            width => (avail[0] + width).max(1.0),
+        };
+        let height = match self.size[1] {
+            height if height > 0.0 => height,
+            height if height < 0.0 => (avail[1] + height).max(1.0),
+            _ => 250.0,
+        };
+        let frame_min = ui.cursor_screen_pos();
+        let frame_max = [frame_min[0] + width, frame_min[1] + height];
+        ui.invisible_button_flags(label, [width, height], ButtonFlags::MOUSE_BUTTON_LEFT);
+        let id = ui.new_id_str(label);
+        let hovered = ui.is_item_hovered();
+        let active = ui.is_item_active();
+        if hovered {
+            ui.set_item_key_owner(Key::MouseWheelY);
+        }
+
+        // The view is forgotten when the plot isn't drawn for a frame
+        let state = ui.widget_states.take::<PlotState>(id);
+        let new = state.is_none();
+        let mut state = state.unwrap_or_else(|| PlotState {
+            x: PlotRange::new(0.0, 1.0),
+            y: PlotRange::new(0.0, 1.0),
+            fit: true,
+            hidden: HashSet::new(),
+        });
+
+        // Layout of the plot area, inside the frame
+        let top = frame_min[1]
+            + padding[1]
+            + match title.is_empty() && self.y_label.is_none() {
+                true => 0.0,
+                false => line_height + padding[1],
+            };
+        let bottom = frame_max[1]
+            - padding[1]
+            - line_height
+            - match self.x_label {
+                Some(_) => line_height + padding[1],
+                None => 0.0,
+            };
+        let bottom = bottom.max(top + 1.0);
+        let max_y_ticks = ((bottom - top) / (line_height * 2.5)).max(1.0) as usize;
+        let (y_ticks, y_step) = ticks(state.y, max_y_ticks);
+        let y_tick_labels: Vec<String> = y_ticks.iter().map(|&y| format_tick(y, y_step)).collect();
+        let y_labels_width = y_tick_labels
+            .iter()
+            .map(|label| ui.calc_text_size(label)[0])
+            .fold(0.0, f32::max);
+        let left = frame_min[0] + padding[0] * 2.0 + y_labels_width;
+        let right = (frame_max[0] - padding[0]).max(left + 1.0);
+        let area = Area {
+            min: [left, top],
+            max: [right, bottom],
+        };
+
+        // Legend, in the top right corner of the plot area
+        let swatch_size = line_height * 0.8;
+        let legend_width = series
+            .iter()
+            .map(|series| ui.calc_text_size(&series.label)[0])
+            .fold(0.0, f32::max)
+            + swatch_size
+            + padding[0] * 3.0;
+        let legend_entries: Vec<([f32; 2], [f32; 2])> = match self.legend {
+            true => (0..series.len())
+                .map(|index| {
+                    let y = top + padding[1] * 2.0 + index as f32 * line_height;
+                    let x = right - padding[0] - legend_width;
+                    ([x, y], [x + legend_width, y + line_height])
+                })
+                .collect(),
+            false => Vec::new(),
+        };
+
+        // Mouse interaction
+        let io = ui.io();
+        let mouse = io.mouse_pos;
+        let in_x = left <= mouse[0] && mouse[0] <= right;
+        let in_y = top <= mouse[1] && mouse[1] <= bottom;
+        let in_plot = hovered && in_x && in_y;
+        let on_x_axis = hovered && in_x && mouse[1] > bottom;
+        let on_y_axis = hovered && in_y && mouse[0] < left;
+        let on_legend = legend_entries
+            .iter()
+            .position(|(min, max)| contains(*min, *max, mouse))
+            .filter(|_| in_plot);
+        if let (Some(index), true) = (on_legend, ui.is_mouse_clicked(MouseButton::Left)) {
+            let label = &series[index].label;
+            if !state.hidden.remove(label) {
+                state.hidden.insert(label.clone());
+            }
+        }
+        if io.mouse_wheel != 0.0 && (in_plot || on_x_axis || on_y_axis) {
+            let factor = 0.85f64.powf(f64::from(io.mouse_wheel));
+            let [x, y] = area.to_plot(mouse, state.x, state.y);
+            if in_plot || on_x_axis {
+                state.x = state.x.zoom(x, factor);
+            }
+            if in_plot || on_y_axis {
+                state.y = state.y.zoom(y, factor);
+            }
+        }
+        if active && ui.is_mouse_dragging(MouseButton::Left) {
+            let [dx, dy] = io.mouse_delta;
+            let [width, height] = area.size();
+            state.x = state.x.pan(-f64::from(dx / width) * state.x.size());
+            state.y = state.y.pan(f64::from(dy / height) * state.y.size());
+        }
+        if in_plot && on_legend.is_none() && ui.is_mouse_double_clicked(MouseButton::Left) {
+            state.fit = true;
+        }
+
+        // Fitting and limits
+        let visible: Vec<bool> = series
+            .iter()
+            .map(|series| !state.hidden.contains(&series.label))
+            .collect();
+        if state.fit || self.auto_fit {
+            let shown = || series.iter().zip(&visible).filter(|(_, v)| **v);
+            let x_fit = shown()
+                .filter_map(|(series, _)| series.x_extent())
+                .reduce(PlotRange::union);
+            let y_fit = shown()
+                .filter_map(|(series, _)| series.y_extent())
+                .reduce(PlotRange::union);
+            if let Some(x_fit) = x_fit {
+                state.x = x_fit;
+            }
+            if let Some(y_fit) = y_fit {
+                state.y = y_fit;
+            }
+            state.fit = false;
+        }
+        for (limits, range) in [(self.x_limits, &mut state.x), (self.y_limits, &mut state.y)] {
+            if let Some((limits, condition)) = limits {
+                if new || condition == Condition::Always {
+                    *range = limits;
+                }
+            }
+        }
+        let (x_range, y_range) = (state.x, state.y);
+        let hidden = state.hidden.clone();
+        ui.widget_states.insert(id, state);
+
+        // The ticks of the y axis may have changed, keeping the width of the labels of this frame
+        let (y_ticks, y_step) = ticks(y_range, max_y_ticks);
+        let x_labels_width = ui.calc_text_size(format_tick(x_range.max, x_range.size()))[0]
+            .max(ui.calc_text_size(format_tick(x_range.min, x_range.size()))[0]);
+        let max_x_ticks = (area.size()[0] / (x_labels_width + line_height * 2.0)).max(1.0);
+        let (x_ticks, x_step) = ticks(x_range, max_x_ticks as usize);
+
+        let draw_list = ui.get_window_draw_list();
+        let text_color = ImColor32::from(ui.style_color(StyleColor::Text));
+        let mut grid_color = ImColor32::from(ui.style_color(StyleColor::Border));
+        grid_color.a /= 2;
+        draw_list
+            .add_rect(frame_min, frame_max, ui.style_color(StyleColor::FrameBg))
+            .rounding(style.FrameRounding)
+            .filled(true)
+            .build();
+        draw_list
+            .add_rect(area.min, area.max, ui.style_color(StyleColor::WindowBg))
+            .filled(true)
+            .build();
+
+        // Grid and tick labels
+        for &x in &x_ticks {
+            let sx = area.to_screen([x, 0.0], x_range, y_range)[0].round();
+            draw_list
+                .add_line([sx, top], [sx, bottom], grid_color)
+                .build();
+            let label = format_tick(x, x_step);
+            let label_width = ui.calc_text_size(&label)[0];
+            draw_list.add_text([sx - label_width / 2.0, bottom], text_color, label);
+        }
+        for &y in &y_ticks {
+            let sy = area.to_screen([0.0, y], x_range, y_range)[1].round();
+            draw_list
+                .add_line([left, sy], [right, sy], grid_color)
+                .build();
+            let label = format_tick(y, y_step);
+            let label_width = ui.calc_text_size(&label)[0];
+            let position = [left - padding[0] - label_width, sy - line_height / 2.0];
+            draw_list.add_text(position, text_color, label);
+        }
+        draw_list
+            .add_rect(area.min, area.max, ui.style_color(StyleColor::Border))
+            .build();
+        if !title.is_empty() {
+            let title_width = ui.calc_text_size(title)[0];
+            let position = [
+                (left + right - title_width) / 2.0,
+                frame_min[1] + padding[1],
+            ];
+            draw_list.add_text(position, text_color, title);
+        }
+        if let Some(y_label) = &self.y_label {
+            let position = [frame_min[0] + padding[0], frame_min[1] + padding[1]];
+            draw_list.add_text(position, text_color, y_label);
+        }
+        if let Some(x_label) = &self.x_label {
+            let label_width = ui.calc_text_size(x_label)[0];
+            let position = [
+                (left + right - label_width) / 2.0,
+                frame_max[1] - padding[1] - line_height,
+            ];
+            draw_list.add_text(position, text_color, x_label);
+        }
+
+        // Series, clipped to the plot area
+        let colors: Vec<ImColor32> = series
+            .iter()
+            .enumerate()
+            .map(|(index, series)| {
+                series.color.unwrap_or_else(|| {
+                    let [r, g, b] = PALETTE[index % PALETTE.len()];
+                    ImColor32::from_rgb(r, g, b)
+                })
+            })
+            .collect();
+        let view = View {
+            area,
+            x: x_range,
+            y: y_range,
+        };
+        draw_list.with_clip_rect_intersect(area.min, area.max, || {
+            for (index, series) in series.iter().enumerate() {
+                if visible[index] {
+                    series.draw(&draw_list, &view, colors[index]);
+                }
+            }
+        });
+
+        // Nearest point to the mouse cursor
+        let mut hovered_point = None;
+        if in_plot && on_legend.is_none() && !(active && ui.is_mouse_dragging(MouseButton::Left)) {
+            let mut nearest_distance = HOVER_RADIUS * HOVER_RADIUS;
+            for (index, series) in series.iter().enumerate() {
+                if !visible[index] {
+                    continue;
+                }
+                if let Some((point, distance)) = series.nearest(&view, mouse) {
+                    if distance <= nearest_distance {
+                        nearest_distance = distance;
+                        hovered_point = Some(PlotPoint {
+                            series: index,
+                            index: point,
+                            x: series.xs[point],
+                            y: series.ys[point],
+                        });
+                    }
+                }
+            }
+        }
+        if let Some(point) = &hovered_point {
+            let center = view.to_screen([point.x, point.y]);
+            draw_list
+                .add_circle(center, 4.0, colors[point.series])
+                .filled(true)
+                .build();
+            draw_list.add_circle(center, 5.0, text_color).build();
+            ui.tooltip(|| {
+                ui.text(&series[point.series].label);
+                ui.text(format!(
+                    "x: {}\ny: {}",
+                    format_tick(point.x, x_step / 100.0),
+                    format_tick(point.y, y_step / 100.0)
+                ));
+            });
+        }
+
+        // Legend
+        if let (Some((first, _)), Some((_, last))) = (legend_entries.first(), legend_entries.last())
+        {
+            let min = [first[0] - padding[0], first[1] - padding[1]];
+            let max = [last[0] + padding[0], last[1] + padding[1]];
+            draw_list
+                .add_rect(min, max, ui.style_color(StyleColor::PopupBg))
+                .filled(true)
+                .build();
+            draw_list
+                .add_rect(min, max, ui.style_color(StyleColor::Border))
+                .build();
+            let disabled_color = ImColor32::from(ui.style_color(StyleColor::TextDisabled));
+            for (index, (min, _)) in legend_entries.iter().enumerate() {
+                let shown = !hidden.contains(&series[index].label);
+                let offset = (line_height - swatch_size) / 2.0;
+                let swatch_min = [min[0] + padding[0], min[1] + offset];
+                let swatch_max = [swatch_min[0] + swatch_size, swatch_min[1] + swatch_size];
+                draw_list
+                    .add_rect(swatch_min, swatch_max, colors[index])
+                    .filled(shown)
+                    .build();
+                let position = [swatch_max[0] + padding[0], min[1]];
+                let color = if shown { text_color } else { disabled_color };
+                draw_list.add_text(position, color, &series[index].label);
+            }
+        }
+
+        let mouse_position = in_plot.then(|| view.to_plot(mouse));
+        PlotResponse {
+            hovered,
+            mouse_position,
+            hovered_point,
+            x_range,
+            y_range,
+        }
+    }
+}
+
+/// The series of a plot, added in the closure of [`Plot::build`].
+///
+/// The x and y slices of a series should have the same length, extra values are ignored.
+/// Non-finite values aren't drawn.
+#[derive(Debug)]
+pub struct PlotContent<'d> {
+    series: Vec<PlotSeries<'d>>,
+}
+
+impl<'d> PlotContent<'d> {
+    /// Adds a series drawn as a line through its points
+    pub fn line(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'d [f64],
+        ys: &'d [f64],
+    ) -> &mut PlotSeries<'d> {
+        self.add(label.into(), SeriesKind::Line, xs, ys)
+    }
+    /// Adds a series drawn as a marker at each point
+    pub fn scatter(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'d [f64],
+        ys: &'d [f64],
+    ) -> &mut PlotSeries<'d> {
+        self.add(label.into(), SeriesKind::Scatter, xs, ys)
+    }
+    /// Adds a series drawn as vertical bars from zero to each point, `width` being in x units
+    pub fn bars(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'d [f64],
+        ys: &'d [f64],
+        width: f64,
+    ) -> &mut PlotSeries<'d> {
+        self.add(label.into(), SeriesKind::Bars { width }, xs, ys)
+    }
+    /// Adds a series drawn as the area between two lines, e.g. a confidence interval
+    pub fn shaded(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'d [f64],
+        ys1: &'d [f64],
+        ys2: &'d [f64],
+    ) -> &mut PlotSeries<'d> {
+        let len = ys2.len();
+        self.add(
+            label.into(),
+            SeriesKind::Shaded { ys2 },
+            &xs[..xs.len().min(len)],
+            ys1,
+        )
+    }
+
+    fn add(
+        &mut self,
+        label: String,
+        kind: SeriesKind<'d>,
+        xs: &'d [f64],
+        ys: &'d [f64],
+    ) -> &mut PlotSeries<'d> {
+        let len = xs.len().min(ys.len());
+        self.series.push(PlotSeries {
+            label,
+            kind,
+            xs: &xs[..len],
+            ys: &ys[..len],
+            sorted: xs[..len].windows(2).all(|pair| pair[0] <= pair[1]),
+            color: None,
+            thickness: 1.0,
+            marker_size: 2.5,
+        });
+        self.series.last_mut().unwrap()
+    }
+}
+
+/// A series of a plot, whose style can be changed after adding it to [`PlotContent`]
+#[derive(Clone, Debug)]
+pub struct PlotSeries<'d> {
+    label: String,
+    kind: SeriesKind<'d>,
+    xs: &'d [f64],
+    ys: &'d [f64],
+    /// The x values are in increasing order
+    sorted: bool,
+    color: Option<ImColor32>,
+    thickness: f32,
+    marker_size: f32,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum SeriesKind<'d> {
+    Line,
+    Scatter,
+    Bars { width: f64 },
+    Shaded { ys2: &'d [f64] },
+}
+
+impl PlotSeries<'_> {
+    /// Sets the color of the series. Defaults to a color of a palette, by series index.
+    pub fn color(&mut self, color: impl Into<ImColor32>) -> &mut Self {
+        self.color = Some(color.into());
+        self
+    }
+    /// Sets the thickness of lines, in pixels. Defaults to 1.
+    pub fn thickness(&mut self, thickness: f32) -> &mut Self {
+        self.thickness = thickness;
+        self
+    }
+    /// Sets the radius of scatter markers, in pixels. Defaults to 2.5.
+    pub fn marker_size(&mut self, marker_size: f32) -> &mut Self {
+        self.marker_size = marker_size;
+        self
+    }
+
+    fn x_extent(&self) -> Option<PlotRange> {
+        let range = PlotRange::fit(self.xs.iter().copied())?;
+        Some(match self.kind {
+            SeriesKind::Bars { width } => PlotRange::new(range.min - width, range.max + width),
+            _ => range,
+        })
+    }
+
+    fn y_extent(&self) -> Option<PlotRange> {
+        let ys = self.ys.iter().copied();
+        match self.kind {
+            SeriesKind::Bars { .. } => PlotRange::fit(ys.chain([0.0])),
+            SeriesKind::Shaded { ys2 } => PlotRange::fit(ys.chain(ys2.iter().copied())),
+            _ => PlotRange::fit(ys),
+        }
+    }
+
+    /// Returns the range of the points which may be visible
+    fn visible_range(&self, x: PlotRange) -> Range<usize> {
+        let margin = match self.kind {
+            SeriesKind::Bars { width } => width,
+            _ => 0.0,
+        };
+        match self.sorted {
+            true => downsample::visible_range(self.xs, x.min - margin, x.max + margin),
+            false => 0..self.xs.len(),
+        }
+    }
+
+    /// Returns the indices of the points to draw
+    fn drawn_points(&self, view: &View) -> Vec<usize> {
+        let range = self.visible_range(view.x);
+        if !self.sorted {
+            return range.collect();
+        }
+        let buckets = (view.area.size()[0].ceil() as usize).max(1);
+        match self.kind {
+            SeriesKind::Shaded { ys2 } => {
+                downsample::min_max_band(self.xs, self.ys, ys2, range, buckets)
+            }
+            _ => downsample::min_max(self.xs, self.ys, range, buckets),
+        }
+    }
+
+    fn draw(&self, draw_list: &DrawListMut<'_>, view: &View, color: ImColor32) {
+        let point = |index: usize| [self.xs[index], self.ys[index]];
+        let finite = |index: usize| self.xs[index].is_finite() && self.ys[index].is_finite();
+        match self.kind {
+            SeriesKind::Line => {
+                // Lines are broken by non-finite values
+                let mut segment = Vec::new();
+                for index in self.drawn_points(view) {
+                    if finite(index) {
+                        segment.push(view.to_screen(point(index)));
+                    } else if !segment.is_empty() {
+                        draw_polyline(
+                            draw_list,
+                            std::mem::take(&mut segment),
+                            color,
+                            self.thickness,
+                        );
+                    }
+                }
+                draw_polyline(draw_list, segment, color, self.thickness);
+            }
+            SeriesKind::Scatter => {
+                let mut last_pixel = None;
+                for index in self.visible_range(view.x).filter(|&index| finite(index)) {
+                    let center = view.to_screen(point(index));
+                    // Skips the markers drawn over the previous one
+                    let pixel = (center[0] as i32, center[1] as i32);
+                    if last_pixel == Some(pixel)
+                        || !view.area.contains_with_margin(center, self.marker_size)
+                    {
+                        continue;
+                    }
+                    last_pixel = Some(pixel);
+                    draw_list
+                        .add_circle(center, self.marker_size, color)
+                        .num_segments(8)
+                        .filled(true)
+                        .build();
+                }
+            }
+            SeriesKind::Bars { width } => {
+                let mut fill = color;
+                fill.a = (fill.a as f32 * 0.75) as u8;
+                let draw_bar = |min, max| draw_list.add_rect(min, max, fill).filled(true).build();
+                // Consecutive bars centered on the same pixel column are drawn as a single bar
+                // covering all of them, instead of being downsampled like lines
+                let mut column_bar: Option<(i32, [f32; 2], [f32; 2])> = None;
+                for index in self.visible_range(view.x).filter(|&index| finite(index)) {
+                    let [x, y] = point(index);
+                    let column = view.to_screen([x, 0.0])[0].floor() as i32;
+                    let min = view.to_screen([x - width / 2.0, y]);
+                    let max = view.to_screen([x + width / 2.0, 0.0]);
+                    let (min, max) = (
+                        [min[0].min(max[0]), min[1].min(max[1])],
+                        [min[0].max(max[0]).max(min[0] + 1.0), min[1].max(max[1])],
+                    );
+                    match &mut column_bar {
+                        Some((bar_column, bar_min, bar_max)) if *bar_column == column => {
+                            *bar_min = [bar_min[0].min(min[0]), bar_min[1].min(min[1])];
+                            *bar_max = [bar_max[0].max(max[0]), bar_max[1].max(max[1])];
+                        }
+                        _ => {
+                            if let Some((_, min, max)) = column_bar.replace((column, min, max)) {
+                                draw_bar(min, max);
+                            }
+                        }
+                    }
+                }
+                if let Some((_, min, max)) = column_bar {
+                    draw_bar(min, max);
+                }
+            }
+            SeriesKind::Shaded { ys2 } => {
+                let mut fill = color;
+                fill.a = (fill.a as f32 * 0.4) as u8;
+                let points: Vec<usize> = self
+                    .drawn_points(view)
+                    .into_iter()
+                    .filter(|&index| finite(index) && ys2[index].is_finite())
+                    .collect();
+                for pair in points.windows(2) {
+                    let a = view.to_screen(point(pair[0]));
+                    let b = view.to_screen(point(pair[1]));
+                    let c = view.to_screen([self.xs[pair[1]], ys2[pair[1]]]);
+                    let d = view.to_screen([self.xs[pair[0]], ys2[pair[0]]]);
+                    draw_list.add_triangle(a, b, c, fill).filled(true).build();
+                    draw_list.add_triangle(a, c, d, fill).filled(true).build();
+                }
+            }
+        }
+    }
+
+    /// Returns the nearest point to `mouse` and its squared distance in pixels
+    fn nearest(&self, view: &View, mouse: [f32; 2]) -> Option<(usize, f32)> {
+        let range = match self.sorted {
+            true => {
+                let min = view.to_plot([mouse[0] - HOVER_RADIUS, mouse[1]])[0];
+                let max = view.to_plot([mouse[0] + HOVER_RADIUS, mouse[1]])[0];
+                downsample::visible_range(self.xs, min, max)
+            }
+            false => 0..self.xs.len(),
+        };
+        range
+            .filter(|&index| self.xs[index].is_finite() && self.ys[index].is_finite())
+            .map(|index| {
+                let [x, y] = view.to_screen([self.xs[index], self.ys[index]]);
+                let distance = (x - mouse[0]).powi(2) + (y - mouse[1]).powi(2);
+                (index, distance)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+fn draw_polyline(
+    draw_list: &DrawListMut<'_>,
+    points: Vec<[f32; 2]>,
+    color: ImColor32,
+    thickness: f32,
+) {
+    if points.len() >= 2 {
+        draw_list
+            .add_polyline(points, color)
+            .thickness(thickness)
+            .build();
+    }
+}
+
+/// Information about a plot after building it
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotResponse {
+    /// The plot is hovered
+    pub hovered: bool,
+    /// The position of the mouse cursor in plot coordinates, if it's over the plot area
+    pub mouse_position: Option<[f64; 2]>,
+    /// The nearest point to the mouse cursor, if it's close enough
+    pub hovered_point: Option<PlotPoint>,
+    /// The visible range of the x axis
+    pub x_range: PlotRange,
+    /// The visible range of the y axis
+    pub y_range: PlotRange,
+}
+
+/// A point of a plot series
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PlotPoint {
+    /// Index of the series, in the order they were added
+    pub series: usize,
+    /// Index of the point in the series
+    pub index: usize,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A rectangle on the screen
+#[derive(Copy, Clone, Debug)]
+struct Area {
+    min: [f32; 2],
+    max: [f32; 2],
+}
+
+impl Area {
+    fn size(&self) -> [f32; 2] {
+        [self.max[0] - self.min[0], self.max[1] - self.min[1]]
+    }
+
+    fn contains_with_margin(&self, point: [f32; 2], margin: f32) -> bool {
+        let min = [self.min[0] - margin, self.min[1] - margin];
+        let max = [self.max[0] + margin, self.max[1] + margin];
+        contains(min, max, point)
+    }
+
+    fn to_screen(self, point: [f64; 2], x: PlotRange, y: PlotRange) -> [f32; 2] {
+        let [width, height] = self.size();
+        [
+            self.min[0] + ((point[0] - x.min) / x.size()) as f32 * width,
+            self.max[1] - ((point[1] - y.min) / y.size()) as f32 * height,
+        ]
+    }
+
+    fn to_plot(self, point: [f32; 2], x: PlotRange, y: PlotRange) -> [f64; 2] {
+        let [width, height] = self.size();
+        [
+            x.min + f64::from((point[0] - self.min[0]) / width) * x.size(),
+            y.min + f64::from((self.max[1] - point[1]) / height) * y.size(),
+        ]
+    }
+}
+
+/// The plot area and its visible ranges
+struct View {
+    area: Area,
+    x: PlotRange,
+    y: PlotRange,
+}
+
+impl View {
+    fn to_screen(&self, point: [f64; 2]) -> [f32; 2] {
+        self.area.to_screen(point, self.x, self.y)
+    }
+
+    fn to_plot(&self, point: [f32; 2]) -> [f64; 2] {
+        self.area.to_plot(point, self.x, self.y)
+    }
+}
+
+fn contains(min: [f32; 2], max: [f32; 2], point: [f32; 2]) -> bool {
+    min[0] <= point[0] && point[0] <= max[0] && min[1] <= point[1] && point[1] <= max[1]
+}
+
+/// View of a plot, kept between frames
+#[derive(Clone, Debug)]
+struct PlotState {
+    x: PlotRange,
+    y: PlotRange,
+    /// The view should be fitted to the data
+    fit: bool,
+    /// Labels of the hidden series
+    hidden: HashSet<String>,
+}
+
+impl Ui {
+    /// Creates a builder for an interactive plot, see [`Plot`].
+    pub fn plot<Label: AsRef<str>>(&self, label: Label) -> Plot<'_, Label> {
+        Plot::new(self, label)
+    }
+}
+
+#[test]
+fn test_plot() {
+    use crate::test::{press, release, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let xs: Vec<f64> = (0..=100_000).map(|i| i as f64 / 1000.0).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| x * 2.0).collect();
+    let frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [400.0, 300.0], |ui| {
+            ui.plot("##plot").size([-1.0, -1.0]).build(|plot| {
+                plot.line("double", &xs, &ys);
+            })
+        })
+    };
+
+    // The view is fitted to the data
+    let response = frame(&mut ctx);
+    assert_eq!(response.x_range, PlotRange::new(-5.0, 105.0));
+    assert_eq!(response.y_range, PlotRange::new(-10.0, 210.0));
+    // Zooming in around the mouse cursor
+    ctx.io_mut().mouse_pos = [200.0, 150.0];
+    frame(&mut ctx);
+    ctx.io_mut().add_mouse_wheel_event([0.0, 1.0]);
+    let response = frame(&mut ctx);
+    assert!(response.hovered);
+    let [x, y] = response.mouse_position.unwrap();
+    assert!(response.x_range.size() < 110.0 && response.x_range.contains(x));
+    assert!(response.y_range.size() < 220.0 && response.y_range.contains(y));
+    // The nearest point is hovered
+    let point = response.hovered_point.unwrap();
+    assert_eq!(point.series, 0);
+    assert!((point.x - x).abs() < response.x_range.size() * 0.1);
+    assert_eq!(point.y, point.x * 2.0);
+    // Dragging pans the view
+    press(&mut ctx, [200.0, 150.0], frame);
+    ctx.io_mut().mouse_pos = [150.0, 150.0];
+    frame(&mut ctx);
+    ctx.io_mut().mouse_pos = [100.0, 150.0];
+    let panned = frame(&mut ctx);
+    assert!(panned.x_range.min > response.x_range.min);
+    assert_eq!(panned.x_range.size(), response.x_range.size());
+    release(&mut ctx, frame);
+    // The view is forgotten when the plot isn't drawn for a frame
+    assert_eq!(ctx.frame().widget_states.len(), 1);
+    ctx.render();
+    ctx.io_mut().mouse_pos = [-1.0, -1.0];
+    assert_eq!(frame(&mut ctx).x_range, PlotRange::new(-5.0, 105.0));
+}
+
+#[test]
+fn test_plot_bars() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let xs: Vec<f64> = (0..100_000).map(f64::from).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| x % 7.0).collect();
+    let mut vertices = |count: usize| {
+        let ui = ctx.new_frame();
+        ui.window("bars")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([400.0, 300.0], crate::Condition::Always)
+            .build(|| {
+                ui.plot(format!("##bars{count}"))
+                    .size([-1.0, -1.0])
+                    .build(|plot| {
+                        plot.bars("bars", &xs[..count], &ys[..count], 1.0);
+                    });
+            });
+        ctx.render().total_vtx_count
+    };
+    // Bars on the same pixel column are drawn as one bar, instead of four points each
+    let few = vertices(10);
+    let many = vertices(xs.len());
+    assert!(many - few < 4 * 400);
+}
@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+use crate::widget::custom_widget::visible_label;
+use crate::{sys, HoveredFlags, Id, Ui};
+
+/// The type of the function returning the tooltips of the items of a [`RadioGroup`] or
+/// [`SegmentedControl`](crate::SegmentedControl), when none is set
+pub type ItemTooltipFn<V> = for<'b> fn(&'b V) -> Option<Cow<'b, str>>;
+
+impl Ui {
+    /// Renders a group of radio buttons, one per item, of which one is selected. Returns true if
+    /// another item was selected.
+    ///
+    /// Like [`Ui::combo`], `label_fn` returns the text of each item.
+    pub fn radio_group<V, L>(
+        &self,
+        label: impl AsRef<str>,
+        current_item: &mut usize,
+        items: &[V],
+        label_fn: L,
+    ) -> bool
+    where
+        for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    {
+        self.radio_group_config(label, items, label_fn)
+            .build(current_item)
+    }
+
+    /// Creates an unbuilt radio group
+    pub fn radio_group_config<'a, Label, V, L>(
+        &self,
+        label: Label,
+        items: &'a [V],
+        label_fn: L,
+    ) -> RadioGroup<'_, 'a, Label, V, L>
+    where
+        Label: AsRef<str>,
+        for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    {
+        RadioGroup {
+            label,
+            items,
+            label_fn,
+            tooltip_fn: None,
+            horizontal: false,
+            ui: self,
+        }
+    }
+}
+
+/// Builder for a group of radio buttons.
+///
+/// The visible part of the label is a heading above vertical groups, and follows horizontal
+/// ones. After [`build`](Self::build), the `is_item_*` queries apply to the whole group.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # use std::borrow::Cow;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let mut quality = 1;
+/// ui.radio_group_config("Quality", &["Low", "Medium", "High"], |item| Cow::from(*item))
+///     .horizontal(true)
+///     .build(&mut quality);
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct RadioGroup<'ui, 'a, Label, V, L, T = ItemTooltipFn<V>> {
+    label: Label,
+    items: &'a [V],
+    label_fn: L,
+    tooltip_fn: Option<T>,
+    horizontal: bool,
+    ui: &'ui Ui,
+}
+
+impl<'ui, 'a, Label, V, L, T> RadioGroup<'ui, 'a, Label, V, L, T>
+where
+    Label: AsRef<str>,
+    for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    for<'b> T: Fn(&'b V) -> Option<Cow<'b, str>>,
+{
+    /// Lays out the radio buttons on one line instead of one below the other
+    #[inline]
+    pub fn horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+    /// Sets the function returning the tooltip shown when an item is hovered, if any
+    pub fn tooltip_fn<T2>(self, tooltip_fn: T2) -> RadioGroup<'ui, 'a, Label, V, L, T2>
+    where
+        for<'b> T2: Fn(&'b V) -> Option<Cow<'b, str>>,
+    {
+        RadioGroup {
+            label: self.label,
+            items: self.items,
+            label_fn: self.label_fn,
+            tooltip_fn: Some(tooltip_fn),
+            horizontal: self.horizontal,
+            ui: self.ui,
+        }
+    }
+
+    /// Builds the radio group, selecting the item at `current_item`. Returns true if another
+    /// item was selected.
+    pub fn build(self, current_item: &mut usize) -> bool {
+        let ui = self.ui;
+        let label = self.label.as_ref();
+        let text = visible_label(label);
+
+        let _id = ui.push_id(label);
+        let group = ui.begin_group();
+        if !self.horizontal && !text.is_empty() {
+            ui.text(text);
+        }
+        let mut edited = None;
+        for (index, item) in self.items.iter().enumerate() {
+            if self.horizontal && index > 0 {
+                ui.same_line();
+            }
+            let _item_id = ui.push_id_usize(index);
+            let pressed = ui.radio_button_bool((self.label_fn)(item), *current_item == index);
+            if pressed && *current_item != index {
+                *current_item = index;
+                edited = Some(Id(unsafe { sys::igGetItemID() }));
+            }
+            let hovered = ui.is_item_hovered_with_flags(HoveredFlags::FOR_TOOLTIP);
+            if let Some(tooltip) = self.tooltip_fn.as_ref().filter(|_| hovered) {
+                if let Some(tooltip) = tooltip(item) {
+                    ui.tooltip_text(tooltip);
+                }
+            }
+        }
+        if self.horizontal && !text.is_empty() {
+            let style = unsafe { ui.style() };
+            ui.same_line_with_spacing(0.0, style.item_inner_spacing[0]);
+            ui.text(text);
+        }
+        group.end();
+        // The group only reports the edits of the item which is still active
+        if let Some(id) = edited {
+            ui.mark_item_edited(id);
+        }
+        edited.is_some()
+    }
+}
+
+#[test]
+fn test_radio_group() {
+    use crate::test::{click, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut quality = 0;
+    // Returns whether the selection changed, whether the group was edited and its rectangle
+    let frame = |ctx: &mut crate::Context, quality: &mut usize| {
+        test_frame(ctx, [200.0, 200.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            let changed = ui.radio_group("Quality", quality, &["Low", "Medium", "High"], |item| {
+                Cow::from(*item)
+            });
+            let rect = [ui.item_rect_min(), ui.item_rect_max()];
+            (changed, ui.is_item_edited(), rect)
+        })
+    };
+
+    let (changed, edited, [min, max]) = frame(&mut ctx, &mut quality);
+    assert!(!changed && !edited);
+    assert_eq!(min, [10.0, 40.0]);
+
+    // Clicking the last radio button selects it
+    let high = [min[0] + 8.0, max[1] - 8.0];
+    let (changed, edited, _) = click(&mut ctx, high, |ctx| frame(ctx, &mut quality));
+    assert!(changed && edited);
+    assert_eq!(quality, 2);
+    // Clicking it again doesn't change the selection
+    let (changed, edited, _) = click(&mut ctx, high, |ctx| frame(ctx, &mut quality));
+    assert!(!changed && !edited);
+    assert_eq!(quality, 2);
+}
@@ -0,0 +1,228 @@
+use std::borrow::Cow;
+
+use crate::widget::custom_widget::{visible_label, ButtonBehaviorFlags, CustomWidget, Rect};
+use crate::widget::radio_group::ItemTooltipFn;
+use crate::{HoveredFlags, ItemFlag, StyleColor, Ui};
+
+impl Ui {
+    /// Renders a segmented control, a row of joined buttons of which one is selected. Returns
+    /// true if another item was selected.
+    ///
+    /// Like [`Ui::combo`], `label_fn` returns the text of each item.
+    pub fn segmented_control<V, L>(
+        &self,
+        label: impl AsRef<str>,
+        current_item: &mut usize,
+        items: &[V],
+        label_fn: L,
+    ) -> bool
+    where
+        for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    {
+        self.segmented_control_config(label, items, label_fn)
+            .build(current_item)
+    }
+
+    /// Creates an unbuilt segmented control
+    pub fn segmented_control_config<'a, Label, V, L>(
+        &self,
+        label: Label,
+        items: &'a [V],
+        label_fn: L,
+    ) -> SegmentedControl<'_, 'a, Label, V, L>
+    where
+        Label: AsRef<str>,
+        for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    {
+        SegmentedControl {
+            label,
+            items,
+            label_fn,
+            tooltip_fn: None,
+            width: 0.0,
+            ui: self,
+        }
+    }
+}
+
+/// Builder for a segmented control widget.
+///
+/// Each segment is an item of its own, which can be reached with keyboard navigation. After
+/// [`build`](Self::build), the `is_item_*` queries apply to the whole control.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # use std::borrow::Cow;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let mut alignment = 0;
+/// ui.segmented_control_config("Align", &["Left", "Center", "Right"], |item| Cow::from(*item))
+///     .width(240.0)
+///     .build(&mut alignment);
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct SegmentedControl<'ui, 'a, Label, V, L, T = ItemTooltipFn<V>> {
+    label: Label,
+    items: &'a [V],
+    label_fn: L,
+    tooltip_fn: Option<T>,
+    width: f32,
+    ui: &'ui Ui,
+}
+
+impl<'ui, 'a, Label, V, L, T> SegmentedControl<'ui, 'a, Label, V, L, T>
+where
+    Label: AsRef<str>,
+    for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    for<'b> T: Fn(&'b V) -> Option<Cow<'b, str>>,
+{
+    /// Sets the width of the segments, shared equally. Zero, the default, fits each segment to
+    /// its text.
+    #[inline]
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+    /// Sets the function returning the tooltip shown when an item is hovered, if any
+    pub fn tooltip_fn<T2>(self, tooltip_fn: T2) -> SegmentedControl<'ui, 'a, Label, V, L, T2>
+    where
+        for<'b> T2: Fn(&'b V) -> Option<Cow<'b, str>>,
+    {
+        SegmentedControl {
+            label: self.label,
+            items: self.items,
+            label_fn: self.label_fn,
+            tooltip_fn: Some(tooltip_fn),
+            width: self.width,
+            ui: self.ui,
+        }
+    }
+
+    /// Builds the segmented control, selecting the item at `current_item`. Returns true if
+    /// another item was selected.
+    pub fn build(self, current_item: &mut usize) -> bool {
+        let ui = self.ui;
+        let style = unsafe { ui.style() };
+        let label = self.label.as_ref();
+        let texts: Vec<_> = self.items.iter().map(&self.label_fn).collect();
+        let widths: Vec<f32> = if self.width > 0.0 {
+            vec![self.width / texts.len() as f32; texts.len()]
+        } else {
+            texts
+                .iter()
+                .map(|text| ui.calc_text_size_with_opts(text, true, -1.0)[0])
+                .map(|width| width + style.frame_padding[0] * 2.0)
+                .collect()
+        };
+        let height = ui.frame_height();
+        let pos = ui.cursor_screen_pos();
+        let rect = Rect::from_pos_size(pos, [widths.iter().sum(), height]);
+
+        let _id = ui.push_id(label);
+        let group = ui.begin_group();
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(rect.min, rect.max, ui.style_color(StyleColor::FrameBg))
+            .filled(true)
+            .rounding(style.frame_rounding)
+            .build();
+        ui.item_size(rect, style.frame_padding[1]);
+
+        let mut edited = None;
+        let mut x = pos[0];
+        for (index, (text, width)) in texts.iter().zip(&widths).enumerate() {
+            let segment = Rect::from_pos_size([x, pos[1]], [*width, height]);
+            x += width;
+            let id = ui.new_id(index);
+            if !ui.item_add(segment, id, None, ItemFlag::empty()) {
+                continue;
+            }
+            let widget = CustomWidget::new(ui, id, segment);
+            let state = widget.button_behavior(ButtonBehaviorFlags::empty());
+            if state.pressed && *current_item != index {
+                *current_item = index;
+                widget.mark_edited();
+                edited = Some(id);
+            }
+            let hovered = ui.is_item_hovered_with_flags(HoveredFlags::FOR_TOOLTIP);
+            if let Some(tooltip) = self.tooltip_fn.as_ref().filter(|_| hovered) {
+                if let Some(tooltip) = tooltip(&self.items[index]) {
+                    ui.tooltip_text(tooltip);
+                }
+            }
+
+            let selected = *current_item == index;
+            let color = match (selected, state.held, state.hovered) {
+                (true, true, _) => Some(StyleColor::ButtonActive),
+                (true, false, true) => Some(StyleColor::ButtonHovered),
+                (true, false, false) => Some(StyleColor::Button),
+                (false, true, _) => Some(StyleColor::FrameBgActive),
+                (false, false, true) => Some(StyleColor::FrameBgHovered),
+                (false, false, false) => None,
+            };
+            if let Some(color) = color {
+                let first = index == 0;
+                let last = index + 1 == texts.len();
+                draw_list
+                    .add_rect(segment.min, segment.max, ui.style_color(color))
+                    .filled(true)
+                    .rounding(style.frame_rounding)
+                    .round_top_left(first)
+                    .round_bot_left(first)
+                    .round_top_right(last)
+                    .round_bot_right(last)
+                    .build();
+            }
+            widget.render_nav_highlight();
+            let text = visible_label(text);
+            let text_size = ui.calc_text_size(text);
+            let center = segment.center();
+            draw_list.add_text(
+                [
+                    (center[0] - text_size[0] * 0.5).max(segment.min[0]),
+                    center[1] - text_size[1] * 0.5,
+                ],
+                ui.style_color(StyleColor::Text),
+                text,
+            );
+        }
+
+        let text = visible_label(label);
+        if !text.is_empty() {
+            ui.same_line_with_spacing(0.0, style.item_inner_spacing[0]);
+            ui.text(text);
+        }
+        group.end();
+        // The group only reports the edits of the item which is still active
+        if let Some(id) = edited {
+            ui.mark_item_edited(id);
+        }
+        edited.is_some()
+    }
+}
+
+#[test]
+fn test_segmented_control() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let items = ["One", "Two", "Three"];
+    let mut current = 0;
+    let frame = |ctx: &mut crate::Context, current: &mut usize| {
+        crate::test::test_frame(ctx, [400.0, 100.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            let changed = ui
+                .segmented_control_config("Count", &items, |item| Cow::from(*item))
+                .width(300.0)
+                .build(current);
+            (changed, ui.is_item_edited())
+        })
+    };
+
+    frame(&mut ctx, &mut current);
+    let clicked = crate::test::click(&mut ctx, [250.0, 45.0], |ctx| frame(ctx, &mut current));
+    assert_eq!(clicked, (true, true));
+    assert_eq!(current, 2);
+    assert_eq!(frame(&mut ctx, &mut current), (false, false));
+}
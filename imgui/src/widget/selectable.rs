@@ -18,6 +18,8 @@ bitflags!(
         const DISABLED = sys::ImGuiSelectableFlags_Disabled;
         /// Hit testing to allow subsequent willdgets to overlap this one
         const ALLOW_OVERLAP = sys::ImGuiSelectableFlags_AllowOverlap;
+        /// Make the item displayed as hovered
+        const HIGHLIGHT = sys::ImGuiSelectableFlags_Highlight;
     }
 );
 
@@ -4,6 +4,7 @@ use std::os::raw::c_void;
 use crate::internal::DataTypeKind;
 use crate::math::MintVec2;
 use crate::sys;
+use crate::widget::format::{self, DisplayFormat, InputClamp};
 use crate::Ui;
 
 bitflags!(
@@ -85,7 +86,7 @@ impl<'ui, Label, Data, Format> Slider<'ui, Label, Data, Format>
 where
     Label: AsRef<str>,
     Data: DataTypeKind,
-    Format: AsRef<str>,
+    Format: DisplayFormat<Data>,
 {
     /// Sets the range inclusively, such that both values given
     /// are valid values which the slider can be dragged to.
@@ -112,9 +113,12 @@ where
         self.max = max;
         self
     }
-    /// Sets the display format using *a C-style printf string*
+    /// Sets the display format, using either *a C-style printf string* or a [`FormatWith`]
+    /// closure
+    ///
+    /// [`FormatWith`]: crate::FormatWith
     #[inline]
-    pub fn display_format<Format2: AsRef<str>>(
+    pub fn display_format<Format2: DisplayFormat<Data>>(
         self,
         display_format: Format2,
     ) -> Slider<'ui, Label, Data, Format2> {
@@ -137,30 +141,25 @@ where
     ///
     /// Returns true if the slider value was changed.
     pub fn build(self, value: &mut Data) -> bool {
-        unsafe {
-            let (label, display_format) = self
-                .ui
-                .scratch_txt_with_opt(self.label, self.display_format);
-
-            sys::igSliderScalar(
-                label,
-                Data::KIND as i32,
-                value as *mut Data as *mut c_void,
-                &self.min as *const Data as *const c_void,
-                &self.max as *const Data as *const c_void,
-                display_format,
-                self.flags.bits() as i32,
-            )
-        }
+        self.build_one(self.label.as_ref(), value)
     }
     /// Builds a horizontal array of multiple sliders attached to the given slice.
     ///
     /// Returns true if any slider value was changed.
     pub fn build_array(self, values: &mut [Data]) -> bool {
+        let display_format =
+            match format::native_format(self.display_format.as_ref().map(|f| f.kind())) {
+                Ok(display_format) => display_format,
+                Err(_) => {
+                    // Each value gets its own format string and text input
+                    let label = self.label.as_ref();
+                    return format::build_array(self.ui, label, values, |value| {
+                        self.build_one("", value)
+                    });
+                }
+            };
         unsafe {
-            let (label, display_format) = self
-                .ui
-                .scratch_txt_with_opt(self.label, self.display_format);
+            let (label, display_format) = self.ui.scratch_txt_with_opt(&self.label, display_format);
 
             sys::igSliderScalarN(
                 label,
@@ -174,6 +173,31 @@ where
             )
         }
     }
+    fn build_one(&self, label: &str, value: &mut Data) -> bool {
+        let input_clamp = if self.flags.contains(SliderFlags::NO_INPUT) {
+            None
+        } else {
+            Some(InputClamp::slider(self.flags.bits(), self.min, self.max))
+        };
+        format::build_scalar(
+            self.ui,
+            label,
+            value,
+            self.display_format.as_ref().map(|f| f.kind()),
+            input_clamp,
+            |label, display_format, value| unsafe {
+                sys::igSliderScalar(
+                    label,
+                    Data::KIND as i32,
+                    value as *mut Data as *mut c_void,
+                    &self.min as *const Data as *const c_void,
+                    &self.max as *const Data as *const c_void,
+                    display_format,
+                    self.flags.bits() as i32,
+                )
+            },
+        )
+    }
 }
 
 /// Builder for a vertical slider widget.
@@ -221,7 +245,7 @@ impl<Label, Data, Format> VerticalSlider<Label, Data, Format>
 where
     Label: AsRef<str>,
     Data: DataTypeKind,
-    Format: AsRef<str>,
+    Format: DisplayFormat<Data>,
 {
     /// Sets the range for the vertical slider.
     ///
@@ -240,9 +264,12 @@ where
         self.max = max;
         self
     }
-    /// Sets the display format using *a C-style printf string*
+    /// Sets the display format, using either *a C-style printf string* or a [`FormatWith`]
+    /// closure
+    ///
+    /// [`FormatWith`]: crate::FormatWith
     #[inline]
-    pub fn display_format<Format2: AsRef<str>>(
+    pub fn display_format<Format2: DisplayFormat<Data>>(
         self,
         display_format: Format2,
     ) -> VerticalSlider<Label, Data, Format2> {
@@ -265,20 +292,25 @@ where
     ///
     /// Returns true if the slider value was changed.
     pub fn build(self, ui: &Ui, value: &mut Data) -> bool {
-        unsafe {
-            let (label, display_format) = ui.scratch_txt_with_opt(self.label, self.display_format);
-
-            sys::igVSliderScalar(
-                label,
-                self.size.into(),
-                Data::KIND as i32,
-                value as *mut Data as *mut c_void,
-                &self.min as *const Data as *const c_void,
-                &self.max as *const Data as *const c_void,
-                display_format,
-                self.flags.bits() as i32,
-            )
-        }
+        format::build_scalar(
+            ui,
+            self.label.as_ref(),
+            value,
+            self.display_format.as_ref().map(|f| f.kind()),
+            None,
+            |label, display_format, value| unsafe {
+                sys::igVSliderScalar(
+                    label,
+                    self.size.into(),
+                    Data::KIND as i32,
+                    value as *mut Data as *mut c_void,
+                    &self.min as *const Data as *const c_void,
+                    &self.max as *const Data as *const c_void,
+                    display_format,
+                    self.flags.bits() as i32,
+                )
+            },
+        )
     }
 }
 
@@ -368,8 +400,9 @@ where
     ///
     /// Returns true if the slider value was changed.
     pub fn build(self, ui: &Ui, value_rad: &mut f32) -> bool {
+        let display_format = format::valid_format(self.display_format.as_ref(), f32::KIND);
         unsafe {
-            let (label, display_format) = ui.scratch_txt_two(self.label, self.display_format);
+            let (label, display_format) = ui.scratch_txt_two(self.label, display_format);
 
             sys::igSliderAngle(
                 label,
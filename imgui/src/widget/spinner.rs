@@ -0,0 +1,249 @@
+use std::f32::consts::PI;
+
+use crate::math::MintVec2;
+use crate::sys;
+use crate::widget::custom_widget::Rect;
+use crate::{Id, ImColor32, ItemFlag, StyleColor, Ui};
+
+/// Builder for an indeterminate spinner, a rotating arc showing that something is in progress.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// Spinner::new().radius(8.0).thickness(3.0).build(&ui);
+/// ui.same_line();
+/// ui.text("Loading...");
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Spinner {
+    radius: f32,
+    thickness: f32,
+    speed: f32,
+    color: Option<ImColor32>,
+}
+
+impl Spinner {
+    /// Creates a spinner as high as the text, in the [`StyleColor::CheckMark`] color
+    #[inline]
+    pub fn new() -> Self {
+        Spinner {
+            radius: 0.0,
+            thickness: 0.0,
+            speed: 1.0,
+            color: None,
+        }
+    }
+    /// Sets the radius of the spinner. Zero, the default, uses half the text line height.
+    #[inline]
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+    /// Sets the thickness of the arc. Zero, the default, uses a fifth of the radius.
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+    /// Sets the number of turns per second
+    #[inline]
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    /// Sets the color of the arc
+    #[inline]
+    pub fn color(mut self, color: impl Into<ImColor32>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Builds the spinner
+    pub fn build(self, ui: &Ui) {
+        let radius = if self.radius > 0.0 {
+            self.radius
+        } else {
+            ui.text_line_height() * 0.5
+        };
+        let thickness = if self.thickness > 0.0 {
+            self.thickness
+        } else {
+            (radius * 0.2).max(1.0)
+        };
+        let pos = ui.cursor_screen_pos();
+        let size = [radius * 2.0, radius * 2.0];
+        let rect = Rect::from_pos_size(pos, size);
+        ui.item_size(rect, 0.0);
+        if !ui.item_add(rect, Id(0), None, ItemFlag::empty()) {
+            return;
+        }
+
+        // The arc turns at a constant speed while its length grows and shrinks
+        let time = ui.time() as f32 * self.speed;
+        let start = time * PI * 2.0;
+        let length = PI * (1.0 + 0.7 * (time * PI).sin());
+        let center = rect.center();
+        let arc_radius = radius - thickness * 0.5;
+        let segments = 30;
+        let points = (0..=segments)
+            .map(|i| {
+                let angle = start + length * i as f32 / segments as f32;
+                [
+                    center[0] + angle.cos() * arc_radius,
+                    center[1] + angle.sin() * arc_radius,
+                ]
+            })
+            .collect();
+        let color = self
+            .color
+            .unwrap_or_else(|| ui.style_color(StyleColor::CheckMark).into());
+        ui.get_window_draw_list()
+            .add_polyline(points, color)
+            .thickness(thickness)
+            .build();
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for an indeterminate loading bar, a [`ProgressBar`](crate::ProgressBar) whose fill
+/// moves back and forth when the fraction of the work done isn't known.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// LoadingBar::new()
+///     .size([200.0, 0.0])
+///     .overlay_text("Connecting...")
+///     .build(&ui);
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct LoadingBar<T = &'static str> {
+    size: [f32; 2],
+    speed: f32,
+    overlay_text: Option<T>,
+}
+
+impl LoadingBar {
+    /// Creates a loading bar which fills the width of the window
+    #[inline]
+    pub fn new() -> Self {
+        LoadingBar {
+            size: [-1.0, 0.0],
+            speed: 1.0,
+            overlay_text: None,
+        }
+    }
+}
+
+impl Default for LoadingBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: AsRef<str>> LoadingBar<T> {
+    /// Sets an optional text that will be drawn over the loading bar.
+    pub fn overlay_text<T2: AsRef<str>>(self, overlay_text: T2) -> LoadingBar<T2> {
+        LoadingBar {
+            size: self.size,
+            speed: self.speed,
+            overlay_text: Some(overlay_text),
+        }
+    }
+
+    /// Sets the size of the loading bar, like [`ProgressBar::size`](crate::ProgressBar::size).
+    #[inline]
+    pub fn size(mut self, size: impl Into<MintVec2>) -> Self {
+        self.size = size.into().into();
+        self
+    }
+
+    /// Sets the speed of the animation, 1.0 being the default
+    #[inline]
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Builds the loading bar
+    #[doc(alias = "ProgressBar")]
+    pub fn build(self, ui: &Ui) {
+        // Dear ImGui animates progress bars with a negative fraction, which is the time
+        let fraction = -(ui.time() as f32 * self.speed).max(f32::MIN_POSITIVE);
+        unsafe {
+            sys::igProgressBar(
+                fraction,
+                self.size.into(),
+                ui.scratch_txt_opt(self.overlay_text),
+            );
+        }
+    }
+}
+
+#[test]
+fn test_spinner() {
+    use crate::test::test_frame;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [200.0, 100.0], |ui| {
+            Spinner::new().build(ui);
+            let default_size = ui.item_rect_size();
+            Spinner::new()
+                .radius(10.0)
+                .thickness(3.0)
+                .speed(2.0)
+                .color([1.0, 0.0, 0.0, 1.0])
+                .build(ui);
+            (ui.text_line_height(), default_size, ui.item_rect_size())
+        })
+    };
+
+    // The spinner animates without asserting across frames
+    for _ in 0..3 {
+        let (line_height, default_size, size) = frame(&mut ctx);
+        assert_eq!(default_size, [line_height, line_height]);
+        assert_eq!(size, [20.0, 20.0]);
+    }
+}
+
+#[test]
+fn test_loading_bar() {
+    use crate::test::test_frame;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [300.0, 100.0], |ui| {
+            let width = ui.content_region_avail()[0];
+            LoadingBar::new().build(ui);
+            let default_size = ui.item_rect_size();
+            LoadingBar::new()
+                .size([200.0, 0.0])
+                .speed(3.0)
+                .overlay_text("Loading")
+                .build(ui);
+            (width, ui.frame_height(), default_size, ui.item_rect_size())
+        })
+    };
+
+    // The loading bar animates without asserting across frames
+    for _ in 0..3 {
+        let (width, frame_height, default_size, size) = frame(&mut ctx);
+        // A width of -1 aligns the right edge of the bar one pixel before the window's
+        assert_eq!(default_size, [width - 1.0, frame_height]);
+        assert_eq!(size, [200.0, frame_height]);
+    }
+}
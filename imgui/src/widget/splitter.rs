@@ -0,0 +1,435 @@
+//! Splitters resizing the areas on both sides of them.
+//!
+//! [`Splitter`] is a draggable bar between areas laid out by the caller, [`SplitPanes`] lays out
+//! two child windows around one. The ratios of the split panes are saved in the `[Splitter]`
+//! sections of the `.ini` settings.
+
+use std::ffi::CStr;
+use std::fmt::Write;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+
+use crate::sys;
+use crate::widget::state::WidgetStates;
+use crate::{ChildFlags, Id, MouseButton, Ui};
+
+/// Distance in pixels around a splitter within which it's hovered
+const HOVER_EXTEND: f32 = 4.0;
+/// Delay in seconds before a hovered splitter is highlighted
+const HOVER_VISIBILITY_DELAY: f32 = 0.04;
+
+/// How a [`Splitter`] or [`SplitPanes`] divides the space
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SplitDirection {
+    /// Panes side by side, with a vertical splitter between them
+    Horizontal,
+    /// Panes above each other, with a horizontal splitter between them
+    Vertical,
+}
+
+impl SplitDirection {
+    /// Index of the axis along which the sizes are measured
+    fn axis(self) -> usize {
+        match self {
+            SplitDirection::Horizontal => 0,
+            SplitDirection::Vertical => 1,
+        }
+    }
+}
+
+/// Builder for a splitter, a bar which can be dragged to resize the areas on both sides of it.
+///
+/// The splitter is placed `size1` pixels after the cursor position and doesn't move the cursor,
+/// so the areas are laid out by the caller, e.g. with [`ChildWindow`](crate::ChildWindow)s.
+/// [`Ui::split_panes`] lays out two child windows around a splitter.
+///
+/// The splitter is drawn with the `SeparatorHovered` and `SeparatorActive` style colors when it's
+/// hovered or dragged.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let (mut tree_width, mut editor_width) = (200.0, 600.0);
+/// let spacing = ui.clone_style().item_spacing[0];
+/// ui.splitter("##sidebar", SplitDirection::Horizontal)
+///     .min_sizes(100.0, 200.0)
+///     .reset_size(200.0)
+///     .build(&mut tree_width, &mut editor_width);
+/// ui.child_window("tree").size([tree_width, 0.0]).build(|| {});
+/// ui.same_line_with_spacing(0.0, spacing);
+/// ui.child_window("editor").size([editor_width, 0.0]).build(|| {});
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Splitter<'ui, Label> {
+    label: Label,
+    direction: SplitDirection,
+    thickness: Option<f32>,
+    length: f32,
+    min_sizes: [f32; 2],
+    reset_size: Option<f32>,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label: AsRef<str>> Splitter<'ui, Label> {
+    /// Creates a splitter builder. The label is only used as an id.
+    #[doc(alias = "SplitterBehavior")]
+    pub fn new(ui: &'ui Ui, label: Label, direction: SplitDirection) -> Self {
+        Splitter {
+            label,
+            direction,
+            thickness: None,
+            length: 0.0,
+            min_sizes: [0.0, 0.0],
+            reset_size: None,
+            ui,
+        }
+    }
+    /// Sets the thickness of the splitter. Defaults to the item spacing along the split axis.
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+    /// Sets the length of the splitter.
+    ///
+    /// Defaults to zero, which uses the available space, and negative values are relative to the
+    /// available space.
+    pub fn length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+    /// Sets the minimum sizes of the areas before and after the splitter while dragging it
+    pub fn min_sizes(mut self, min_size1: f32, min_size2: f32) -> Self {
+        self.min_sizes = [min_size1, min_size2];
+        self
+    }
+    /// Sets the size of the first area when the splitter is double-clicked, keeping the sum of
+    /// both sizes. Double-clicking does nothing by default.
+    pub fn reset_size(mut self, size1: f32) -> Self {
+        self.reset_size = Some(size1);
+        self
+    }
+
+    /// Builds the splitter, returning true if the sizes were changed.
+    pub fn build(self, size1: &mut f32, size2: &mut f32) -> bool {
+        let ui = self.ui;
+        let axis = self.direction.axis();
+        let style = unsafe { &*sys::igGetStyle() };
+        let item_spacing = [style.ItemSpacing.x, style.ItemSpacing.y];
+        let thickness = self.thickness.unwrap_or(item_spacing[axis]);
+        let available = ui.content_region_avail()[1 - axis];
+        let length = match self.length {
+            length if length > 0.0 => length,
+            length => (available + length).max(1.0),
+        };
+
+        let mut min = ui.cursor_screen_pos();
+        min[axis] += *size1;
+        let mut max = min;
+        max[axis] += thickness;
+        max[1 - axis] += length;
+        let bb = sys::ImRect {
+            Min: min.into(),
+            Max: max.into(),
+        };
+        let id = ui.new_id_str(self.label);
+        let axis_flag = match self.direction {
+            SplitDirection::Horizontal => sys::ImGuiAxis_X,
+            SplitDirection::Vertical => sys::ImGuiAxis_Y,
+        };
+        let previous = (*size1, *size2);
+        unsafe {
+            sys::igSplitterBehavior(
+                bb,
+                id.0,
+                axis_flag,
+                size1,
+                size2,
+                self.min_sizes[0],
+                self.min_sizes[1],
+                HOVER_EXTEND,
+                HOVER_VISIBILITY_DELAY,
+                0,
+            );
+        }
+        if let Some(reset_size) = self.reset_size {
+            if ui.is_item_hovered() && ui.is_mouse_double_clicked(MouseButton::Left) {
+                let total = *size1 + *size2;
+                *size1 = reset_size.min(total);
+                *size2 = total - *size1;
+            }
+        }
+        (*size1, *size2) != previous
+    }
+}
+
+/// Builder for two child windows with a [`Splitter`] between them.
+///
+/// The split ratio is kept in the `.ini` settings, keyed by the label, so it's restored the next
+/// time the application runs.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// ui.split_panes("##browser", SplitDirection::Horizontal)
+///     .default_ratio(0.25)
+///     .min_sizes(100.0, 200.0)
+///     .child_flags(ChildFlags::BORDERS)
+///     .build(
+///         || ui.text("files"),
+///         || ui.text("preview"),
+///     );
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct SplitPanes<'ui, Label> {
+    label: Label,
+    direction: SplitDirection,
+    default_ratio: f32,
+    min_sizes: [f32; 2],
+    size: [f32; 2],
+    child_flags: ChildFlags,
+    ui: &'ui Ui,
+}
+
+impl<'ui, Label: AsRef<str>> SplitPanes<'ui, Label> {
+    /// Creates a builder for split panes. The label is only used as an id.
+    pub fn new(ui: &'ui Ui, label: Label, direction: SplitDirection) -> Self {
+        SplitPanes {
+            label,
+            direction,
+            default_ratio: 0.5,
+            min_sizes: [0.0, 0.0],
+            size: [0.0, 0.0],
+            child_flags: ChildFlags::empty(),
+            ui,
+        }
+    }
+    /// Sets the share of the first pane before the user moves the splitter, or when they
+    /// double-click it. Defaults to 0.5.
+    pub fn default_ratio(mut self, ratio: f32) -> Self {
+        self.default_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+    /// Sets the minimum sizes of the first and second panes, in pixels
+    pub fn min_sizes(mut self, min_size1: f32, min_size2: f32) -> Self {
+        self.min_sizes = [min_size1, min_size2];
+        self
+    }
+    /// Sets the total size of the panes.
+    ///
+    /// Zero uses the available space, and negative values are relative to the available space.
+    pub fn size(mut self, size: impl Into<crate::MintVec2>) -> Self {
+        self.size = size.into().into();
+        self
+    }
+    /// Sets the flags of both child windows, e.g. [`ChildFlags::BORDERS`]
+    pub fn child_flags(mut self, child_flags: ChildFlags) -> Self {
+        self.child_flags = child_flags;
+        self
+    }
+
+    /// Builds the panes, calling `first` and `second` inside their child windows.
+    pub fn build(self, first: impl FnOnce(), second: impl FnOnce()) {
+        let ui = self.ui;
+        let axis = self.direction.axis();
+        let label = self.label.as_ref();
+        let id = ui.new_id_str(label);
+        let style = unsafe { &*sys::igGetStyle() };
+        let thickness = [style.ItemSpacing.x, style.ItemSpacing.y][axis];
+        let avail = ui.content_region_avail();
+        let size = [0, 1].map(|i| match self.size[i] {
+            size if size > 0.0 => size,
+            size => (avail[i] + size).max(1.0),
+        });
+        let available = (size[axis] - thickness).max(0.0);
+        let [min_size1, min_size2] = self.min_sizes;
+
+        let mut ratio = ui
+            .widget_states
+            .take::<SplitRatio>(id)
+            .map_or(self.default_ratio, |ratio| ratio.0);
+        let mut size1 = ratio * available;
+        if min_size1 + min_size2 <= available {
+            size1 = size1.clamp(min_size1, available - min_size2);
+        }
+        let mut size2 = available - size1;
+        let changed = ui
+            .splitter(label, self.direction)
+            .thickness(thickness)
+            .length(size[1 - axis])
+            .min_sizes(min_size1, min_size2)
+            .reset_size(self.default_ratio * available)
+            .build(&mut size1, &mut size2);
+        if changed && available > 0.0 {
+            ratio = (size1 / available).clamp(0.0, 1.0);
+            unsafe { sys::igMarkIniSettingsDirty_Nil() };
+        }
+        ui.widget_states.insert_saved(id, SplitRatio(ratio));
+
+        let _id = ui.push_id(label);
+        let position = ui.cursor_screen_pos();
+        let mut pane_size = size;
+        pane_size[axis] = size1.max(1.0);
+        ui.child_window("##first")
+            .size(pane_size)
+            .child_flags(self.child_flags)
+            .build(first);
+        let mut second_position = position;
+        second_position[axis] += size1 + thickness;
+        ui.set_cursor_screen_pos(second_position);
+        pane_size[axis] = size2.max(1.0);
+        ui.child_window("##second")
+            .size(pane_size)
+            .child_flags(self.child_flags)
+            .build(second);
+    }
+}
+
+impl Ui {
+    /// Creates a builder for a splitter, see [`Splitter`].
+    #[doc(alias = "SplitterBehavior")]
+    pub fn splitter<Label: AsRef<str>>(
+        &self,
+        label: Label,
+        direction: SplitDirection,
+    ) -> Splitter<'_, Label> {
+        Splitter::new(self, label, direction)
+    }
+
+    /// Creates a builder for two resizable child windows, see [`SplitPanes`].
+    pub fn split_panes<Label: AsRef<str>>(
+        &self,
+        label: Label,
+        direction: SplitDirection,
+    ) -> SplitPanes<'_, Label> {
+        SplitPanes::new(self, label, direction)
+    }
+}
+
+/// Split ratio of a [`SplitPanes`], saved in a `[Splitter]` section of the `.ini` settings
+#[derive(Copy, Clone, Debug)]
+struct SplitRatio(f32);
+
+/// Registers the handler of the `[Splitter]` settings in the current context, which must not
+/// outlive `states`.
+pub(crate) unsafe fn add_settings_handler(states: &WidgetStates) {
+    let type_name = c"Splitter";
+    let handler = sys::ImGuiSettingsHandler {
+        TypeName: type_name.as_ptr(),
+        TypeHash: sys::igImHashStr(type_name.as_ptr(), 0, 0),
+        ClearAllFn: Some(clear_all),
+        ReadOpenFn: Some(read_open),
+        ReadLineFn: Some(read_line),
+        WriteAllFn: Some(write_all),
+        UserData: states as *const WidgetStates as *mut c_void,
+        ..Default::default()
+    };
+    sys::igAddSettingsHandler(&handler);
+}
+
+unsafe fn states<'a>(handler: *mut sys::ImGuiSettingsHandler) -> &'a WidgetStates {
+    &*((*handler).UserData as *const WidgetStates)
+}
+
+unsafe extern "C" fn clear_all(
+    _ctx: *mut sys::ImGuiContext,
+    handler: *mut sys::ImGuiSettingsHandler,
+) {
+    states(handler).clear_saved::<SplitRatio>();
+}
+
+unsafe extern "C" fn read_open(
+    _ctx: *mut sys::ImGuiContext,
+    handler: *mut sys::ImGuiSettingsHandler,
+    name: *const c_char,
+) -> *mut c_void {
+    let name = CStr::from_ptr(name).to_str().unwrap_or_default();
+    let id = name
+        .strip_prefix("0x")
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+    states(handler).reading.set(id);
+    match id {
+        Some(_) => (*handler).UserData,
+        None => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn read_line(
+    _ctx: *mut sys::ImGuiContext,
+    handler: *mut sys::ImGuiSettingsHandler,
+    _entry: *mut c_void,
+    line: *const c_char,
+) {
+    let states = states(handler);
+    let line = CStr::from_ptr(line).to_str().unwrap_or_default();
+    let ratio = line
+        .strip_prefix("Ratio=")
+        .and_then(|ratio| ratio.trim().parse::<f32>().ok())
+        .filter(|ratio| ratio.is_finite());
+    if let (Some(id), Some(ratio)) = (states.reading.get(), ratio) {
+        states.insert_saved(Id(id), SplitRatio(ratio.clamp(0.0, 1.0)));
+    }
+}
+
+unsafe extern "C" fn write_all(
+    _ctx: *mut sys::ImGuiContext,
+    handler: *mut sys::ImGuiSettingsHandler,
+    buf: *mut sys::ImGuiTextBuffer,
+) {
+    let mut text = String::new();
+    for (id, SplitRatio(ratio)) in states(handler).saved::<SplitRatio>() {
+        let _ = write!(text, "[Splitter][0x{id:08X}]\nRatio={ratio:.4}\n\n");
+    }
+    let range = text.as_bytes().as_ptr_range();
+    sys::ImGuiTextBuffer_append(
+        buf,
+        range.start as *const c_char,
+        range.end as *const c_char,
+    );
+}
+
+#[test]
+fn test_split_panes() {
+    use crate::test::{press, release, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let frame = |ctx: &mut crate::Context| {
+        test_frame(ctx, [420.0, 300.0], |ui| {
+            let (mut first, mut second) = (0.0, 0.0);
+            ui.split_panes("##panes", SplitDirection::Horizontal)
+                .default_ratio(0.25)
+                .build(
+                    || first = ui.window_size()[0],
+                    || second = ui.window_size()[0],
+                );
+            [first, second]
+        })
+    };
+
+    let [first, second] = frame(&mut ctx);
+    assert!((first * 3.0 - second).abs() < 1.0, "{first} {second}");
+    let mut ini = String::new();
+    ctx.save_ini_settings(&mut ini);
+    assert!(ini.contains("]\nRatio=0.2500\n"), "{ini}");
+
+    // Dragging the splitter
+    let splitter_x = frame(&mut ctx)[0] + 10.0;
+    press(&mut ctx, [splitter_x, 100.0], frame);
+    ctx.io_mut().mouse_pos = [splitter_x + 100.0, 100.0];
+    frame(&mut ctx);
+    let [dragged, _] = release(&mut ctx, frame);
+    assert_eq!(dragged, first + 100.0);
+
+    // The ratio is restored from the settings
+    let ini = ini.replace("Ratio=0.2500", "Ratio=0.7500");
+    ctx.load_ini_settings(&ini);
+    let [first, second] = frame(&mut ctx);
+    assert!((first - second * 3.0).abs() < 1.0, "{first} {second}");
+}
@@ -0,0 +1,98 @@
+//! States of the widgets kept between frames.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::sys;
+use crate::Id;
+
+/// States of the widgets kept between frames, by id.
+///
+/// The states are discarded when their widget isn't built for a frame, except the saved ones,
+/// which are kept in the `.ini` settings.
+#[derive(Default)]
+pub(crate) struct WidgetStates {
+    states: RefCell<HashMap<sys::ImGuiID, WidgetState>>,
+    /// The frame the states were last pruned in
+    pruned: Cell<i32>,
+    /// Id of the `.ini` settings section being read
+    pub(crate) reading: Cell<Option<sys::ImGuiID>>,
+}
+
+struct WidgetState {
+    /// The frame the state was last stored in, `None` for saved states
+    frame: Option<i32>,
+    value: Box<dyn Any>,
+}
+
+impl WidgetStates {
+    /// Removes the state of a widget, returning it if it has the type `T`.
+    ///
+    /// The states of the widgets that weren't built during the previous frame are discarded.
+    pub(crate) fn take<T: 'static>(&self, id: Id) -> Option<T> {
+        self.prune();
+        let state = self.states.borrow_mut().remove(&id.0)?;
+        state.value.downcast().ok().map(|value| *value)
+    }
+
+    /// Stores the state of a widget until it isn't built for a frame
+    pub(crate) fn insert<T: 'static>(&self, id: Id, value: T) {
+        let frame = unsafe { sys::igGetFrameCount() };
+        self.insert_state(id.0, Some(frame), value);
+    }
+
+    /// Stores the state of a widget saved in the `.ini` settings, kept until they're cleared
+    pub(crate) fn insert_saved<T: 'static>(&self, id: Id, value: T) {
+        self.insert_state(id.0, None, value);
+    }
+
+    /// Returns the saved states of type `T`, sorted by id
+    pub(crate) fn saved<T: Clone + 'static>(&self) -> Vec<(sys::ImGuiID, T)> {
+        let states = self.states.borrow();
+        let mut saved: Vec<_> = states
+            .iter()
+            .filter(|(_, state)| state.frame.is_none())
+            .filter_map(|(&id, state)| Some((id, state.value.downcast_ref::<T>()?.clone())))
+            .collect();
+        saved.sort_unstable_by_key(|&(id, _)| id);
+        saved
+    }
+
+    /// Removes the saved states of type `T`
+    pub(crate) fn clear_saved<T: 'static>(&self) {
+        self.states
+            .borrow_mut()
+            .retain(|_, state| state.frame.is_some() || !state.value.is::<T>());
+    }
+
+    fn insert_state<T: 'static>(&self, id: sys::ImGuiID, frame: Option<i32>, value: T) {
+        let value = Box::new(value);
+        self.states
+            .borrow_mut()
+            .insert(id, WidgetState { frame, value });
+    }
+
+    fn prune(&self) {
+        let frame = unsafe { sys::igGetFrameCount() };
+        if self.pruned.replace(frame) != frame {
+            self.states
+                .borrow_mut()
+                .retain(|_, state| state.frame.is_none_or(|built| built >= frame - 1));
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.states.borrow().len()
+    }
+}
+
+impl fmt::Debug for WidgetStates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WidgetStates")
+            .field("len", &self.states.borrow().len())
+            .finish()
+    }
+}
@@ -0,0 +1,167 @@
+use crate::math::MintVec2;
+use crate::widget::custom_widget::{visible_label, ButtonBehaviorFlags, CustomWidget, Rect};
+use crate::{ItemFlag, StyleColor, Ui};
+
+impl Ui {
+    /// Renders a toggle switch, a checkbox drawn as a sliding switch. Returns true if the value
+    /// was changed.
+    #[doc(alias = "ToggleButton")]
+    pub fn toggle(&self, label: impl AsRef<str>, value: &mut bool) -> bool {
+        self.toggle_config(label).build(value)
+    }
+
+    /// Creates an unbuilt toggle switch
+    pub fn toggle_config<Label: AsRef<str>>(&self, label: Label) -> Toggle<'_, Label> {
+        Toggle {
+            label,
+            size: [0.0, 0.0],
+            ui: self,
+        }
+    }
+}
+
+/// Builder for a toggle switch widget.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let mut vsync = true;
+/// if ui.toggle_config("VSync").size([40.0, 20.0]).build(&mut vsync) {
+///     println!("VSync is now {}", if vsync { "on" } else { "off" });
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Toggle<'ui, Label> {
+    label: Label,
+    size: [f32; 2],
+    ui: &'ui Ui,
+}
+
+impl<Label: AsRef<str>> Toggle<'_, Label> {
+    /// Sets the size of the switch, without its label.
+    ///
+    /// A zero height uses the frame height, and a zero width 1.75 times the height.
+    #[inline]
+    pub fn size(mut self, size: impl Into<MintVec2>) -> Self {
+        self.size = size.into().into();
+        self
+    }
+
+    /// Builds the toggle switch bound to `value`. Returns true if the value was changed.
+    pub fn build(self, value: &mut bool) -> bool {
+        let ui = self.ui;
+        let style = unsafe { ui.style() };
+        let label = self.label.as_ref();
+        let text = visible_label(label);
+        let text_size = ui.calc_text_size(text);
+
+        let height = if self.size[1] > 0.0 {
+            self.size[1]
+        } else {
+            ui.frame_height()
+        };
+        let width = if self.size[0] > 0.0 {
+            self.size[0]
+        } else {
+            height * 1.75
+        };
+        let pos = ui.cursor_screen_pos();
+        let track = Rect::from_pos_size(pos, [width, height]);
+        let label_width = if text.is_empty() {
+            0.0
+        } else {
+            style.item_inner_spacing[0] + text_size[0]
+        };
+        let rect = Rect::from_pos_size(pos, [width + label_width, height.max(text_size[1])]);
+
+        let id = ui.new_id_str(label);
+        ui.item_size(rect, style.frame_padding[1]);
+        if !ui.item_add(rect, id, None, ItemFlag::empty()) {
+            return false;
+        }
+        let widget = CustomWidget::new(ui, id, rect);
+        let state = widget.button_behavior(ButtonBehaviorFlags::empty());
+        if state.pressed {
+            *value = !*value;
+            widget.mark_edited();
+        }
+
+        let track_color = match (*value, state.held, state.hovered) {
+            (true, true, _) => StyleColor::ButtonActive,
+            (true, false, true) => StyleColor::ButtonHovered,
+            (true, false, false) => StyleColor::Button,
+            (false, true, _) => StyleColor::FrameBgActive,
+            (false, false, true) => StyleColor::FrameBgHovered,
+            (false, false, false) => StyleColor::FrameBg,
+        };
+        let knob_color = if *value {
+            StyleColor::CheckMark
+        } else {
+            StyleColor::Text
+        };
+        let radius = height * 0.5;
+        let knob_x = if *value {
+            track.max[0] - radius
+        } else {
+            track.min[0] + radius
+        };
+        let draw_list = ui.get_window_draw_list();
+        widget.render_nav_highlight();
+        draw_list
+            .add_rect(track.min, track.max, ui.style_color(track_color))
+            .filled(true)
+            .rounding(radius)
+            .build();
+        draw_list
+            .add_circle(
+                [knob_x, track.min[1] + radius],
+                (radius - 2.0).max(1.0),
+                ui.style_color(knob_color),
+            )
+            .filled(true)
+            .build();
+        if !text.is_empty() {
+            draw_list.add_text(
+                [
+                    track.max[0] + style.item_inner_spacing[0],
+                    pos[1] + (height - text_size[1]) * 0.5,
+                ],
+                ui.style_color(StyleColor::Text),
+                text,
+            );
+        }
+        state.pressed
+    }
+}
+
+#[test]
+fn test_toggle() {
+    use crate::test::{click, press_keys, test_frame};
+
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    ctx.io_mut().config_flags |= crate::ConfigFlags::NAV_ENABLE_KEYBOARD;
+    let mut value = false;
+    let frame = |ctx: &mut crate::Context, value: &mut bool| {
+        test_frame(ctx, [200.0, 100.0], |ui| {
+            ui.set_cursor_screen_pos([10.0, 40.0]);
+            ui.toggle_config("Enabled").size([40.0, 20.0]).build(value);
+            ui.is_item_edited()
+        })
+    };
+
+    frame(&mut ctx, &mut value);
+    assert!(click(&mut ctx, [20.0, 50.0], |ctx| frame(ctx, &mut value)));
+    assert!(value);
+    assert!(!frame(&mut ctx, &mut value));
+
+    // The toggle can be reached and switched with the keyboard
+    ctx.io_mut().mouse_pos = [-100.0, -100.0];
+    press_keys(&mut ctx, &[crate::Key::Tab, crate::Key::Space], |ctx| {
+        frame(ctx, &mut value)
+    });
+    assert!(!value);
+}
@@ -0,0 +1,247 @@
+//! Validated text and scalar inputs.
+
+use crate::sys;
+use crate::ImColor32;
+
+/// Builder for a text or scalar input whose edits are checked by a validator closure before
+/// being committed to the bound value.
+///
+/// Created with [`InputText::validate`](crate::InputText::validate) or
+/// [`InputScalar::validate`](crate::InputScalar::validate).
+///
+/// While the text is being edited, it's kept as a draft: the bound value is only updated when
+/// the input is deactivated after an edit (see
+/// [`Ui::is_item_deactivated_after_edit`](crate::Ui::is_item_deactivated_after_edit)) and the
+/// draft is valid. Invalid drafts are kept, so that the user can fix them, until the bound value
+/// is changed by the application or the input isn't built for a frame. Invalid fields get a
+/// colored frame border and show the error in a tooltip when hovered.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut name = String::from("server");
+/// let mut port = 8080u16;
+/// let mut form = Form::new();
+/// ui.input_text("Name", &mut name)
+///     .validate(|name| match name.is_empty() {
+///         true => Err("The name is required".to_owned()),
+///         false => Ok(()),
+///     })
+///     .form(&mut form)
+///     .build();
+/// ui.input_scalar("Port", &mut port)
+///     .validate(|port| match *port < 1024 {
+///         true => Err("Ports below 1024 are reserved".to_owned()),
+///         false => Ok(()),
+///     })
+///     .form(&mut form)
+///     .build();
+/// ui.disabled(!form.is_valid(), || {
+///     if ui.button("Submit") {
+///         // submit name and port
+///     }
+/// });
+/// ```
+#[must_use]
+pub struct Validated<'f, W, V> {
+    widget: W,
+    validator: V,
+    form: Option<&'f mut Form>,
+    error_color: ImColor32,
+}
+
+impl<W, V> Validated<'static, W, V> {
+    pub(crate) fn new(widget: W, validator: V) -> Self {
+        Validated {
+            widget,
+            validator,
+            form: None,
+            error_color: ImColor32::from_rgb(230, 70, 70),
+        }
+    }
+}
+
+impl<'f, W, V> Validated<'f, W, V> {
+    /// Adds the result of the validation to `form`.
+    pub fn form<'f2>(self, form: &'f2 mut Form) -> Validated<'f2, W, V> {
+        Validated {
+            widget: self.widget,
+            validator: self.validator,
+            form: Some(form),
+            error_color: self.error_color,
+        }
+    }
+    /// Sets the color of the frame border of the input when it's invalid. Defaults to red.
+    pub fn error_color(mut self, color: impl Into<ImColor32>) -> Self {
+        self.error_color = color.into();
+        self
+    }
+    /// Builds the input, validating its text.
+    ///
+    /// Returns true if a new valid value was committed to the bound value.
+    pub fn build<'ui, 'p>(self) -> bool
+    where
+        W: private::Sealed<'ui, 'p>,
+        V: FnOnce(&W::Value) -> Result<(), String>,
+    {
+        let ui = self.widget.ui();
+        let key = ui.new_id_str(self.widget.label());
+        let frame_width = ui.calc_item_width();
+        // Drafts are kept with the bound value they were edited from, and discarded when it changes
+        let mut draft = ui
+            .widget_states
+            .take::<(W::Value, W::Value)>(key)
+            .filter(|(edited_from, _)| edited_from == self.widget.value())
+            .map_or_else(|| self.widget.value().clone(), |(_, draft)| draft);
+        let value = self.widget.build_draft(&mut draft);
+        let result = (self.validator)(&draft);
+
+        let committed = result.is_ok() && ui.is_item_deactivated_after_edit() && *value != draft;
+        if committed {
+            *value = draft;
+        } else if *value != draft {
+            ui.widget_states.insert(key, (value.clone(), draft));
+        }
+
+        if let Err(message) = result {
+            let style = unsafe { &*sys::igGetStyle() };
+            let [min_x, min_y] = ui.item_rect_min();
+            let [max_x, max_y] = ui.item_rect_max();
+            // The item rectangle includes the label, on the right of the frame
+            let max_x = max_x.min(min_x + frame_width);
+            ui.get_window_draw_list()
+                .add_rect([min_x, min_y], [max_x, max_y], self.error_color)
+                .rounding(style.FrameRounding)
+                .thickness(style.FrameBorderSize.max(1.0))
+                .build();
+            if ui.is_item_hovered() {
+                ui.tooltip_text(&message);
+            }
+            if let Some(form) = self.form {
+                form.errors.push(message);
+            }
+        }
+        committed
+    }
+}
+
+pub(crate) mod private {
+    use crate::Ui;
+
+    /// An input widget whose value can be validated
+    pub trait Sealed<'ui, 'p> {
+        type Value: Clone + PartialEq + 'static;
+        fn ui(&self) -> &'ui Ui;
+        fn label(&self) -> &str;
+        fn value(&self) -> &Self::Value;
+        /// Builds the widget editing `draft` instead of its bound value, which is returned
+        fn build_draft(self, draft: &mut Self::Value) -> &'p mut Self::Value;
+    }
+}
+
+/// Aggregates the validity of the [`Validated`] inputs of a form, e.g. to disable its submit
+/// button.
+///
+/// Create a new form every frame, before building its inputs. See [`Validated`] for an example.
+#[derive(Clone, Debug, Default)]
+pub struct Form {
+    errors: Vec<String>,
+}
+
+impl Form {
+    /// Creates a form without inputs
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns true if all the inputs added to the form are valid
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+    /// Returns the errors of the inputs added to the form, in order
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+}
+
+/// Builds a validated name input, returning whether it committed a value and the errors
+#[cfg(test)]
+fn frame(ctx: &mut crate::Context, name: &mut String, focus: bool) -> (bool, Vec<String>) {
+    let ui = ctx.new_frame();
+    let mut form = Form::new();
+    let mut committed = false;
+    ui.window("form").build(|| {
+        if focus {
+            ui.set_keyboard_focus_here();
+        }
+        committed = ui
+            .input_text("name", name)
+            .validate(|name| match name.is_empty() {
+                true => Err("required".to_owned()),
+                false => Ok(()),
+            })
+            .form(&mut form)
+            .build();
+    });
+    ctx.render();
+    (committed, form.errors().to_vec())
+}
+
+#[cfg(test)]
+fn press(ctx: &mut crate::Context, name: &mut String, key: crate::Key) -> (bool, Vec<String>) {
+    crate::test::press_keys(ctx, &[key], |ctx| frame(ctx, name, false))
+}
+
+#[test]
+fn test_validated_input_text() {
+    let (_guard, mut ctx) = crate::test::test_ctx_input();
+    let mut name = String::from("server");
+    let required = vec!["required".to_owned()];
+
+    frame(&mut ctx, &mut name, false);
+    frame(&mut ctx, &mut name, true);
+    frame(&mut ctx, &mut name, false);
+    // The focused input selected all its text, erasing it makes the draft invalid
+    assert_eq!(
+        press(&mut ctx, &mut name, crate::Key::Backspace),
+        (false, required.clone())
+    );
+    assert_eq!(name, "server");
+    // Invalid drafts aren't committed and are kept after the input is deactivated
+    assert_eq!(
+        press(&mut ctx, &mut name, crate::Key::Enter),
+        (false, required.clone())
+    );
+    assert_eq!(frame(&mut ctx, &mut name, false), (false, required.clone()));
+    assert_eq!(name, "server");
+    // Until the input isn't built for a frame
+    ctx.new_frame();
+    ctx.render();
+    assert_eq!(frame(&mut ctx, &mut name, false), (false, vec![]));
+    frame(&mut ctx, &mut name, true);
+    frame(&mut ctx, &mut name, false);
+    assert_eq!(
+        press(&mut ctx, &mut name, crate::Key::Backspace),
+        (false, required.clone())
+    );
+    press(&mut ctx, &mut name, crate::Key::Enter);
+    assert_eq!(frame(&mut ctx, &mut name, false), (false, required));
+    // Or the bound value is changed
+    name = String::from("web");
+    assert_eq!(frame(&mut ctx, &mut name, false), (false, vec![]));
+
+    frame(&mut ctx, &mut name, true);
+    frame(&mut ctx, &mut name, false);
+    for c in "db".chars() {
+        ctx.io_mut().add_input_character(c);
+    }
+    assert_eq!(frame(&mut ctx, &mut name, false), (false, vec![]));
+    assert_eq!(name, "web");
+    ctx.io_mut().add_key_event(crate::Key::Enter, true);
+    assert_eq!(frame(&mut ctx, &mut name, false), (true, vec![]));
+    assert_eq!(name, "db");
+    ctx.io_mut().add_key_event(crate::Key::Enter, false);
+    assert_eq!(frame(&mut ctx, &mut name, false), (false, vec![]));
+}